@@ -0,0 +1,77 @@
+use std::hint::black_box;
+use std::time::Instant;
+
+use primitive_types::U256;
+use programming_bitcoin_in_rust::field_element::FieldElement;
+use programming_bitcoin_in_rust::montgomery::MontgomeryContext;
+use programming_bitcoin_in_rust::scalar::Scalar;
+
+const ITERATIONS: u32 = 100_000;
+
+fn bench_modulus(name: &str, p: U256) {
+    let a = FieldElement::new(p - U256::from(12345), p);
+    let b = FieldElement::new(p - U256::from(67890), p);
+
+    let start = Instant::now();
+    let mut acc = a;
+    for _ in 0..ITERATIONS {
+        acc = black_box(acc) * b;
+    }
+    let plain = start.elapsed();
+
+    let ctx = MontgomeryContext::new(p);
+    let b_mont = b.to_montgomery(&ctx);
+    let start = Instant::now();
+    let mut acc_mont = a.to_montgomery(&ctx);
+    for _ in 0..ITERATIONS {
+        acc_mont = ctx.mul(black_box(acc_mont), b_mont);
+    }
+    let montgomery = start.elapsed();
+
+    let start = Instant::now();
+    let mut acc_square = a;
+    for _ in 0..ITERATIONS {
+        acc_square = black_box(acc_square).square();
+    }
+    let square = start.elapsed();
+
+    assert_eq!(ctx.from_montgomery(acc_mont), acc);
+    black_box(acc_square);
+    println!("{}", name);
+    println!("  plain mul:      {:?} / op", plain / ITERATIONS);
+    println!("  montgomery mul: {:?} / op", montgomery / ITERATIONS);
+    println!("  square:         {:?} / op", square / ITERATIONS);
+}
+
+fn main() {
+    let p = U256::from_str_radix(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+        16,
+    )
+    .unwrap();
+    let n = U256::from_str_radix(
+        "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        16,
+    )
+    .unwrap();
+    // P は擬メルセンヌ還元、N は `%` が通常の経路
+    bench_modulus("secp256k1 P", p);
+    bench_modulus("secp256k1 N", n);
+
+    // Scalar::inverse は Montgomery 形式、FieldElement::inverse は通常の経路
+    let a = Scalar::from_be_bytes_reduced(&[0xa5; 32]);
+    let field = FieldElement::new(a.num(), n);
+    let start = Instant::now();
+    for _ in 0..100 {
+        black_box(black_box(field).inverse());
+    }
+    let plain = start.elapsed();
+    let start = Instant::now();
+    for _ in 0..100 {
+        black_box(black_box(a).inverse());
+    }
+    let montgomery = start.elapsed();
+    println!("inverse mod N");
+    println!("  plain:      {:?} / op", plain / 100);
+    println!("  montgomery: {:?} / op", montgomery / 100);
+}
@@ -0,0 +1,352 @@
+//! Adaptor signatures (a.k.a. verifiably encrypted signatures).
+//!
+//! A pre-signature is encrypted to a point `T = t * G`: anyone can check
+//! that it turns into a valid signature once `t` is known, the holder of
+//! `t` can decrypt it, and whoever sees both the pre-signature and the
+//! final signature learns `t`. This is the building block of scriptless
+//! atomic swaps and DLCs.
+//!
+//! The ECDSA variant follows the construction used by
+//! secp256k1-zkp's `ecdsa_adaptor` module, with a Chaum-Pedersen proof
+//! that the two nonce points share the same `k`; the Schnorr variant
+//! produces BIP340 signatures.
+
+use primitive_types::U256;
+
+use crate::curve::CurvePoint;
+use crate::hash::tagged_hash;
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::scalar::Scalar;
+use crate::schnorr::{challenge, SchnorrSignature};
+use crate::secret::SecretScalar;
+use crate::signature::Signature;
+use crate::xonly::XOnlyPublicKey;
+
+/// A deterministic nonce from the key, the encryption key and the message,
+/// with `aux_rand` mixed in as in BIP340.
+fn nonce(
+    tag: &str,
+    key: &Scalar,
+    encryption_key: &S256Point,
+    msg: &[u8],
+    aux_rand: &[u8; 32],
+) -> Scalar {
+    let aux = tagged_hash(tag, aux_rand);
+    let mut data: Vec<u8> = key
+        .to_be_bytes()
+        .iter()
+        .zip(aux.iter())
+        .map(|(a, b)| a ^ b)
+        .collect();
+    data.extend_from_slice(&encryption_key.sec_compressed());
+    data.extend_from_slice(msg);
+    let k = Scalar::from_be_bytes_reduced(&tagged_hash(tag, &data));
+    assert!(!k.is_zero(), "nonce hash is zero");
+    k
+}
+
+// 秘密のスカラーを掛けるので定数時間の掛け算を使う
+fn secret_mul(point: &S256Point, scalar: &Scalar) -> S256Point {
+    S256Point::from(point.mul_secret(&SecretScalar::new(scalar.num())))
+}
+
+/// A non-interactive proof that `log_G(a) == log_B(b)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+struct DleqProof {
+    e: Scalar,
+    s: Scalar,
+}
+
+impl DleqProof {
+    fn challenge(points: [&S256Point; 5]) -> Scalar {
+        let mut data = Vec::with_capacity(33 * 5);
+        for point in points {
+            data.extend_from_slice(&point.sec_compressed());
+        }
+        Scalar::from_be_bytes_reduced(&tagged_hash("DLEQ", &data))
+    }
+
+    /// Proves that `a = k * G` and `b = k * base`.
+    fn prove(
+        k: &Scalar,
+        base: &S256Point,
+        a: &S256Point,
+        b: &S256Point,
+        aux_rand: &[u8; 32],
+    ) -> Self {
+        let mut msg = a.sec_compressed().to_vec();
+        msg.extend_from_slice(&b.sec_compressed());
+        let nonce = nonce("DLEQ/nonce", k, base, &msg, aux_rand);
+        let ra = PrivateKey::new(nonce).point();
+        let rb = secret_mul(base, &nonce);
+        let e = Self::challenge([base, a, b, &ra, &rb]);
+        Self {
+            e,
+            s: nonce + e * *k,
+        }
+    }
+
+    fn verify(&self, base: &S256Point, a: &S256Point, b: &S256Point) -> bool {
+        // s * G - e * a と s * base - e * b がコミットメントに一致するか
+        let ra = S256Point::from(CurvePoint::multi_mul(&[
+            (self.s.num(), S256Point::generator().into()),
+            ((-self.e).num(), (*a).into()),
+        ]));
+        let rb = S256Point::from(CurvePoint::multi_mul(&[
+            (self.s.num(), (*base).into()),
+            ((-self.e).num(), (*b).into()),
+        ]));
+        !ra.is_infinity() && !rb.is_infinity() && Self::challenge([base, a, b, &ra, &rb]) == self.e
+    }
+}
+
+/// An ECDSA pre-signature encrypted to `T`.
+///
+/// With nonce `k`, `r_hat = k * G`, `r = k * T` and
+/// `s_hat = (z + r.x * d) / k`; the final signature is `(r.x, s_hat / t)`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct EcdsaAdaptorSignature {
+    r: S256Point,
+    r_hat: S256Point,
+    s_hat: Scalar,
+    proof: DleqProof,
+}
+
+impl EcdsaAdaptorSignature {
+    /// Pre-signs the message hash `z` with `key`, encrypted to
+    /// `encryption_key`.
+    pub fn encrypt(
+        key: &PrivateKey,
+        z: U256,
+        encryption_key: &S256Point,
+        aux_rand: &[u8; 32],
+    ) -> Self {
+        let z_bytes = Scalar::reduce(z).to_be_bytes();
+        let k = nonce(
+            "ECDSAAdaptor/nonce",
+            &key.to_scalar(),
+            encryption_key,
+            &z_bytes,
+            aux_rand,
+        );
+        let r_hat = PrivateKey::new(k).point();
+        let r = secret_mul(encryption_key, &k);
+        let rx = Scalar::reduce(r.x().expect("never the point at infinity").num());
+        let s_hat = (Scalar::reduce(z) + rx * key.to_scalar()) * k.inverse();
+        assert!(!rx.is_zero() && !s_hat.is_zero(), "degenerate nonce");
+
+        Self {
+            r,
+            r_hat,
+            s_hat,
+            proof: DleqProof::prove(&k, encryption_key, &r_hat, &r, aux_rand),
+        }
+    }
+
+    /// Checks that decrypting with the discrete log of `encryption_key`
+    /// yields a valid signature of `z` under `pubkey`.
+    pub fn verify(&self, pubkey: &S256Point, z: U256, encryption_key: &S256Point) -> bool {
+        let rx = match self.r.x() {
+            Some(x) => Scalar::reduce(x.num()),
+            None => return false,
+        };
+        if rx.is_zero() || self.s_hat.is_zero() {
+            return false;
+        }
+        if !self.proof.verify(encryption_key, &self.r_hat, &self.r) {
+            return false;
+        }
+
+        // 通常の検証と同じ式で R.x ではなく r_hat そのものと比べる
+        let s_inv = self.s_hat.inverse();
+        let point = S256Point::from(CurvePoint::multi_mul(&[
+            (
+                (Scalar::reduce(z) * s_inv).num(),
+                S256Point::generator().into(),
+            ),
+            ((rx * s_inv).num(), (*pubkey).into()),
+        ]));
+        point == self.r_hat
+    }
+
+    /// The final low-S signature, using the discrete log of the encryption
+    /// key.
+    pub fn decrypt(&self, decryption_key: &PrivateKey) -> Signature {
+        let rx = Scalar::reduce(self.r.x().expect("never the point at infinity").num());
+        let s = self.s_hat * decryption_key.to_scalar().inverse();
+        Signature::new(rx.num(), s.num()).normalize_s()
+    }
+
+    /// Extracts the decryption key from the final signature `sig`, or
+    /// `None` if `sig` was not decrypted from this pre-signature.
+    pub fn recover(&self, sig: &Signature, encryption_key: &S256Point) -> Option<PrivateKey> {
+        if sig.s.is_zero() || Scalar::try_new(sig.s).is_err() {
+            return None;
+        }
+        let t = self.s_hat * Scalar::new(sig.s).inverse();
+        // low-S に正規化されていれば -t になっている
+        [t, -t]
+            .into_iter()
+            .map(PrivateKey::new)
+            .find(|key| key.point() == *encryption_key)
+    }
+}
+
+/// A BIP340 pre-signature encrypted to `T`.
+///
+/// The nonce point of the final signature is `r` itself, `k * G + T`
+/// (negated if its y is odd), and `s = s_hat + t` or `s_hat - t`
+/// accordingly.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SchnorrAdaptorSignature {
+    r: S256Point,
+    s_hat: Scalar,
+}
+
+impl SchnorrAdaptorSignature {
+    /// Pre-signs `msg` with `key`, encrypted to `encryption_key`.
+    pub fn encrypt(
+        key: &PrivateKey,
+        msg: &[u8],
+        encryption_key: &S256Point,
+        aux_rand: &[u8; 32],
+    ) -> Self {
+        let point = key.point();
+        let pubkey = XOnlyPublicKey::from_point(&point).expect("never the point at infinity");
+        let d = if point.has_even_y() {
+            key.to_scalar()
+        } else {
+            -key.to_scalar()
+        };
+
+        let k = nonce("SchnorrAdaptor/nonce", &d, encryption_key, msg, aux_rand);
+        let r = PrivateKey::new(k).point() + *encryption_key;
+        assert!(!r.is_infinity(), "degenerate nonce");
+        // 最終的な R は偶数の y を持つので、奇数なら k ごと反転する
+        let k = if r.has_even_y() { k } else { -k };
+        let e = challenge(&r.x().expect("checked above"), &pubkey, msg);
+
+        Self {
+            r,
+            s_hat: k + e * d,
+        }
+    }
+
+    /// Checks that decrypting with the discrete log of `encryption_key`
+    /// yields a valid BIP340 signature of `msg` under `pubkey`.
+    pub fn verify(&self, pubkey: &XOnlyPublicKey, msg: &[u8], encryption_key: &S256Point) -> bool {
+        let rx = match self.r.x() {
+            Some(x) => x,
+            None => return false,
+        };
+        let e = challenge(&rx, pubkey, msg);
+
+        // s_hat * G - e * P が ±(R - T) に一致するか
+        let point = S256Point::from(CurvePoint::multi_mul(&[
+            (self.s_hat.num(), S256Point::generator().into()),
+            ((-e).num(), pubkey.lift_x().into()),
+        ]));
+        let expected = self.r - *encryption_key;
+        if self.r.has_even_y() {
+            point == expected
+        } else {
+            point == -expected
+        }
+    }
+
+    pub fn decrypt(&self, decryption_key: &PrivateKey) -> SchnorrSignature {
+        let t = decryption_key.to_scalar();
+        SchnorrSignature {
+            r: self.r.x().expect("never the point at infinity"),
+            s: if self.r.has_even_y() {
+                self.s_hat + t
+            } else {
+                self.s_hat - t
+            },
+        }
+    }
+
+    /// Extracts the decryption key from the final signature `sig`, or
+    /// `None` if `sig` was not decrypted from this pre-signature.
+    pub fn recover(
+        &self,
+        sig: &SchnorrSignature,
+        encryption_key: &S256Point,
+    ) -> Option<PrivateKey> {
+        if self.r.x() != Some(sig.r) {
+            return None;
+        }
+        let t = if self.r.has_even_y() {
+            sig.s - self.s_hat
+        } else {
+            self.s_hat - sig.s
+        };
+        if t.is_zero() {
+            return None;
+        }
+        let key = PrivateKey::new(t);
+        (key.point() == *encryption_key).then_some(key)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{EcdsaAdaptorSignature, SchnorrAdaptorSignature};
+    use crate::hash::hash256;
+    use crate::private_key::PrivateKey;
+    use crate::scalar::Scalar;
+    use crate::schnorr;
+    use crate::xonly::XOnlyPublicKey;
+    use primitive_types::U256;
+
+    #[test]
+    fn ecdsa_adaptor() {
+        let key = PrivateKey::new(Scalar::from(12345));
+        let adaptor = PrivateKey::new(Scalar::from(67890));
+        let encryption_key = adaptor.point();
+        let z = U256::from_big_endian(&hash256(b"atomic swap"));
+
+        let pre = EcdsaAdaptorSignature::encrypt(&key, z, &encryption_key, &[0; 32]);
+        assert!(pre.verify(&key.point(), z, &encryption_key));
+        assert!(!pre.verify(&key.point(), z + 1, &encryption_key));
+        assert!(!pre.verify(&adaptor.point(), z, &encryption_key));
+        assert!(!pre.verify(&key.point(), z, &key.point()));
+
+        let sig = pre.decrypt(&adaptor);
+        assert!(sig.is_low_s());
+        assert!(key.point().verify(z, &sig));
+        let recovered = pre.recover(&sig, &encryption_key).unwrap();
+        assert_eq!(recovered.secret(), adaptor.secret());
+
+        // 無関係な署名からは取り出せない
+        assert!(pre.recover(&key.sign(z), &encryption_key).is_none());
+    }
+
+    #[test]
+    fn schnorr_adaptor() {
+        // 公開鍵の y の偶奇が異なる鍵も試す
+        for secret in [3u64, 12345] {
+            let key = PrivateKey::new(Scalar::from(secret));
+            let pubkey = XOnlyPublicKey::from_point(&key.point()).unwrap();
+            for aux in 0..4u8 {
+                let adaptor = PrivateKey::new(Scalar::from(1000 + aux as u64));
+                let encryption_key = adaptor.point();
+                let msg = b"discreet log contract";
+
+                let pre = SchnorrAdaptorSignature::encrypt(&key, msg, &encryption_key, &[aux; 32]);
+                assert!(pre.verify(&pubkey, msg, &encryption_key));
+                assert!(!pre.verify(&pubkey, b"other", &encryption_key));
+                assert!(!pre.verify(&pubkey, msg, &key.point()));
+
+                let sig = pre.decrypt(&adaptor);
+                assert!(schnorr::verify(&pubkey, msg, &sig));
+                let recovered = pre.recover(&sig, &encryption_key).unwrap();
+                assert_eq!(recovered.secret(), adaptor.secret());
+
+                let unrelated = schnorr::sign(&key, msg, &[aux; 32]);
+                assert!(pre.recover(&unrelated, &encryption_key).is_none());
+            }
+        }
+    }
+}
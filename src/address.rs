@@ -0,0 +1,464 @@
+//! Bitcoin addresses of the standard output types.
+//!
+//! Legacy types are Base58Check of a version byte and a hash; segwit types
+//! are Bech32 (version 0) or Bech32m (version 1, taproot) witness programs.
+//! Signet and regtest share testnet's Base58 version bytes, and signet
+//! shares its `tb` prefix, so such addresses parse as `Testnet`.
+
+use std::fmt;
+use std::str::FromStr;
+
+use sha2::{Digest, Sha256};
+
+use crate::base58::{decode_base58check, encode_base58check};
+use crate::bech32::{decode_segwit_address, encode_segwit_address};
+use crate::error::{AddressError, ParseError};
+use crate::hash::hash160;
+use crate::network::Network;
+use crate::s256_point::S256Point;
+use crate::script::{
+    Script, ScriptType, OP_0, OP_1, OP_CHECKSIG, OP_DUP, OP_EQUAL, OP_EQUALVERIFY, OP_HASH160,
+};
+use crate::xonly::XOnlyPublicKey;
+
+/// An address, i.e. a human-friendly encoding of a standard
+/// `script_pubkey` for a given network.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Address {
+    /// Pay to public key hash: `hash160` of a SEC public key.
+    P2pkh { hash: [u8; 20], network: Network },
+    /// Pay to script hash: `hash160` of a redeem script.
+    P2sh { hash: [u8; 20], network: Network },
+    /// Pay to witness public key hash: `hash160` of a compressed key.
+    P2wpkh { program: [u8; 20], network: Network },
+    /// Pay to witness script hash: `sha256` of a witness script.
+    P2wsh { program: [u8; 32], network: Network },
+    /// Pay to taproot: the x coordinate of the tweaked output key.
+    ///
+    /// Kept as raw bytes because consensus does not check that it is on
+    /// the curve; outputs with an invalid key are unspendable, but they
+    /// exist and still have an address.
+    P2tr { program: [u8; 32], network: Network },
+}
+
+impl Address {
+    /// P2SH address of `redeem_script`, e.g. to wrap a multisig script.
+    pub fn p2sh(redeem_script: &Script, network: Network) -> Self {
+        Address::P2sh {
+            hash: hash160(redeem_script.as_bytes()),
+            network,
+        }
+    }
+
+    /// Native segwit v0 address (`bc1q...`) of `key`, committing to the
+    /// `hash160` of its compressed SEC encoding.
+    ///
+    /// Panics for the point at infinity.
+    pub fn p2wpkh(key: &S256Point, network: Network) -> Self {
+        Address::P2wpkh {
+            program: key.identifier(),
+            network,
+        }
+    }
+
+    /// `p2wpkh` from a SEC-encoded key. Uncompressed keys are rejected:
+    /// outputs paying to their hash can never be spent under BIP143
+    /// policy.
+    pub fn p2wpkh_from_sec(sec: &[u8], network: Network) -> Result<Self, AddressError> {
+        let key = S256Point::parse(sec).map_err(|_| AddressError::InvalidKey)?;
+        if sec.len() != 33 {
+            return Err(AddressError::UncompressedKey);
+        }
+        Ok(Self::p2wpkh(&key, network))
+    }
+
+    /// Native segwit v0 address of `witness_script`, whose single SHA256
+    /// (not `hash160`) is the 32-byte witness program.
+    pub fn p2wsh(witness_script: &Script, network: Network) -> Self {
+        Address::P2wsh {
+            program: Sha256::digest(witness_script.as_bytes()).into(),
+            network,
+        }
+    }
+
+    /// Taproot address (`bc1p...`) of a tweaked output key, e.g.
+    /// `TaprootSpendInfo::output_key`, encoded as a Bech32m version 1
+    /// witness program.
+    pub fn p2tr(output_key: XOnlyPublicKey, network: Network) -> Self {
+        Address::P2tr {
+            program: output_key.serialize(),
+            network,
+        }
+    }
+
+    pub fn network(&self) -> Network {
+        match *self {
+            Address::P2pkh { network, .. }
+            | Address::P2sh { network, .. }
+            | Address::P2wpkh { network, .. }
+            | Address::P2wsh { network, .. }
+            | Address::P2tr { network, .. } => network,
+        }
+    }
+
+    /// The `script_pubkey` of outputs paying to this address.
+    pub fn script_pubkey(&self) -> Script {
+        let mut ret = Vec::with_capacity(34);
+        match self {
+            Address::P2pkh { hash, .. } => {
+                ret.extend_from_slice(&[OP_DUP, OP_HASH160, 20]);
+                ret.extend_from_slice(hash);
+                ret.extend_from_slice(&[OP_EQUALVERIFY, OP_CHECKSIG]);
+            }
+            Address::P2sh { hash, .. } => {
+                ret.extend_from_slice(&[OP_HASH160, 20]);
+                ret.extend_from_slice(hash);
+                ret.push(OP_EQUAL);
+            }
+            Address::P2wpkh { program, .. } => {
+                ret.extend_from_slice(&[OP_0, 20]);
+                ret.extend_from_slice(program);
+            }
+            Address::P2wsh { program, .. } => {
+                ret.extend_from_slice(&[OP_0, 32]);
+                ret.extend_from_slice(program);
+            }
+            Address::P2tr { program, .. } => {
+                ret.extend_from_slice(&[OP_1, 32]);
+                ret.extend_from_slice(program);
+            }
+        }
+        Script::new(ret)
+    }
+
+    /// The address of a `script_pubkey`, so an explorer can label outputs.
+    ///
+    /// P2PK and `OP_RETURN` outputs are standard but have no address; they
+    /// are reported as `NoAddress` with their type.
+    pub fn from_script(script: &Script, network: Network) -> Result<Self, AddressError> {
+        let bytes = script.as_bytes();
+        let ret = match script.script_type() {
+            ScriptType::P2pkh => Address::P2pkh {
+                hash: bytes[3..23].try_into().expect("20 bytes"),
+                network,
+            },
+            ScriptType::P2sh => Address::P2sh {
+                hash: bytes[2..22].try_into().expect("20 bytes"),
+                network,
+            },
+            ScriptType::P2wpkh => Address::P2wpkh {
+                program: bytes[2..].try_into().expect("20 bytes"),
+                network,
+            },
+            ScriptType::P2wsh => Address::P2wsh {
+                program: bytes[2..].try_into().expect("32 bytes"),
+                network,
+            },
+            ScriptType::P2tr => Address::P2tr {
+                program: bytes[2..].try_into().expect("32 bytes"),
+                network,
+            },
+            script_type @ (ScriptType::P2pk | ScriptType::NullData) => {
+                return Err(AddressError::NoAddress(script_type))
+            }
+            ScriptType::NonStandard => return Err(AddressError::NonStandardScript),
+        };
+        Ok(ret)
+    }
+
+    fn from_base58(s: &str) -> Result<Self, ParseError> {
+        let payload = decode_base58check(s)?;
+        if payload.len() != 21 {
+            return Err(ParseError::InvalidLength);
+        }
+        let hash = payload[1..].try_into().expect("20 bytes");
+        if let Some(network) = Network::from_p2pkh_prefix(payload[0]) {
+            Ok(Address::P2pkh { hash, network })
+        } else if let Some(network) = Network::from_p2sh_prefix(payload[0]) {
+            Ok(Address::P2sh { hash, network })
+        } else {
+            Err(ParseError::InvalidPrefix)
+        }
+    }
+
+    fn from_segwit(s: &str, network: Network) -> Result<Self, ParseError> {
+        let (_, version, program) = decode_segwit_address(s)?;
+        match (version, program.len()) {
+            (0, 20) => Ok(Address::P2wpkh {
+                program: program.try_into().expect("20 bytes"),
+                network,
+            }),
+            (0, 32) => Ok(Address::P2wsh {
+                program: program.try_into().expect("32 bytes"),
+                network,
+            }),
+            (1, 32) => Ok(Address::P2tr {
+                program: program.try_into().expect("32 bytes"),
+                network,
+            }),
+            // 将来のバージョンや長さは扱わない
+            _ => Err(ParseError::InvalidPrefix),
+        }
+    }
+}
+
+impl FromStr for Address {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // 既知の hrp で始まれば segwit、そうでなければ Base58Check
+        let segwit_network = s
+            .rfind('1')
+            .and_then(|separator| Network::from_bech32_hrp(&s[..separator].to_ascii_lowercase()));
+        match segwit_network {
+            Some(network) => Self::from_segwit(s, network),
+            None => Self::from_base58(s),
+        }
+    }
+}
+
+impl fmt::Display for Address {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let base58 = |prefix: u8, hash: &[u8; 20]| {
+            let mut payload = vec![prefix];
+            payload.extend_from_slice(hash);
+            encode_base58check(&payload)
+        };
+        let segwit = |network: Network, version: u8, program: &[u8]| {
+            encode_segwit_address(network.bech32_hrp(), version, program)
+                .expect("standard programs are valid")
+        };
+        let s = match self {
+            Address::P2pkh { hash, network } => base58(network.p2pkh_prefix(), hash),
+            Address::P2sh { hash, network } => base58(network.p2sh_prefix(), hash),
+            Address::P2wpkh { program, network } => segwit(*network, 0, program),
+            Address::P2wsh { program, network } => segwit(*network, 0, program),
+            Address::P2tr { program, network } => segwit(*network, 1, program),
+        };
+        f.write_str(&s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Address;
+    use crate::error::{AddressError, ParseError};
+    use crate::hex;
+    use crate::network::Network;
+    use crate::s256_point::S256Point;
+    use crate::script::{Script, ScriptType};
+    use crate::taproot::TaprootSpendInfo;
+    use crate::xonly::XOnlyPublicKey;
+
+    #[test]
+    fn parse_and_display() {
+        let cases = [
+            (
+                "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs",
+                Network::Mainnet,
+                "76a914f54a5851e9372b87810a8e60cdd2e7cfd80b6e3188ac",
+            ),
+            (
+                "3CLoMMyuoDQTPRD3XYZtCvgvkadrAdvdXh",
+                Network::Mainnet,
+                "a91474d691da1574e6b3c192ecfb52cc8984ee7b6c5687",
+            ),
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+                Network::Mainnet,
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                Network::Testnet,
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                Network::Mainnet,
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+        for (s, network, script) in cases {
+            let address: Address = s.parse().unwrap();
+            assert_eq!(address.to_string(), s);
+            assert_eq!(address.network(), network);
+
+            let script = Script::new(hex::decode(script).unwrap());
+            assert_eq!(address.script_pubkey(), script);
+            assert_eq!(Address::from_script(&script, network), Ok(address));
+        }
+
+        // 大文字の bech32 も受け付け、表示は小文字
+        let upper: Address = "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4"
+            .parse()
+            .unwrap();
+        assert_eq!(
+            upper.to_string(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+    }
+
+    #[test]
+    fn p2sh() {
+        // 本の第 8 章の 2-of-2 マルチシグ
+        let redeem_script = Script::new(
+            hex::decode(
+                "5221022626e955ea6ea6d98850c994f9107b036b1334f18ca8830bfff1295d21cfdb70\
+                 2103b287eaf122eea69030a0e9feed096bed8045c8b98bec453e1ffac7fbdbd4bb7152ae",
+            )
+            .unwrap(),
+        );
+        let address = Address::p2sh(&redeem_script, Network::Mainnet);
+        assert_eq!(address.to_string(), "3CLoMMyuoDQTPRD3XYZtCvgvkadrAdvdXh");
+
+        let testnet = Address::p2sh(&redeem_script, Network::Testnet);
+        assert_eq!(testnet.to_string(), "2N3u1R6uwQfuobCqbCgBkpsgBxvr1tZpe7B");
+    }
+
+    #[test]
+    fn p2wpkh() {
+        // BIP173 の例: 秘密鍵 1 の公開鍵 G
+        let g = S256Point::generator();
+        let address = Address::p2wpkh(&g, Network::Mainnet);
+        assert_eq!(
+            address.to_string(),
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+        );
+        assert_eq!(
+            Address::p2wpkh(&g, Network::Testnet).to_string(),
+            "tb1qw508d6qejxtdg4y5r3zarvary0c5xw7kxpjzsx"
+        );
+
+        assert_eq!(
+            Address::p2wpkh_from_sec(&g.sec_compressed(), Network::Mainnet),
+            Ok(address)
+        );
+        assert_eq!(
+            Address::p2wpkh_from_sec(&g.sec_uncompressed(), Network::Mainnet),
+            Err(AddressError::UncompressedKey)
+        );
+        assert_eq!(
+            Address::p2wpkh_from_sec(&[0x05; 33], Network::Mainnet),
+            Err(AddressError::InvalidKey)
+        );
+    }
+
+    #[test]
+    fn p2wsh() {
+        // BIP173 の例: <G> OP_CHECKSIG
+        let mut script = vec![0x21];
+        script.extend_from_slice(&S256Point::generator().sec_compressed());
+        script.push(0xac);
+        let script = Script::new(script);
+
+        assert_eq!(
+            Address::p2wsh(&script, Network::Mainnet).to_string(),
+            "bc1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3qccfmv3"
+        );
+        assert_eq!(
+            Address::p2wsh(&script, Network::Testnet).to_string(),
+            "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7"
+        );
+    }
+
+    #[test]
+    fn p2tr() {
+        // BIP341 wallet-test-vectors の scriptPubKey 0
+        let info = TaprootSpendInfo::new(
+            XOnlyPublicKey::parse(
+                &hex::decode("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d")
+                    .unwrap(),
+            )
+            .unwrap(),
+            None,
+        )
+        .unwrap();
+        let address = Address::p2tr(info.output_key, Network::Mainnet);
+        assert_eq!(
+            address.to_string(),
+            "bc1p2wsldez5mud2yam29q22wgfh9439spgduvct83k3pm50fcxa5dps59h4z5"
+        );
+        assert_eq!(
+            address.script_pubkey().as_bytes(),
+            hex::decode("512053a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+                .unwrap()
+        );
+    }
+
+    #[test]
+    fn networks() {
+        let mainnet: Address = "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs".parse().unwrap();
+        let Address::P2pkh { hash, .. } = mainnet else {
+            panic!("not P2PKH");
+        };
+        for network in Network::ALL {
+            let address = Address::P2pkh { hash, network };
+            let parsed: Address = address.to_string().parse().unwrap();
+            // signet と regtest の Base58 は testnet として戻る
+            let expected = match network {
+                Network::Mainnet => Network::Mainnet,
+                _ => Network::Testnet,
+            };
+            assert_eq!(parsed.network(), expected);
+        }
+
+        let regtest = Address::P2wpkh {
+            program: [7; 20],
+            network: Network::Regtest,
+        };
+        assert!(regtest.to_string().starts_with("bcrt1q"));
+        assert_eq!(regtest.to_string().parse::<Address>(), Ok(regtest));
+    }
+
+    #[test]
+    fn errors() {
+        // segwit v2 はまだ扱わない
+        assert_eq!(
+            "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs".parse::<Address>(),
+            Err(ParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t5".parse::<Address>(),
+            Err(ParseError::InvalidChecksum)
+        );
+        assert_eq!(
+            "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt".parse::<Address>(),
+            Err(ParseError::InvalidChecksum)
+        );
+        // WIF は 21 バイトではない
+        assert_eq!(
+            "5HueCGU8rMjxEXxiPuD5BDku4MkFqeZyd4dZ1jvhTVqvbTLvyTJ".parse::<Address>(),
+            Err(ParseError::InvalidLength)
+        );
+
+        let op_return = Script::new(vec![0x6a, 0x04, 1, 2, 3, 4]);
+        assert_eq!(
+            Address::from_script(&op_return, Network::Mainnet),
+            Err(AddressError::NoAddress(ScriptType::NullData))
+        );
+        let mut p2pk = vec![0x21];
+        p2pk.extend_from_slice(&S256Point::generator().sec_compressed());
+        p2pk.push(0xac);
+        assert_eq!(
+            Address::from_script(&Script::new(p2pk), Network::Mainnet),
+            Err(AddressError::NoAddress(ScriptType::P2pk))
+        );
+        // x が曲線上にない taproot 出力も使えないだけでアドレスはある
+        let mut p2tr = vec![0x51, 0x20];
+        p2tr.extend_from_slice(&[0; 32]);
+        let address = Address::from_script(&Script::new(p2tr), Network::Mainnet).unwrap();
+        assert_eq!(
+            address,
+            Address::P2tr {
+                program: [0; 32],
+                network: Network::Mainnet
+            }
+        );
+        assert_eq!(address.to_string().parse::<Address>(), Ok(address));
+        assert_eq!(
+            Address::from_script(&Script::new(vec![0x51]), Network::Mainnet),
+            Err(AddressError::NonStandardScript)
+        );
+    }
+}
@@ -0,0 +1,162 @@
+//! Base58 and Base58Check, the encodings of legacy addresses, WIF keys and
+//! extended keys.
+//!
+//! Base58 drops `0`, `O`, `I` and `l` to avoid look-alike characters, and
+//! Base58Check appends the first 4 bytes of `hash256` of the payload so
+//! typos are caught.
+
+use crate::error::ParseError;
+use crate::hash::hash256;
+
+pub(crate) const ALPHABET: &[u8; 58] =
+    b"123456789ABCDEFGHJKLMNPQRSTUVWXYZabcdefghijkmnopqrstuvwxyz";
+
+/// Base58 encoding of `data`; every leading zero byte becomes a `1`.
+pub fn encode_base58(data: &[u8]) -> String {
+    let zeros = data.iter().take_while(|&&b| b == 0).count();
+
+    // 58 進の桁を下位から溜める
+    let mut digits: Vec<u8> = Vec::with_capacity(data.len() * 138 / 100 + 1);
+    for &byte in &data[zeros..] {
+        let mut carry = byte as u32;
+        for digit in digits.iter_mut() {
+            carry += (*digit as u32) << 8;
+            *digit = (carry % 58) as u8;
+            carry /= 58;
+        }
+        while carry > 0 {
+            digits.push((carry % 58) as u8);
+            carry /= 58;
+        }
+    }
+
+    let mut ret = String::with_capacity(zeros + digits.len());
+    ret.extend(std::iter::repeat_n('1', zeros));
+    ret.extend(digits.iter().rev().map(|&d| ALPHABET[d as usize] as char));
+    ret
+}
+
+/// Inverse of `encode_base58`.
+pub fn decode_base58(s: &str) -> Result<Vec<u8>, ParseError> {
+    let zeros = s.bytes().take_while(|&c| c == b'1').count();
+
+    // 256 進の桁を下位から溜める
+    let mut bytes: Vec<u8> = Vec::with_capacity(s.len() * 733 / 1000 + 1);
+    for c in s.bytes().skip(zeros) {
+        let mut carry = ALPHABET
+            .iter()
+            .position(|&a| a == c)
+            .ok_or(ParseError::InvalidCharacter)? as u32;
+        for byte in bytes.iter_mut() {
+            carry += *byte as u32 * 58;
+            *byte = carry as u8;
+            carry >>= 8;
+        }
+        while carry > 0 {
+            bytes.push(carry as u8);
+            carry >>= 8;
+        }
+    }
+
+    let mut ret = vec![0u8; zeros];
+    ret.extend(bytes.iter().rev());
+    Ok(ret)
+}
+
+/// Base58 of `data` followed by the first 4 bytes of its `hash256`.
+pub fn encode_base58check(data: &[u8]) -> String {
+    let mut payload = data.to_vec();
+    payload.extend_from_slice(&hash256(data)[..4]);
+    encode_base58(&payload)
+}
+
+/// Decodes Base58Check and returns the payload without the checksum.
+pub fn decode_base58check(s: &str) -> Result<Vec<u8>, ParseError> {
+    let mut payload = decode_base58(s)?;
+    if payload.len() < 4 {
+        return Err(ParseError::InvalidLength);
+    }
+    let checksum = payload.split_off(payload.len() - 4);
+    if hash256(&payload)[..4] != checksum[..] {
+        return Err(ParseError::InvalidChecksum);
+    }
+    Ok(payload)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base58, decode_base58check, encode_base58, encode_base58check};
+    use crate::error::ParseError;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn base58() {
+        // 本の練習問題
+        let cases = [
+            (
+                "7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d",
+                "9MA8fRQrT4u8Zj8ZRd6MAiiyaxb2Y1CMpvVkHQu5hVM6",
+            ),
+            (
+                "eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c",
+                "4fE3H2E6XMp4SsxtwinF7w9a34ooUrwWe4WsW1458Pd",
+            ),
+            (
+                "c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6",
+                "EQJsjkd6JaGwxrjEhfeqPenqHwrBmPQZjJGNSCHBkcF7",
+            ),
+        ];
+        for (bytes, encoded) in cases {
+            assert_eq!(encode_base58(&hex(bytes)), encoded);
+            assert_eq!(decode_base58(encoded).unwrap(), hex(bytes));
+        }
+
+        assert_eq!(encode_base58(&[0, 0, 1]), "112");
+        assert_eq!(decode_base58("112").unwrap(), vec![0, 0, 1]);
+        assert_eq!(encode_base58(&[]), "");
+        assert_eq!(decode_base58("0OIl"), Err(ParseError::InvalidCharacter));
+        assert_eq!(decode_base58("1é"), Err(ParseError::InvalidCharacter));
+    }
+
+    #[test]
+    fn leading_zeros() {
+        // 先頭のゼロバイトは 1 文字ずつ '1' になり、そのまま戻る
+        for zeros in 0..5 {
+            for tail in [&[][..], &[0][..], &[1, 0, 0][..], &[0xff; 8][..]] {
+                let mut data = vec![0u8; zeros];
+                data.extend_from_slice(tail);
+                let encoded = encode_base58(&data);
+                let ones = encoded.bytes().take_while(|&c| c == b'1').count();
+                assert_eq!(ones, data.iter().take_while(|&&b| b == 0).count());
+                assert_eq!(decode_base58(&encoded).unwrap(), data);
+                assert_eq!(
+                    decode_base58check(&encode_base58check(&data)).unwrap(),
+                    data
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn base58check() {
+        let data = hex("00f54a5851e9372b87810a8e60cdd2e7cfd80b6e31");
+        let encoded = encode_base58check(&data);
+
+        assert_eq!(encoded, "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs");
+        assert_eq!(decode_base58check(&encoded).unwrap(), data);
+
+        let mut tampered = encoded.into_bytes();
+        tampered[5] = if tampered[5] == b'2' { b'3' } else { b'2' };
+        assert_eq!(
+            decode_base58check(std::str::from_utf8(&tampered).unwrap()),
+            Err(ParseError::InvalidChecksum)
+        );
+        assert_eq!(decode_base58check("11"), Err(ParseError::InvalidLength));
+    }
+}
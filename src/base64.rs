@@ -0,0 +1,85 @@
+use crate::error::ParseError;
+
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+
+/// Standard Base64 (RFC 4648) with `=` padding, as used for signed
+/// messages.
+pub fn encode_base64(data: &[u8]) -> String {
+    let mut ret = String::with_capacity(data.len().div_ceil(3) * 4);
+    for chunk in data.chunks(3) {
+        let b = [
+            chunk[0],
+            *chunk.get(1).unwrap_or(&0),
+            *chunk.get(2).unwrap_or(&0),
+        ];
+        let n = (b[0] as u32) << 16 | (b[1] as u32) << 8 | b[2] as u32;
+        for i in 0..4 {
+            if i <= chunk.len() {
+                ret.push(ALPHABET[(n >> (18 - 6 * i)) as usize & 0x3f] as char);
+            } else {
+                ret.push('=');
+            }
+        }
+    }
+    ret
+}
+
+/// Inverse of `encode_base64`; the input must be padded.
+pub fn decode_base64(s: &str) -> Result<Vec<u8>, ParseError> {
+    let bytes = s.as_bytes();
+    if !bytes.len().is_multiple_of(4) {
+        return Err(ParseError::InvalidLength);
+    }
+
+    let mut ret = Vec::with_capacity(bytes.len() / 4 * 3);
+    for (i, chunk) in bytes.chunks(4).enumerate() {
+        let last = i == bytes.len() / 4 - 1;
+        let padding = chunk.iter().rev().take_while(|&&c| c == b'=').count();
+        if padding > 2 || (padding > 0 && !last) {
+            return Err(ParseError::InvalidCharacter);
+        }
+
+        let mut n = 0u32;
+        for &c in &chunk[..4 - padding] {
+            let value = ALPHABET
+                .iter()
+                .position(|&a| a == c)
+                .ok_or(ParseError::InvalidCharacter)?;
+            n = n << 6 | value as u32;
+        }
+        n <<= 6 * padding;
+        ret.extend_from_slice(&n.to_be_bytes()[1..4 - padding]);
+    }
+    Ok(ret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode_base64, encode_base64};
+    use crate::error::ParseError;
+
+    #[test]
+    fn rfc4648_vectors() {
+        let cases = [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ];
+        for (data, encoded) in cases {
+            assert_eq!(encode_base64(data.as_bytes()), encoded);
+            assert_eq!(decode_base64(encoded).unwrap(), data.as_bytes());
+        }
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(decode_base64("Zm9"), Err(ParseError::InvalidLength));
+        assert_eq!(decode_base64("Zm9*"), Err(ParseError::InvalidCharacter));
+        assert_eq!(decode_base64("Zg==Zm9v"), Err(ParseError::InvalidCharacter));
+        assert_eq!(decode_base64("Z==="), Err(ParseError::InvalidCharacter));
+    }
+}
@@ -0,0 +1,364 @@
+//! Bech32 (BIP173) and Bech32m (BIP350), the encodings of segwit and
+//! taproot addresses.
+//!
+//! A string is a human-readable part (`bc`, `tb`, ...), the separator `1`
+//! and 5-bit values followed by a 6-character BCH checksum. Bech32m only
+//! changes the constant the checksum is XORed with; segwit version 0
+//! programs use Bech32 and versions 1 to 16 use Bech32m.
+
+use crate::error::ParseError;
+
+const CHARSET: &[u8; 32] = b"qpzry9x8gf2tvdw0s3jn54khce6mua7l";
+
+const GENERATOR: [u32; 5] = [
+    0x3b6a_57b2,
+    0x2650_8e6d,
+    0x1ea1_19fa,
+    0x3d42_33dd,
+    0x2a14_62b3,
+];
+
+// BIP173 のチェックサムに使う GF(32) 上の BCH 符号
+fn polymod(values: &[u8]) -> u32 {
+    let mut chk = 1u32;
+    for &value in values {
+        let top = chk >> 25;
+        chk = (chk & 0x1ff_ffff) << 5 ^ value as u32;
+        for (i, generator) in GENERATOR.iter().enumerate() {
+            if (top >> i) & 1 == 1 {
+                chk ^= generator;
+            }
+        }
+    }
+    chk
+}
+
+fn hrp_expand(hrp: &str) -> Vec<u8> {
+    let mut ret: Vec<u8> = hrp.bytes().map(|b| b >> 5).collect();
+    ret.push(0);
+    ret.extend(hrp.bytes().map(|b| b & 31));
+    ret
+}
+
+/// Regroups bits, e.g. bytes into the 5-bit groups bech32 encodes.
+/// Without `pad`, leftover bits must be fewer than `from` and all zero.
+pub fn convert_bits(data: &[u8], from: u32, to: u32, pad: bool) -> Option<Vec<u8>> {
+    let mut acc = 0u32;
+    let mut bits = 0u32;
+    let mut ret = Vec::new();
+    let max = (1 << to) - 1;
+    for &value in data {
+        if value as u32 >> from != 0 {
+            return None;
+        }
+        acc = acc << from | value as u32;
+        bits += from;
+        while bits >= to {
+            bits -= to;
+            ret.push((acc >> bits & max) as u8);
+        }
+    }
+    if pad {
+        if bits > 0 {
+            ret.push((acc << (to - bits) & max) as u8);
+        }
+    } else if bits >= from || (acc << (to - bits)) & max != 0 {
+        return None;
+    }
+    Some(ret)
+}
+
+/// The checksum flavour.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Variant {
+    /// BIP173, for segwit version 0.
+    Bech32,
+    /// BIP350, for segwit versions 1 to 16.
+    Bech32m,
+}
+
+impl Variant {
+    fn constant(self) -> u32 {
+        match self {
+            Variant::Bech32 => 1,
+            Variant::Bech32m => 0x2bc8_30a3,
+        }
+    }
+}
+
+/// Encodes the human-readable part and 5-bit `data` with a checksum of
+/// the given variant. The result is lowercase.
+pub fn encode(hrp: &str, data: &[u8], variant: Variant) -> Result<String, ParseError> {
+    if hrp.is_empty() || hrp.len() > 83 || hrp.len() + data.len() + 7 > 90 {
+        return Err(ParseError::InvalidLength);
+    }
+    if hrp.bytes().any(|b| !(33..=126).contains(&b)) || data.iter().any(|&d| d > 31) {
+        return Err(ParseError::InvalidCharacter);
+    }
+    let hrp = hrp.to_ascii_lowercase();
+
+    let mut values = hrp_expand(&hrp);
+    values.extend_from_slice(data);
+    values.extend_from_slice(&[0; 6]);
+    let checksum = polymod(&values) ^ variant.constant();
+
+    let mut ret = hrp;
+    ret.push('1');
+    ret.extend(data.iter().map(|&d| CHARSET[d as usize] as char));
+    ret.extend((0..6).map(|i| CHARSET[(checksum >> (5 * (5 - i)) & 31) as usize] as char));
+    Ok(ret)
+}
+
+/// Decodes a Bech32 or Bech32m string into its lowercase human-readable
+/// part, the 5-bit data without the checksum and the variant whose
+/// checksum matched.
+pub fn decode(s: &str) -> Result<(String, Vec<u8>, Variant), ParseError> {
+    if s.len() > 90 {
+        return Err(ParseError::InvalidLength);
+    }
+    // 大文字と小文字の混在は不可
+    if s.bytes().any(|b| b.is_ascii_lowercase()) && s.bytes().any(|b| b.is_ascii_uppercase()) {
+        return Err(ParseError::InvalidCharacter);
+    }
+    if s.bytes().any(|b| !(33..=126).contains(&b)) {
+        return Err(ParseError::InvalidCharacter);
+    }
+    let s = s.to_ascii_lowercase();
+    let separator = s.rfind('1').ok_or(ParseError::InvalidCharacter)?;
+    let (hrp, data) = (&s[..separator], &s[separator + 1..]);
+    if hrp.is_empty() || data.len() < 6 {
+        return Err(ParseError::InvalidLength);
+    }
+
+    let data = data
+        .bytes()
+        .map(|c| {
+            CHARSET
+                .iter()
+                .position(|&a| a == c)
+                .map(|value| value as u8)
+                .ok_or(ParseError::InvalidCharacter)
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+
+    let mut values = hrp_expand(hrp);
+    values.extend_from_slice(&data);
+    let variant = match polymod(&values) {
+        c if c == Variant::Bech32.constant() => Variant::Bech32,
+        c if c == Variant::Bech32m.constant() => Variant::Bech32m,
+        _ => return Err(ParseError::InvalidChecksum),
+    };
+    Ok((hrp.to_string(), data[..data.len() - 6].to_vec(), variant))
+}
+
+/// Encodes a segwit address: Bech32 for `version` 0 and Bech32m for 1 to
+/// 16.
+pub fn encode_segwit_address(hrp: &str, version: u8, program: &[u8]) -> Result<String, ParseError> {
+    check_witness_program(version, program)?;
+    let variant = if version == 0 {
+        Variant::Bech32
+    } else {
+        Variant::Bech32m
+    };
+    let mut data = vec![version];
+    data.extend(convert_bits(program, 8, 5, true).expect("bytes always regroup"));
+    encode(hrp, &data, variant)
+}
+
+/// Decodes a segwit address into its human-readable part, witness version
+/// and witness program, checking that the checksum variant matches the
+/// version.
+pub fn decode_segwit_address(s: &str) -> Result<(String, u8, Vec<u8>), ParseError> {
+    let (hrp, data, variant) = decode(s)?;
+    let (&version, data) = data.split_first().ok_or(ParseError::InvalidLength)?;
+    if version > 16 {
+        return Err(ParseError::InvalidPrefix);
+    }
+    // v0 は Bech32、v1 以降は Bech32m でなければならない
+    if (version == 0) != (variant == Variant::Bech32) {
+        return Err(ParseError::InvalidChecksum);
+    }
+    let program = convert_bits(data, 5, 8, false).ok_or(ParseError::InvalidLength)?;
+    check_witness_program(version, &program)?;
+    Ok((hrp, version, program))
+}
+
+fn check_witness_program(version: u8, program: &[u8]) -> Result<(), ParseError> {
+    if version > 16 {
+        return Err(ParseError::InvalidPrefix);
+    }
+    if !(2..=40).contains(&program.len())
+        || (version == 0 && program.len() != 20 && program.len() != 32)
+    {
+        return Err(ParseError::InvalidLength);
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        convert_bits, decode, decode_segwit_address, encode, encode_segwit_address, Variant,
+    };
+    use crate::error::ParseError;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn checksums() {
+        // BIP173 と BIP350 の有効な文字列
+        for (s, variant) in [
+            ("A12UEL5L", Variant::Bech32),
+            ("a12uel5l", Variant::Bech32),
+            (
+                "abcdef1qpzry9x8gf2tvdw0s3jn54khce6mua7lmqqqxw",
+                Variant::Bech32,
+            ),
+            ("?1ezyfcl", Variant::Bech32),
+            ("A1LQFN3A", Variant::Bech32m),
+            (
+                "abcdef1l7aum6echk45nj3s0wdvt2fg8x9yrzpqzd3ryx",
+                Variant::Bech32m,
+            ),
+            ("?1v759aa", Variant::Bech32m),
+        ] {
+            let (hrp, data, decoded) = decode(s).unwrap();
+            assert_eq!(decoded, variant);
+            assert_eq!(
+                encode(&hrp, &data, variant).unwrap(),
+                s.to_ascii_lowercase()
+            );
+        }
+
+        assert_eq!(decode("a12uel5m"), Err(ParseError::InvalidChecksum));
+        assert_eq!(decode("A12uEL5L"), Err(ParseError::InvalidCharacter));
+        assert_eq!(decode("pzry9x0s0muk"), Err(ParseError::InvalidCharacter));
+        assert_eq!(decode("1pzry9x0s0muk"), Err(ParseError::InvalidLength));
+        assert_eq!(decode("li1dgmt3"), Err(ParseError::InvalidLength));
+        assert_eq!(
+            encode("", &[], Variant::Bech32),
+            Err(ParseError::InvalidLength)
+        );
+        assert_eq!(
+            encode("a", &[32], Variant::Bech32),
+            Err(ParseError::InvalidCharacter)
+        );
+    }
+
+    #[test]
+    fn segwit_addresses() {
+        // BIP350 の有効なアドレス
+        let cases = [
+            (
+                "BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4",
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            (
+                "tb1qrp33g0q5c5txsp9arysrx4k6zdkfs4nce4xj0gdcccefvpysxf3q0sl5k7",
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+            ),
+            (
+                "bc1pw508d6qejxtdg4y5r3zarvary0c5xw7kw508d6qejxtdg4y5r3zarvary0c5xw7kt5nd6y",
+                "5128751e76e8199196d454941c45d1b3a323f1433bd6751e76e8199196d454941c45d1b3a323f1433bd6",
+            ),
+            ("BC1SW50QGDZ25J", "6002751e"),
+            (
+                "bc1zw508d6qejxtdg4y5r3zarvaryvaxxpcs",
+                "5210751e76e8199196d454941c45d1b3a323",
+            ),
+            (
+                "tb1pqqqqp399et2xygdj5xreqhjjvcmzhxw4aywxecjdzew6hylgvsesf3hn0c",
+                "5120000000c4a5cad46221b2a187905e5266362b99d5e91c6ce24d165dab93e86433",
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqzk5jj0",
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+            ),
+        ];
+        for (address, script) in cases {
+            let script = hex(script);
+            let (hrp, version, program) = decode_segwit_address(address).unwrap();
+            // scriptPubKey は OP_n (v0 は OP_0) とプッシュしたプログラム
+            let expected_version = if script[0] == 0 { 0 } else { script[0] - 0x50 };
+            assert_eq!(version, expected_version);
+            assert_eq!(program, &script[2..]);
+            assert_eq!(
+                encode_segwit_address(&hrp, version, &program).unwrap(),
+                address.to_ascii_lowercase()
+            );
+        }
+    }
+
+    #[test]
+    fn invalid_segwit_addresses() {
+        // BIP350 の無効なアドレス
+        let cases = [
+            // v1 なのに Bech32
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vqh2y7hd",
+                ParseError::InvalidChecksum,
+            ),
+            // v0 なのに Bech32m
+            (
+                "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kemeawh",
+                ParseError::InvalidChecksum,
+            ),
+            (
+                "bc1p38j9r5y49hruaue7wxjce0updqjuyyx0kh56v8s25huc6995vvpql3jow4",
+                ParseError::InvalidCharacter,
+            ),
+            (
+                "BC130XLXVLHEMJA6C4DQV22UAPCTQUPFHLXM9H8Z3K2E72Q4K9HCZ7VQ7ZWS8R",
+                ParseError::InvalidPrefix,
+            ),
+            ("bc1pw5dgrnzv", ParseError::InvalidLength),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v8n0nx0muaewav253zgeav",
+                ParseError::InvalidLength,
+            ),
+            (
+                "BC1QR508D6QEJXTDG4Y5R3ZARVARYV98GJ9P",
+                ParseError::InvalidLength,
+            ),
+            (
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vq47Zagq",
+                ParseError::InvalidCharacter,
+            ),
+            (
+                "bc1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7v07qwwzcrf",
+                ParseError::InvalidLength,
+            ),
+            (
+                "tb1p0xlxvlhemja6c4dqv22uapctqupfhlxm9h8z3k2e72q4k9hcz7vpggkg4j",
+                ParseError::InvalidLength,
+            ),
+            ("bc1gmk9yu", ParseError::InvalidLength),
+        ];
+        for (address, err) in cases {
+            assert_eq!(decode_segwit_address(address), Err(err), "{}", address);
+        }
+
+        assert_eq!(
+            encode_segwit_address("bc", 0, &[0; 21]),
+            Err(ParseError::InvalidLength)
+        );
+        assert_eq!(
+            encode_segwit_address("bc", 17, &[0; 32]),
+            Err(ParseError::InvalidPrefix)
+        );
+    }
+
+    #[test]
+    fn regroup_bits() {
+        assert_eq!(convert_bits(&[0xff], 8, 5, true).unwrap(), vec![31, 28]);
+        assert_eq!(convert_bits(&[31, 28], 5, 8, false).unwrap(), vec![0xff]);
+        // 余りのビットが 0 でない
+        assert_eq!(convert_bits(&[31, 29], 5, 8, false), None);
+        assert_eq!(convert_bits(&[32], 5, 8, false), None);
+    }
+}
@@ -0,0 +1,303 @@
+//! BIP322 "simple" signed messages for P2WPKH and key-path P2TR addresses.
+//!
+//! The message is committed to by a virtual `to_spend` transaction paying
+//! the address, and the signature is the witness of a virtual `to_sign`
+//! transaction spending it, so a message is signed exactly like a real
+//! spend from that address. Addresses whose spends need a script (P2SH,
+//! P2WSH, taproot script paths) are not supported.
+
+use primitive_types::U256;
+use sha2::{Digest, Sha256};
+
+use crate::base64::{decode_base64, encode_base64};
+use crate::bech32::decode_segwit_address;
+use crate::encoding::encode_varint;
+use crate::hash::{hash160, hash256, tagged_hash};
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::schnorr::{self, SchnorrSignature};
+use crate::signature::Signature;
+use crate::taproot::TaprootSpendInfo;
+use crate::xonly::XOnlyPublicKey;
+
+const SIGHASH_DEFAULT: u8 = 0x00;
+const SIGHASH_ALL: u8 = 0x01;
+
+/// `tagged_hash("BIP0322-signed-message", msg)`.
+pub fn message_hash(msg: &[u8]) -> [u8; 32] {
+    tagged_hash("BIP0322-signed-message", msg)
+}
+
+fn p2wpkh_script(program: &[u8; 20]) -> Vec<u8> {
+    let mut ret = vec![0x00, 0x14];
+    ret.extend_from_slice(program);
+    ret
+}
+
+fn p2tr_script(output_key: &XOnlyPublicKey) -> Vec<u8> {
+    let mut ret = vec![0x51, 0x20];
+    ret.extend_from_slice(&output_key.serialize());
+    ret
+}
+
+/// txid of `to_spend`: version 0, one input spending the null outpoint with
+/// `OP_0 PUSH32 message_hash`, one zero-value output to `script_pubkey`.
+fn to_spend_txid(script_pubkey: &[u8], msg: &[u8]) -> [u8; 32] {
+    let mut tx = Vec::new();
+    tx.extend_from_slice(&0u32.to_le_bytes());
+    tx.push(1);
+    tx.extend_from_slice(&[0; 32]);
+    tx.extend_from_slice(&0xffff_ffffu32.to_le_bytes());
+    tx.extend_from_slice(&[0x22, 0x00, 0x20]);
+    tx.extend_from_slice(&message_hash(msg));
+    tx.extend_from_slice(&0u32.to_le_bytes());
+    tx.push(1);
+    tx.extend_from_slice(&0u64.to_le_bytes());
+    tx.extend_from_slice(&encode_varint(script_pubkey.len() as u64));
+    tx.extend_from_slice(script_pubkey);
+    tx.extend_from_slice(&0u32.to_le_bytes());
+    hash256(&tx)
+}
+
+/// BIP143 signature hash of `to_sign`, whose only input spends output 0 of
+/// `to_spend` and whose only output is a zero-value `OP_RETURN`.
+fn to_sign_sighash(program: &[u8; 20], msg: &[u8]) -> U256 {
+    let mut outpoint = to_spend_txid(&p2wpkh_script(program), msg).to_vec();
+    outpoint.extend_from_slice(&0u32.to_le_bytes());
+
+    // P2WPKH の scriptCode は同じ鍵ハッシュの P2PKH スクリプト
+    let mut script_code = vec![0x19, 0x76, 0xa9, 0x14];
+    script_code.extend_from_slice(program);
+    script_code.extend_from_slice(&[0x88, 0xac]);
+
+    let mut outputs = 0u64.to_le_bytes().to_vec();
+    outputs.extend_from_slice(&[0x01, 0x6a]);
+
+    let mut preimage = Vec::new();
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    preimage.extend_from_slice(&hash256(&outpoint));
+    preimage.extend_from_slice(&hash256(&0u32.to_le_bytes()));
+    preimage.extend_from_slice(&outpoint);
+    preimage.extend_from_slice(&script_code);
+    preimage.extend_from_slice(&0u64.to_le_bytes());
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    preimage.extend_from_slice(&hash256(&outputs));
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    preimage.extend_from_slice(&(SIGHASH_ALL as u32).to_le_bytes());
+    U256::from_big_endian(&hash256(&preimage))
+}
+
+/// BIP341 key-path signature hash of `to_sign` spending a P2TR `to_spend`
+/// output; `hash_type` is `SIGHASH_DEFAULT` or `SIGHASH_ALL`.
+fn to_sign_taproot_sighash(output_key: &XOnlyPublicKey, msg: &[u8], hash_type: u8) -> [u8; 32] {
+    let script_pubkey = p2tr_script(output_key);
+    let mut outpoint = to_spend_txid(&script_pubkey, msg).to_vec();
+    outpoint.extend_from_slice(&0u32.to_le_bytes());
+
+    let mut script_pubkeys = encode_varint(script_pubkey.len() as u64);
+    script_pubkeys.extend_from_slice(&script_pubkey);
+
+    let mut outputs = 0u64.to_le_bytes().to_vec();
+    outputs.extend_from_slice(&[0x01, 0x6a]);
+
+    // 先頭の 0 は sighash のエポック
+    let mut preimage = vec![0x00, hash_type];
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    preimage.extend_from_slice(&Sha256::digest(&outpoint));
+    preimage.extend_from_slice(&Sha256::digest(0u64.to_le_bytes()));
+    preimage.extend_from_slice(&Sha256::digest(&script_pubkeys));
+    preimage.extend_from_slice(&Sha256::digest(0u32.to_le_bytes()));
+    preimage.extend_from_slice(&Sha256::digest(&outputs));
+    // spend_type = 0 (キーパス、annex なし)、入力のインデックス 0
+    preimage.push(0x00);
+    preimage.extend_from_slice(&0u32.to_le_bytes());
+    tagged_hash("TapSighash", &preimage)
+}
+
+/// Signs `msg` for the P2WPKH address of `key`; returns the Base64 of the
+/// `to_sign` witness.
+pub fn sign_simple(key: &PrivateKey, msg: &str) -> String {
+    let point = key.point();
+    let program = point.identifier();
+
+    let mut sig = key.sign(to_sign_sighash(&program, msg.as_bytes())).der();
+    sig.push(SIGHASH_ALL);
+    let pubkey = point.sec_compressed();
+
+    // ウィットネス: 要素数, (長さ, 署名), (長さ, 公開鍵)
+    let mut witness = vec![2];
+    witness.extend_from_slice(&encode_varint(sig.len() as u64));
+    witness.extend_from_slice(&sig);
+    witness.extend_from_slice(&encode_varint(pubkey.len() as u64));
+    witness.extend_from_slice(&pubkey);
+    encode_base64(&witness)
+}
+
+/// Signs `msg` for the BIP86 P2TR address of `key` (key path, no script
+/// tree); returns the Base64 of the `to_sign` witness.
+pub fn sign_simple_taproot(key: &PrivateKey, msg: &str, aux_rand: &[u8; 32]) -> String {
+    let internal_key =
+        XOnlyPublicKey::from_point(&key.point()).expect("a private key is never infinity");
+    let info = TaprootSpendInfo::new(internal_key, None).expect("tweak is below N");
+    let tweaked = info.tweak_private_key(key).expect("tweak is below N");
+
+    let sighash = to_sign_taproot_sighash(&info.output_key, msg.as_bytes(), SIGHASH_DEFAULT);
+    let sig = schnorr::sign(&tweaked, &sighash, aux_rand).serialize();
+
+    // SIGHASH_DEFAULT の署名は sighash バイトを付けない 64 バイト
+    let mut witness = vec![1, 64];
+    witness.extend_from_slice(&sig);
+    encode_base64(&witness)
+}
+
+/// Checks a BIP322 simple signature of `msg` against a P2WPKH or P2TR
+/// address. Other address types are reported as not verified.
+pub fn verify_simple(address: &str, msg: &str, signature: &str) -> bool {
+    let Ok(witness) = decode_base64(signature) else {
+        return false;
+    };
+    match decode_segwit_address(address) {
+        Ok((_, 0, program)) => match program.try_into() {
+            Ok(program) => verify_p2wpkh(&program, msg, &witness),
+            Err(_) => false,
+        },
+        Ok((_, 1, program)) => match XOnlyPublicKey::parse(&program) {
+            Ok(output_key) => verify_p2tr(&output_key, msg, &witness),
+            Err(_) => false,
+        },
+        _ => false,
+    }
+}
+
+fn verify_p2wpkh(program: &[u8; 20], msg: &str, witness: &[u8]) -> bool {
+    // 2 要素 (署名, 33 バイトの公開鍵) だけを受け付ける
+    let [2, sig_len, rest @ ..] = witness else {
+        return false;
+    };
+    let sig_len = *sig_len as usize;
+    if rest.len() != sig_len + 34 || rest[sig_len] != 33 {
+        return false;
+    }
+    let (sig, pubkey) = (&rest[..sig_len], &rest[sig_len + 1..]);
+    let Some((&SIGHASH_ALL, der)) = sig.split_last() else {
+        return false;
+    };
+
+    if hash160(pubkey) != *program {
+        return false;
+    }
+    let (Ok(point), Some(sig)) = (S256Point::parse(pubkey), Signature::parse_der(der).ok()) else {
+        return false;
+    };
+    point.verify(to_sign_sighash(program, msg.as_bytes()), &sig)
+}
+
+fn verify_p2tr(output_key: &XOnlyPublicKey, msg: &str, witness: &[u8]) -> bool {
+    // 1 要素: 64 バイトの署名 (SIGHASH_DEFAULT) か、SIGHASH_ALL 付きの 65 バイト
+    let (sig, hash_type) = match witness {
+        [1, 64, sig @ ..] if sig.len() == 64 => (sig, SIGHASH_DEFAULT),
+        [1, 65, sig @ .., SIGHASH_ALL] if sig.len() == 64 => (sig, SIGHASH_ALL),
+        _ => return false,
+    };
+    let Ok(sig) = SchnorrSignature::parse(sig) else {
+        return false;
+    };
+    let sighash = to_sign_taproot_sighash(output_key, msg.as_bytes(), hash_type);
+    schnorr::verify(output_key, &sighash, &sig)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{message_hash, sign_simple, sign_simple_taproot, verify_simple};
+    use crate::address::Address;
+    use crate::network::Network;
+    use crate::private_key::PrivateKey;
+    use crate::taproot::TaprootSpendInfo;
+    use crate::xonly::XOnlyPublicKey;
+
+    fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    const ADDRESS: &str = "bc1q9vza2e8x573nczrlzms0wvx3gsqjx7vavgkx0l";
+
+    #[test]
+    fn message_hash_vectors() {
+        assert_eq!(
+            encode(&message_hash(b"")),
+            "c90c269c4f8fcbe6880f72a721ddfbf1914268a794cbb21cfafee13770ae19f1"
+        );
+        assert_eq!(
+            encode(&message_hash(b"Hello World")),
+            "f0eb03b1a75ac6d9847f55c624a99169b5dccba2a31f5b23bea77ba270de0a7a"
+        );
+    }
+
+    #[test]
+    fn verify_bip322_vectors() {
+        // BIP322 のテストベクタ (鍵 L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k)
+        let empty = "AkcwRAIgM2gBAQqvZX15ZiysmKmQpDrG83avLIT492QBzLnQIxYCIBaTpOaD20qRlEylyxFSeEA2ba9YOixpX8z46TSDtS40ASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+        let hello = "AkcwRAIgZRfIY3p7/DoVTty6YZbWS71bc5Vct9p9Fia83eRmw2QCICK/ENGfwLtptFluMGs2KsqoNSk89pO7F29zJLUx9a/sASECx/EgAxlkQpQ9hYjgGu6EBCPMVPwVIVJqO4XCsMvViHI=";
+
+        assert!(verify_simple(ADDRESS, "", empty));
+        assert!(verify_simple(ADDRESS, "Hello World", hello));
+        assert!(!verify_simple(ADDRESS, "Hello World", empty));
+        assert!(!verify_simple(ADDRESS, "", hello));
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let (key, _, _) =
+            PrivateKey::from_wif("L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k").unwrap();
+
+        let sig = sign_simple(&key, "Hello World");
+        assert!(verify_simple(ADDRESS, "Hello World", &sig));
+        assert!(!verify_simple(ADDRESS, "Hello", &sig));
+        assert!(!verify_simple(
+            "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4",
+            "Hello World",
+            &sig
+        ));
+        assert!(!verify_simple(ADDRESS, "Hello World", "AAAA"));
+        assert!(!verify_simple(
+            "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1",
+            "Hello World",
+            &sig
+        ));
+    }
+
+    #[test]
+    fn verify_bip322_taproot_vector() {
+        // BIP322 のテストベクタ (同じ鍵の BIP86 アドレス、SIGHASH_ALL 付き)
+        let address = "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3";
+        let sig = "AUHd69PrJQEv+oKTfZ8l+WROBHuy9HKrbFCJu7U1iK2iiEy1vMU5EfMtjc+VSHM7aU0SDbak5IUZRVno2P5mjSafAQ==";
+
+        assert!(verify_simple(address, "Hello World", sig));
+        assert!(!verify_simple(address, "", sig));
+    }
+
+    #[test]
+    fn sign_and_verify_taproot() {
+        let (key, _, _) =
+            PrivateKey::from_wif("L3VFeEujGtevx9w18HD1fhRbCH67Az2dpCymeRE1SoPK6XQtaN2k").unwrap();
+        let internal_key = XOnlyPublicKey::from_point(&key.point()).unwrap();
+        let info = TaprootSpendInfo::new(internal_key, None).unwrap();
+        let address = Address::p2tr(info.output_key, Network::Mainnet).to_string();
+        assert_eq!(
+            address,
+            "bc1ppv609nr0vr25u07u95waq5lucwfm6tde4nydujnu8npg4q75mr5sxq8lt3"
+        );
+
+        let sig = sign_simple_taproot(&key, "Hello World", &[7; 32]);
+        assert!(verify_simple(&address, "Hello World", &sig));
+        assert!(!verify_simple(&address, "Hello", &sig));
+        // P2WPKH の署名は P2TR アドレスでは通らない
+        assert!(!verify_simple(
+            &address,
+            "Hello World",
+            &sign_simple(&key, "Hello World")
+        ));
+        assert!(!verify_simple(ADDRESS, "Hello World", &sig));
+    }
+}
@@ -0,0 +1,247 @@
+use std::fmt;
+use std::str::FromStr;
+use std::sync::OnceLock;
+
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+
+use crate::error::MnemonicError;
+use crate::hash::pbkdf2_hmac_sha512;
+
+const ENGLISH: &str = include_str!("bip39/english.txt");
+
+static WORDLIST: OnceLock<Vec<&'static str>> = OnceLock::new();
+
+/// The 2048 words of the BIP39 English wordlist, in order.
+pub fn wordlist() -> &'static [&'static str] {
+    WORDLIST.get_or_init(|| ENGLISH.lines().collect())
+}
+
+/// A BIP39 mnemonic: entropy plus a checksum, written as words that each
+/// encode 11 bits.
+#[derive(Clone, PartialEq, Eq)]
+pub struct Mnemonic {
+    indices: Vec<u16>,
+}
+
+impl Mnemonic {
+    /// A fresh mnemonic over `entropy_bits` random bits (128, 160, 192, 224
+    /// or 256, giving 12 to 24 words).
+    pub fn generate(entropy_bits: usize) -> Result<Self, MnemonicError> {
+        if !entropy_bits.is_multiple_of(32) || !(128..=256).contains(&entropy_bits) {
+            return Err(MnemonicError::InvalidEntropyLength);
+        }
+        let mut entropy = vec![0u8; entropy_bits / 8];
+        rand::thread_rng().fill_bytes(&mut entropy);
+        Self::from_entropy(&entropy)
+    }
+
+    pub fn from_entropy(entropy: &[u8]) -> Result<Self, MnemonicError> {
+        if !entropy.len().is_multiple_of(4) || !(16..=32).contains(&entropy.len()) {
+            return Err(MnemonicError::InvalidEntropyLength);
+        }
+
+        // エントロピーの後ろに SHA256 の先頭 ENT/32 ビットを付け、11 ビットずつ区切る
+        let checksum = Sha256::digest(entropy);
+        let mut bits: Vec<bool> = bytes_to_bits(entropy).collect();
+        bits.extend(bytes_to_bits(&checksum).take(entropy.len() / 4));
+
+        let indices = bits
+            .chunks(11)
+            .map(|chunk| chunk.iter().fold(0u16, |acc, &bit| (acc << 1) | bit as u16))
+            .collect();
+        Ok(Self { indices })
+    }
+
+    /// Parses words separated by whitespace, checking each against the
+    /// wordlist and the final checksum.
+    pub fn parse(s: &str) -> Result<Self, MnemonicError> {
+        let words: Vec<&str> = s.split_whitespace().collect();
+        if !words.len().is_multiple_of(3) || !(12..=24).contains(&words.len()) {
+            return Err(MnemonicError::InvalidWordCount);
+        }
+
+        let list = wordlist();
+        let indices = words
+            .iter()
+            .enumerate()
+            .map(|(i, word)| {
+                list.binary_search(word)
+                    .map(|index| index as u16)
+                    .map_err(|_| MnemonicError::UnknownWord(i))
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        let mnemonic = Self { indices };
+        if Self::from_entropy(&mnemonic.entropy())? != mnemonic {
+            return Err(MnemonicError::InvalidChecksum);
+        }
+        Ok(mnemonic)
+    }
+
+    /// The entropy the mnemonic encodes, without the checksum.
+    pub fn entropy(&self) -> Vec<u8> {
+        let bits: Vec<bool> = self
+            .indices
+            .iter()
+            .flat_map(|&index| (0..11).rev().map(move |i| (index >> i) & 1 == 1))
+            .collect();
+        // 33 ビットごとに 1 ビットがチェックサム
+        let entropy_bits = bits.len() * 32 / 33;
+        bits[..entropy_bits]
+            .chunks(8)
+            .map(|chunk| chunk.iter().fold(0u8, |acc, &bit| (acc << 1) | bit as u8))
+            .collect()
+    }
+
+    pub fn words(&self) -> impl Iterator<Item = &'static str> + '_ {
+        let list = wordlist();
+        self.indices.iter().map(move |&index| list[index as usize])
+    }
+
+    /// The 64-byte seed for `ExtendedPrivKey::new_master`:
+    /// PBKDF2-HMAC-SHA512 of the words, salted with `"mnemonic" + passphrase`,
+    /// 2048 rounds.
+    ///
+    /// BIP39 asks for NFKD normalization of both strings; the English words
+    /// are ASCII already, but a non-ASCII passphrase must be normalized by
+    /// the caller.
+    pub fn to_seed(&self, passphrase: &str) -> [u8; 64] {
+        let salt = format!("mnemonic{}", passphrase);
+        let mut seed = [0u8; 64];
+        pbkdf2_hmac_sha512(
+            self.to_string().as_bytes(),
+            salt.as_bytes(),
+            2048,
+            &mut seed,
+        );
+        seed
+    }
+}
+
+fn bytes_to_bits(bytes: &[u8]) -> impl Iterator<Item = bool> + '_ {
+    bytes
+        .iter()
+        .flat_map(|byte| (0..8).rev().map(move |i| (byte >> i) & 1 == 1))
+}
+
+impl fmt::Display for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let words: Vec<_> = self.words().collect();
+        write!(f, "{}", words.join(" "))
+    }
+}
+
+/// The words are as secret as the seed, so `Debug` does not print them.
+impl fmt::Debug for Mnemonic {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Mnemonic(<{} words>)", self.indices.len())
+    }
+}
+
+impl FromStr for Mnemonic {
+    type Err = MnemonicError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::parse(s)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{wordlist, Mnemonic};
+    use crate::error::MnemonicError;
+    use crate::hd::ExtendedPrivKey;
+    use crate::network::Network;
+
+    fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn english_wordlist() {
+        let list = wordlist();
+        assert_eq!(list.len(), 2048);
+        assert_eq!((list[0], list[3], list[2047]), ("abandon", "about", "zoo"));
+        assert!(list.windows(2).all(|w| w[0] < w[1]));
+    }
+
+    #[test]
+    fn bip39_test_vectors() {
+        // BIP39 の公式テストベクタ (パスフレーズ "TREZOR")
+        let cases = [
+            (
+                [0x00; 16],
+                "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about",
+                "c55257c360c07c72029aebc1b53c05ed0362ada38ead3e3e9efa3708e53495531f09a6987599d18264c1e1c92f2cf141630c7a3c4ab7c81b2f001698e7463b04",
+            ),
+            (
+                [0x7f; 16],
+                "legal winner thank year wave sausage worth useful legal winner thank yellow",
+                "2e8905819b8723fe2c1d161860e5ee1830318dbf49a83bd451cfb8440c28bd6fa457fe1296106559a3c80937a1c1069be3a3a5bd381ee6260e8d9739fce1f607",
+            ),
+            (
+                [0xff; 16],
+                "zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo zoo wrong",
+                "ac27495480225222079d7be181583751e86f571027b0497b5b5d11218e0a8a13332572917f0f8e5a589620c6f15b11c61dee327651a14c34e18231052e48c069",
+            ),
+        ];
+        for (entropy, words, seed) in cases {
+            let mnemonic = Mnemonic::from_entropy(&entropy).unwrap();
+            assert_eq!(mnemonic.to_string(), words);
+            assert_eq!(words.parse::<Mnemonic>().unwrap(), mnemonic);
+            assert_eq!(mnemonic.entropy(), entropy);
+            assert_eq!(encode(&mnemonic.to_seed("TREZOR")), seed);
+        }
+    }
+
+    #[test]
+    fn seed_feeds_bip32() {
+        let mnemonic = Mnemonic::from_entropy(&[0x00; 16]).unwrap();
+        let master =
+            ExtendedPrivKey::new_master(&mnemonic.to_seed("TREZOR"), Network::Mainnet).unwrap();
+        assert_eq!(
+            master.to_string(),
+            "xprv9s21ZrQH143K3h3fDYiay8mocZ3afhfULfb5GX8kCBdno77K4HiA15Tg23wpbeF1pLfs1c5SPmYHrEpTuuRhxMwvKDwqdKiGJS9XFKzUsAF"
+        );
+    }
+
+    #[test]
+    fn generate() {
+        for (bits, words) in [(128, 12), (160, 15), (192, 18), (224, 21), (256, 24)] {
+            let mnemonic = Mnemonic::generate(bits).unwrap();
+            assert_eq!(mnemonic.words().count(), words);
+            assert_eq!(mnemonic.to_string().parse::<Mnemonic>().unwrap(), mnemonic);
+        }
+        assert_eq!(
+            Mnemonic::generate(100),
+            Err(MnemonicError::InvalidEntropyLength)
+        );
+        assert_eq!(
+            Mnemonic::generate(288),
+            Err(MnemonicError::InvalidEntropyLength)
+        );
+    }
+
+    #[test]
+    fn parse_errors() {
+        assert_eq!(
+            "abandon abandon abandon".parse::<Mnemonic>(),
+            Err(MnemonicError::InvalidWordCount)
+        );
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon bitcoin about"
+                .parse::<Mnemonic>(),
+            Err(MnemonicError::UnknownWord(10))
+        );
+        assert_eq!(
+            "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon"
+                .parse::<Mnemonic>(),
+            Err(MnemonicError::InvalidChecksum)
+        );
+        assert_eq!(
+            format!("{:?}", Mnemonic::from_entropy(&[0; 16]).unwrap()),
+            "Mnemonic(<12 words>)"
+        );
+    }
+}
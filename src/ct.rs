@@ -0,0 +1,196 @@
+use std::hint::black_box;
+use std::ops::{BitAnd, BitOr, Not};
+
+use primitive_types::U256;
+
+/// The outcome of a constant-time comparison, `1` for true and `0` for false.
+///
+/// Modelled on `subtle::Choice`: combining choices with `&`, `|` and `!`
+/// never branches, and the value only becomes a `bool` at the very end.
+#[derive(Clone, Copy, Debug)]
+pub struct Choice(u8);
+
+impl Choice {
+    pub fn unwrap_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl From<u8> for Choice {
+    fn from(value: u8) -> Self {
+        debug_assert!(value == 0 || value == 1);
+        Choice(black_box(value))
+    }
+}
+
+impl From<Choice> for bool {
+    fn from(choice: Choice) -> Self {
+        black_box(choice.0) != 0
+    }
+}
+
+impl BitAnd for Choice {
+    type Output = Choice;
+
+    fn bitand(self, other: Choice) -> Choice {
+        Choice(self.0 & other.0)
+    }
+}
+
+impl BitOr for Choice {
+    type Output = Choice;
+
+    fn bitor(self, other: Choice) -> Choice {
+        Choice(self.0 | other.0)
+    }
+}
+
+impl Not for Choice {
+    type Output = Choice;
+
+    fn not(self) -> Choice {
+        Choice(self.0 ^ 1)
+    }
+}
+
+/// Equality whose running time does not depend on the compared values.
+pub trait ConstantTimeEq {
+    fn ct_eq(&self, other: &Self) -> Choice;
+}
+
+impl ConstantTimeEq for u64 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        let x = self ^ other;
+        // x != 0 のときだけ最上位ビットが立つ
+        let nonzero = (x | x.wrapping_neg()) >> 63;
+        Choice::from((nonzero ^ 1) as u8)
+    }
+}
+
+impl ConstantTimeEq for u8 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        (*self as u64).ct_eq(&(*other as u64))
+    }
+}
+
+/// Slices of different lengths compare unequal; the length itself is not
+/// treated as secret.
+impl<T: ConstantTimeEq> ConstantTimeEq for [T] {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        if self.len() != other.len() {
+            return Choice::from(0);
+        }
+        self.iter()
+            .zip(other)
+            .fold(Choice::from(1), |acc, (a, b)| acc & a.ct_eq(b))
+    }
+}
+
+impl<T: ConstantTimeEq, const N: usize> ConstantTimeEq for [T; N] {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self[..].ct_eq(&other[..])
+    }
+}
+
+impl ConstantTimeEq for U256 {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Selection between two values without branching on the `Choice`.
+pub trait ConditionallySelectable: Sized {
+    /// Returns `a` when `choice` is 0 and `b` when it is 1.
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self;
+
+    /// Swaps `a` and `b` when `choice` is 1.
+    fn conditional_swap(a: &mut Self, b: &mut Self, choice: Choice) {
+        let new_a = Self::conditional_select(a, b, choice);
+        let new_b = Self::conditional_select(b, a, choice);
+        *a = new_a;
+        *b = new_b;
+    }
+}
+
+impl ConditionallySelectable for u64 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        // choice == 1 なら全ビットが立ったマスクになる
+        let mask = (choice.unwrap_u8() as u64).wrapping_neg();
+        a ^ (mask & (a ^ b))
+    }
+}
+
+impl ConditionallySelectable for u8 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        u64::conditional_select(&(*a as u64), &(*b as u64), choice) as u8
+    }
+}
+
+impl ConditionallySelectable for Choice {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Choice(u8::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<T: ConditionallySelectable + Copy, const N: usize> ConditionallySelectable for [T; N] {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let mut ret = *a;
+        for (r, (x, y)) in ret.iter_mut().zip(a.iter().zip(b)) {
+            *r = T::conditional_select(x, y, choice);
+        }
+        ret
+    }
+}
+
+impl ConditionallySelectable for U256 {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        U256(<[u64; 4]>::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Choice, ConditionallySelectable, ConstantTimeEq};
+    use primitive_types::U256;
+
+    #[test]
+    fn choice_logic() {
+        let yes = Choice::from(1);
+        let no = Choice::from(0);
+
+        assert!(bool::from(yes & yes));
+        assert!(!bool::from(yes & no));
+        assert!(bool::from(yes | no));
+        assert!(bool::from(!no));
+    }
+
+    #[test]
+    fn ct_eq() {
+        assert!(bool::from(5u64.ct_eq(&5)));
+        assert!(!bool::from(5u64.ct_eq(&6)));
+        assert!(!bool::from(0u64.ct_eq(&u64::MAX)));
+        assert!(bool::from([1u8, 2, 3].ct_eq(&[1, 2, 3])));
+        assert!(!bool::from([1u8, 2, 3][..].ct_eq(&[1, 2][..])));
+        assert!(!bool::from(U256::MAX.ct_eq(&(U256::MAX - 1))));
+    }
+
+    #[test]
+    fn conditional_select() {
+        let yes = Choice::from(1);
+        let no = Choice::from(0);
+
+        assert_eq!(u64::conditional_select(&3, &u64::MAX, no), 3);
+        assert_eq!(u64::conditional_select(&3, &u64::MAX, yes), u64::MAX);
+        assert_eq!(
+            U256::conditional_select(&U256::one(), &U256::MAX, yes),
+            U256::MAX
+        );
+
+        let mut a = U256::from(1);
+        let mut b = U256::from(2);
+        U256::conditional_swap(&mut a, &mut b, no);
+        assert_eq!((a, b), (U256::from(1), U256::from(2)));
+        U256::conditional_swap(&mut a, &mut b, yes);
+        assert_eq!((a, b), (U256::from(2), U256::from(1)));
+    }
+}
@@ -0,0 +1,328 @@
+use std::fmt::Debug;
+use std::hash::Hash;
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Sub};
+
+use primitive_types::U256;
+
+use crate::ct::{ConditionallySelectable, ConstantTimeEq};
+use crate::elliptic::Point;
+use crate::error::PointError;
+use crate::field_element::One;
+use crate::macros::{forward_op_assign, forward_ref_binop};
+use crate::s256_field::S256Field;
+use crate::secp256k1;
+use crate::secret::SecretScalar;
+
+/// Parameters of a short Weierstrass curve `y^2 = x^3 + A*x + B` over the
+/// prime field `Field` of order `P`, with generator `G` of order `N`.
+///
+/// Implemented by zero-sized marker types, so the curve of a `CurvePoint`
+/// is part of its type: points are not burdened with `a` and `b`, and
+/// adding points of different curves is a compile error rather than a
+/// runtime panic.
+pub trait Curve: Clone + Copy + Debug + PartialEq + Eq + Hash {
+    type Field: Clone
+        + Debug
+        + PartialEq
+        + Eq
+        + Hash
+        + Add<Output = Self::Field>
+        + Sub<Output = Self::Field>
+        + Mul<Output = Self::Field>
+        + Div<Output = Self::Field>
+        + Neg<Output = Self::Field>
+        + One
+        + ConstantTimeEq
+        + ConditionallySelectable;
+
+    const A: Self::Field;
+    const B: Self::Field;
+    const P: U256;
+    const N: U256;
+    const G: (Self::Field, Self::Field);
+}
+
+/// secp256k1: `y^2 = x^3 + 7` over the field of `secp256k1::P`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Secp256k1;
+
+impl Curve for Secp256k1 {
+    type Field = S256Field;
+
+    const A: S256Field = S256Field::from_reduced(U256([0, 0, 0, 0]));
+    const B: S256Field = S256Field::from_reduced(U256([7, 0, 0, 0]));
+    const P: U256 = secp256k1::P;
+    const N: U256 = secp256k1::N;
+    const G: (S256Field, S256Field) = (
+        S256Field::from_reduced(secp256k1::G_X),
+        S256Field::from_reduced(secp256k1::G_Y),
+    );
+}
+
+/// A point on the curve `C`. The group law is the one of `Point`, with the
+/// curve coefficients supplied by `C`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum CurvePoint<C: Curve> {
+    Coordinate { x: C::Field, y: C::Field },
+    Infinity,
+}
+
+impl<C: Curve> CurvePoint<C> {
+    pub fn try_new(x: C::Field, y: C::Field) -> Result<Self, PointError> {
+        Point::try_new(x, y, C::A, C::B).map(Self::from_point_unchecked)
+    }
+
+    pub fn new(x: C::Field, y: C::Field) -> Self {
+        match Self::try_new(x, y) {
+            Ok(point) => point,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    pub fn generator() -> Self {
+        let (x, y) = C::G;
+        CurvePoint::Coordinate { x, y }
+    }
+
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, CurvePoint::Infinity)
+    }
+
+    pub fn x(&self) -> Option<C::Field> {
+        match self {
+            CurvePoint::Coordinate { x, .. } => Some(x.clone()),
+            CurvePoint::Infinity => None,
+        }
+    }
+
+    pub fn y(&self) -> Option<C::Field> {
+        match self {
+            CurvePoint::Coordinate { y, .. } => Some(y.clone()),
+            CurvePoint::Infinity => None,
+        }
+    }
+
+    /// The same point with the coefficients of `C` spelled out.
+    pub fn to_point(&self) -> Point<C::Field> {
+        match self {
+            CurvePoint::Coordinate { x, y } => Point::Coordinate {
+                x: x.clone(),
+                y: y.clone(),
+                a: C::A,
+                b: C::B,
+            },
+            CurvePoint::Infinity => Point::Infinity,
+        }
+    }
+
+    // 演算結果は同じ曲線上にあるので a, b は捨ててよい
+    fn from_point_unchecked(point: Point<C::Field>) -> Self {
+        match point {
+            Point::Coordinate { x, y, .. } => CurvePoint::Coordinate { x, y },
+            Point::Infinity => CurvePoint::Infinity,
+        }
+    }
+
+    pub fn double(&self) -> Self {
+        Self::from_point_unchecked(self.to_point().double())
+    }
+
+    /// Constant-time scalar multiplication for secret scalars.
+    pub fn mul_secret(&self, scalar: &SecretScalar) -> Self {
+        Self::from_point_unchecked(self.to_point().mul_secret(scalar))
+    }
+
+    /// `sum(k_i * P_i)` for public scalars; see `Point::multi_mul`.
+    pub fn multi_mul(terms: &[(U256, Self)]) -> Self {
+        let terms: Vec<_> = terms.iter().map(|(k, p)| (*k, p.to_point())).collect();
+        Self::from_point_unchecked(Point::multi_mul(&terms))
+    }
+}
+
+impl<C: Curve> From<CurvePoint<C>> for Point<C::Field> {
+    fn from(point: CurvePoint<C>) -> Self {
+        point.to_point()
+    }
+}
+
+/// Accepts only points whose coefficients are those of `C` and that lie on
+/// the curve.
+impl<C: Curve> TryFrom<Point<C::Field>> for CurvePoint<C> {
+    type Error = PointError;
+
+    fn try_from(point: Point<C::Field>) -> Result<Self, Self::Error> {
+        match point {
+            Point::Coordinate { x, y, a, b } => {
+                if a != C::A || b != C::B {
+                    return Err(PointError::NotOnCurve);
+                }
+                Self::try_new(x, y)
+            }
+            Point::Infinity => Ok(CurvePoint::Infinity),
+        }
+    }
+}
+
+impl<C: Curve> Add for CurvePoint<C> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self::from_point_unchecked(self.to_point() + other.to_point())
+    }
+}
+
+impl<C: Curve> Neg for CurvePoint<C> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self {
+            CurvePoint::Coordinate { x, y } => CurvePoint::Coordinate { x, y: -y },
+            CurvePoint::Infinity => CurvePoint::Infinity,
+        }
+    }
+}
+
+impl<C: Curve> Sub for CurvePoint<C> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+forward_ref_binop!(impl Add, add for CurvePoint<T: Curve>);
+forward_ref_binop!(impl Sub, sub for CurvePoint<T: Curve>);
+forward_op_assign!(impl AddAssign, add_assign, Add, add for CurvePoint<T: Curve>);
+
+/// Scalar multiplication.
+///
+/// Variable time by default, which is fine for public scalars. With the
+/// `ct` feature this goes through the Montgomery ladder of `mul_secret`
+/// instead, so code that multiplies by secret scalars with `*` stays
+/// constant time.
+impl<C: Curve> Mul<U256> for CurvePoint<C> {
+    type Output = Self;
+
+    #[cfg(not(feature = "ct"))]
+    fn mul(self, scalar: U256) -> Self::Output {
+        Self::from_point_unchecked(self.to_point() * scalar)
+    }
+
+    #[cfg(feature = "ct")]
+    fn mul(self, scalar: U256) -> Self::Output {
+        self.mul_secret(&SecretScalar::new(scalar))
+    }
+}
+
+impl<C: Curve> Mul<U256> for &CurvePoint<C> {
+    type Output = CurvePoint<C>;
+
+    fn mul(self, scalar: U256) -> Self::Output {
+        self.clone() * scalar
+    }
+}
+
+impl<C: Curve> Mul<CurvePoint<C>> for U256 {
+    type Output = CurvePoint<C>;
+
+    fn mul(self, point: CurvePoint<C>) -> Self::Output {
+        point * self
+    }
+}
+
+impl<C: Curve> Mul<&CurvePoint<C>> for U256 {
+    type Output = CurvePoint<C>;
+
+    fn mul(self, point: &CurvePoint<C>) -> Self::Output {
+        point * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Curve, CurvePoint, Secp256k1};
+    use crate::elliptic::Point;
+    use crate::error::PointError;
+    use crate::fp::Fp;
+    use crate::secp256k1;
+    use crate::secret::SecretScalar;
+    use primitive_types::U256;
+
+    type F223 = Fp<223>;
+
+    /// 本で使う F_223 上の y^2 = x^3 + 7
+    #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+    struct Curve223;
+
+    impl Curve for Curve223 {
+        type Field = F223;
+
+        const A: F223 = F223::new(0);
+        const B: F223 = F223::new(7);
+        const P: U256 = U256([223, 0, 0, 0]);
+        const N: U256 = U256([21, 0, 0, 0]);
+        const G: (F223, F223) = (F223::new(47), F223::new(71));
+    }
+
+    #[test]
+    fn generator_has_order_n() {
+        let g = CurvePoint::<Curve223>::generator();
+        assert_eq!(g * Curve223::N, CurvePoint::Infinity);
+        assert_eq!(Curve223::N * g, CurvePoint::Infinity);
+        assert_eq!(g * (Curve223::N + 1), g);
+
+        let g = CurvePoint::<Secp256k1>::generator();
+        assert_eq!(g.to_point(), secp256k1::generator());
+        assert_eq!(g * Secp256k1::N, CurvePoint::Infinity);
+    }
+
+    #[test]
+    fn group_law_matches_point() {
+        let p = CurvePoint::<Curve223>::new(F223::new(192), F223::new(105));
+        let q = CurvePoint::<Curve223>::new(F223::new(17), F223::new(56));
+
+        assert_eq!(
+            (p + q).to_point(),
+            Point::new(F223::new(170), F223::new(142), F223::new(0), F223::new(7))
+        );
+        assert_eq!(p.double(), p + p);
+        assert_eq!(p - p, CurvePoint::Infinity);
+
+        let mut acc = CurvePoint::Infinity;
+        acc += &p;
+        acc += q;
+        assert_eq!(acc, p + q);
+
+        assert_eq!(
+            p.mul_secret(&SecretScalar::new(U256::from(5))),
+            p * U256::from(5)
+        );
+        assert_eq!(
+            CurvePoint::multi_mul(&[(U256::from(3), p), (U256::from(4), q)]),
+            p * U256::from(3) + q * U256::from(4)
+        );
+    }
+
+    #[test]
+    fn conversions() {
+        assert_eq!(
+            CurvePoint::<Curve223>::try_new(F223::new(192), F223::new(106)),
+            Err(PointError::NotOnCurve)
+        );
+
+        let point = Point::new(F223::new(192), F223::new(105), F223::new(0), F223::new(7));
+        let typed = CurvePoint::<Curve223>::try_from(point.clone()).unwrap();
+        assert_eq!(Point::from(typed), point);
+
+        // a, b が違う曲線上の点は受け付けない
+        let other = Point::new(F223::new(1), F223::new(0), F223::new(0), F223::new(222));
+        assert_eq!(
+            CurvePoint::<Curve223>::try_from(other),
+            Err(PointError::NotOnCurve)
+        );
+        assert_eq!(
+            CurvePoint::<Curve223>::try_from(Point::Infinity),
+            Ok(CurvePoint::Infinity)
+        );
+    }
+}
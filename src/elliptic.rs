@@ -1,9 +1,19 @@
 use std::fmt;
 use std::fmt::{Debug, Formatter};
-use std::ops::{Add, Div, Mul, Sub};
+use std::ops::{Add, AddAssign, Div, Mul, Neg, Rem, Sub};
+
+use primitive_types::U256;
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::error::PointError;
+use crate::field_element::{batch_invert, One};
+use crate::jacobian::{self, JacobianPoint};
+use crate::macros::{forward_op_assign, forward_ref_binop};
+use crate::secret::SecretScalar;
 
 // Elliptic Curve: y^2 = x^3 + a*x + b
-#[derive(Clone, Debug, PartialEq)]
+// 順序は x, y (, a, b) の辞書式で、無限遠点は最後
+#[derive(Clone, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
 pub enum Point<T> {
     Coordinate { x: T, y: T, a: T, b: T },
     Infinity,
@@ -13,11 +23,11 @@ where
     T: fmt::Display,
 {
     fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
-        match &self {
-            &Point::Coordinate { x, y, a, b } => {
+        match self {
+            Point::Coordinate { x, y, a, b } => {
                 write!(f, "Point({}, {})_{}_{}", x, y, a, b)
             }
-            &Point::Infinity => {
+            Point::Infinity => {
                 write!(f, "Point(Infinity)")
             }
         }
@@ -26,19 +36,260 @@ where
 
 impl<T> Point<T>
 where
-    T: Add<Output = T> + Mul<Output = T> + PartialEq + Copy,
+    T: Add<Output = T> + Mul<Output = T> + PartialEq + Clone,
 {
+    pub fn try_new(x: T, y: T, a: T, b: T) -> Result<Self, PointError> {
+        let point = Self::Coordinate { x, y, a, b };
+        if !point.is_on_curve() {
+            return Err(PointError::NotOnCurve);
+        }
+        Ok(point)
+    }
+
     pub fn new(x: T, y: T, a: T, b: T) -> Self {
-        if y * y != x * x * x + a * x + b {
-            panic!("This is invalid number")
+        match Self::try_new(x, y, a, b) {
+            Ok(point) => point,
+            Err(err) => panic!("{}", err),
+        }
+    }
+
+    /// Whether the coordinates satisfy the curve equation. The point at
+    /// infinity is on every curve.
+    pub fn is_on_curve(&self) -> bool {
+        match self {
+            Point::Coordinate { x, y, a, b } => {
+                y.clone() * y.clone()
+                    == x.clone() * x.clone() * x.clone() + a.clone() * x.clone() + b.clone()
+            }
+            Point::Infinity => true,
+        }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: Clone,
+{
+    pub fn is_infinity(&self) -> bool {
+        matches!(self, Point::Infinity)
+    }
+
+    /// The x coordinate, or `None` for the point at infinity.
+    pub fn x(&self) -> Option<T> {
+        match self {
+            Point::Coordinate { x, .. } => Some(x.clone()),
+            Point::Infinity => None,
+        }
+    }
+
+    /// The y coordinate, or `None` for the point at infinity.
+    pub fn y(&self) -> Option<T> {
+        match self {
+            Point::Coordinate { y, .. } => Some(y.clone()),
+            Point::Infinity => None,
+        }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Div<Output = T> + Mul<Output = T> + Clone,
+{
+    /// `self + self` via the tangent line at `self`.
+    ///
+    /// A point with `y == 0` has a vertical tangent, so doubling it gives
+    /// the point at infinity.
+    pub fn double(&self) -> Self {
+        match self {
+            Point::Coordinate { x, y, a, b } => {
+                // 2y == y となるのは y == 0 のときだけ
+                let two_y = y.clone() + y.clone();
+                if two_y == *y {
+                    return Point::Infinity;
+                }
+
+                //  微分して傾きを求める
+                let x_squared = x.clone() * x.clone();
+                let s = (x_squared.clone() + x_squared.clone() + x_squared + a.clone()) / two_y;
+
+                // 公式
+                let x2 = s.clone() * s.clone() - x.clone() - x.clone();
+                let y2 = s * (x.clone() - x2.clone()) - y.clone();
+                Point::Coordinate {
+                    x: x2,
+                    y: y2,
+                    a: a.clone(),
+                    b: b.clone(),
+                }
+            }
+            Point::Infinity => Point::Infinity,
+        }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + Neg<Output = T>
+        + One
+        + Clone,
+{
+    /// Scalar multiplication using a width-`window` NAF of the scalar.
+    ///
+    /// Faster than `*` but its running time depends on the scalar, so it is
+    /// only meant for public scalars such as those in signature
+    /// verification. Windows of 4 or 5 are a good default for 256-bit
+    /// scalars.
+    pub fn mul_wnaf(&self, scalar: U256, window: usize) -> Self {
+        jacobian::wnaf_mul(self, scalar, window)
+    }
+
+    /// `sum(k_i * P_i)` for public scalars, as needed by ECDSA verification
+    /// (`u * G + v * P`).
+    ///
+    /// Two terms use Shamir's trick; more terms are interleaved Strauss-style
+    /// so all scalars share a single chain of doublings, and from
+    /// `PIPPENGER_THRESHOLD` terms on Pippenger's bucket method is used.
+    pub fn multi_mul(terms: &[(U256, Self)]) -> Self {
+        match terms {
+            [] => Point::Infinity,
+            [(k, point)] => point.mul_wnaf(*k, 4),
+            [(k0, p0), (k1, p1)] => jacobian::shamir_mul(*k0, p0, *k1, p1),
+            _ if terms.len() >= jacobian::PIPPENGER_THRESHOLD => jacobian::pippenger_mul(terms),
+            _ => jacobian::strauss_mul(terms, 4),
+        }
+    }
+}
+
+impl<T> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + One
+        + ConstantTimeEq
+        + ConditionallySelectable
+        + Clone,
+{
+    /// Scalar multiplication for secret scalars (private keys, nonces).
+    ///
+    /// Unlike `*`, which skips work for zero bits, this runs a Montgomery
+    /// ladder over all 256 bits so its timing does not reveal the scalar,
+    /// provided the field arithmetic of `T` is constant time (`S256Field`
+    /// is; the generic `FieldElement` is not).
+    pub fn mul_secret(&self, scalar: &SecretScalar) -> Self {
+        jacobian::ladder_mul(self, scalar.expose_secret())
+    }
+}
+
+impl<T> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    /// `p + q` for every pair, sharing a single field inversion between all
+    /// of them instead of paying one per addition.
+    ///
+    /// Gives the same results as `+`, including doubling and the point at
+    /// infinity.
+    pub fn batch_add(pairs: &[(Self, Self)]) -> Vec<Self> {
+        // 傾き = numerator / denominator の分母だけ集めて一括で逆元にする
+        struct Pending<T> {
+            x0: T,
+            y0: T,
+            x1: T,
+            a: T,
+            b: T,
+            numerator: T,
+        }
+
+        let mut results = Vec::with_capacity(pairs.len());
+        let mut pending = Vec::new();
+        let mut denominators = Vec::new();
+        for (p, q) in pairs {
+            let (
+                Point::Coordinate {
+                    x: x0,
+                    y: y0,
+                    a: a0,
+                    b: b0,
+                },
+                Point::Coordinate {
+                    x: x1,
+                    y: y1,
+                    a: a1,
+                    b: b1,
+                },
+            ) = (p, q)
+            else {
+                results.push(Some(p.clone() + q.clone()));
+                continue;
+            };
+            if a0 != a1 || b0 != b1 {
+                panic!("Points are not on the same curve.")
+            }
+
+            let (numerator, denominator) = if x0 != x1 {
+                (y1.clone() - y0.clone(), x1.clone() - x0.clone())
+            } else {
+                let two_y = y0.clone() + y0.clone();
+                // 逆元同士、または接線が垂直
+                if y0 != y1 || two_y == *y0 {
+                    results.push(Some(Point::Infinity));
+                    continue;
+                }
+                let x_squared = x0.clone() * x0.clone();
+                (
+                    x_squared.clone() + x_squared.clone() + x_squared + a0.clone(),
+                    two_y,
+                )
+            };
+            results.push(None);
+            denominators.push(denominator);
+            pending.push(Pending {
+                x0: x0.clone(),
+                y0: y0.clone(),
+                x1: x1.clone(),
+                a: a0.clone(),
+                b: b0.clone(),
+                numerator,
+            });
         }
-        Self::Coordinate { x, y, a, b }
+
+        batch_invert(&mut denominators);
+
+        let mut computed = pending.into_iter().zip(denominators).map(|(p, inv)| {
+            let s = p.numerator * inv;
+            let x2 = s.clone() * s.clone() - p.x0.clone() - p.x1;
+            let y2 = s * (p.x0 - x2.clone()) - p.y0;
+            Point::Coordinate {
+                x: x2,
+                y: y2,
+                a: p.a,
+                b: p.b,
+            }
+        });
+        results
+            .into_iter()
+            .map(|result| result.unwrap_or_else(|| computed.next().expect("one per pending pair")))
+            .collect()
     }
 }
 
 impl<T> Add for Point<T>
 where
-    T: PartialEq + Add<Output = T> + Sub<Output = T> + Div<Output = T> + Mul<Output = T> + Copy,
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Div<Output = T> + Mul<Output = T> + Clone,
 {
     type Output = Self;
 
@@ -69,36 +320,26 @@ where
                         return Infinity;
                     }
                     // self == other の場合
-                    let one = a0 / a0;
-                    let two = one + one;
-                    let three = one + two;
-
-                    //  微分して傾きを求める
-                    let s = (three * x0 * x0 + a0) / (two * y0);
-
-                    // 公式
-                    let x2 = s * s - two * x0;
-                    let y2 = s * (x0 - x2) - y0;
-
                     return Coordinate {
-                        x: x2,
-                        y: y2,
+                        x: x0,
+                        y: y0,
                         a: a0,
                         b: b0,
-                    };
+                    }
+                    .double();
                 }
 
                 // 傾き = x の増加量分の y の増加量
-                let s = (y1 - y0) / (x1 - x0);
+                let s = (y1 - y0.clone()) / (x1.clone() - x0.clone());
                 // 公式
-                let x2 = s * s - x0 - x1;
-                let y2 = s * (x0 - x2) - y0;
-                return Coordinate {
+                let x2 = s.clone() * s.clone() - x0.clone() - x1;
+                let y2 = s * (x0 - x2.clone()) - y0;
+                Coordinate {
                     x: x2,
                     y: y2,
                     a: a0,
                     b: b0,
-                };
+                }
             }
             (Coordinate { x, y, a, b }, Infinity) => Coordinate { x, y, a, b },
             (Infinity, Coordinate { x, y, a, b }) => Coordinate { x, y, a, b },
@@ -107,40 +348,323 @@ where
     }
 }
 
-impl<T, U> Mul<U> for Point<T>
+/// Choice between two points that selects the coordinates without
+/// branching on `choice`.
+///
+/// This is not fully branch-free: `Point` is an enum, so reading the inputs
+/// and building the result branch on which points are the point at
+/// infinity. Code that must hide that too, such as the ladder and the
+/// fixed-base table, uses an internal Jacobian point type that keeps
+/// infinity as a `Choice` flag.
+impl<T> ConditionallySelectable for Point<T>
+where
+    T: ConditionallySelectable + Clone,
+{
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        let (ax, ay, aa, ab) = match (a, b) {
+            (Point::Coordinate { x, y, a, b }, _)
+            | (Point::Infinity, Point::Coordinate { x, y, a, b }) => (x, y, a, b),
+            (Point::Infinity, Point::Infinity) => return Point::Infinity,
+        };
+        let (bx, by, ba, bb) = match b {
+            Point::Coordinate { x, y, a, b } => (x, y, a, b),
+            Point::Infinity => (ax, ay, aa, ab),
+        };
+        let a_infinity = Choice::from(a.is_infinity() as u8);
+        let b_infinity = Choice::from(b.is_infinity() as u8);
+
+        let x = T::conditional_select(ax, bx, choice);
+        let y = T::conditional_select(ay, by, choice);
+        let curve_a = T::conditional_select(aa, ba, choice);
+        let curve_b = T::conditional_select(ab, bb, choice);
+        let infinity = Choice::conditional_select(&a_infinity, &b_infinity, choice);
+
+        if bool::from(infinity) {
+            Point::Infinity
+        } else {
+            Point::Coordinate {
+                x,
+                y,
+                a: curve_a,
+                b: curve_b,
+            }
+        }
+    }
+}
+
+impl<T> Neg for Point<T>
 where
-    T: Add<Output = T> + Sub<Output = T> + Div<Output = T> + Mul<Output = T> + PartialOrd + Copy,
-    U: Sub<Output = U> + Div<Output = U> + Mul<Output = U> + PartialOrd + Copy,
+    T: Neg<Output = T>,
 {
     type Output = Self;
 
+    // x 軸に対して折り返す
+    fn neg(self) -> Self::Output {
+        match self {
+            Point::Coordinate { x, y, a, b } => Point::Coordinate { x, y: -y, a, b },
+            Point::Infinity => Point::Infinity,
+        }
+    }
+}
+
+impl<T> Neg for &Point<T>
+where
+    Point<T>: Neg<Output = Point<T>> + Clone,
+{
+    type Output = Point<T>;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+impl<T> Sub for Point<T>
+where
+    Point<T>: Add<Output = Point<T>> + Neg<Output = Point<T>>,
+{
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+forward_ref_binop!(impl Add, add for Point);
+forward_ref_binop!(impl Sub, sub for Point);
+forward_op_assign!(impl AddAssign, add_assign, Add, add for Point);
+
+impl<T, U> Mul<U> for &Point<T>
+where
+    Point<T>: Mul<U, Output = Point<T>> + Clone,
+{
+    type Output = Point<T>;
+
     fn mul(self, other: U) -> Self::Output {
-        let zero = other - other;
-        let one = other / other;
+        self.clone() * other
+    }
+}
 
-        let mut counter = other;
-        let mut ret = Self::Infinity;
+/// How `Point<Self> * scalar` is computed for a coordinate type.
+///
+/// Field types go through Jacobian coordinates. The primitive integers of
+/// the book's chapter-2 curves have no `One`, and integer division would
+/// make the projective form inexact, so they keep affine double-and-add.
+pub trait ScalarMul: Sized {
+    fn scalar_mul<U>(point: Point<Self>, scalar: U) -> Point<Self>
+    where
+        U: Div<Output = U> + Rem<Output = U> + From<u8> + PartialOrd + Clone;
+}
+
+impl<T> ScalarMul for T
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Mul<Output = T>
+        + One
+        + Clone,
+{
+    fn scalar_mul<U>(point: Point<T>, scalar: U) -> Point<T>
+    where
+        U: Div<Output = U> + Rem<Output = U> + From<u8> + PartialOrd + Clone,
+    {
+        let zero = U::from(0);
+
+        // 逆元計算を避けるため Jacobian 座標で計算し、最後に一度だけアフィンに戻す
+        // バイナリ法: 下位ビットから見ていき、立っていれば足す
+        let mut counter = scalar;
+        let mut current = JacobianPoint::from_affine(&point);
+        let mut ret = JacobianPoint::Infinity;
 
         while counter > zero {
-            ret = ret + self.clone();
-            counter = counter - one;
+            if counter.clone() % U::from(2) == U::from(1) {
+                ret = ret + &current;
+            }
+            counter = counter / U::from(2);
+            // 使わない倍点は計算しない
+            if counter > zero {
+                current = current.double();
+            }
+        }
+        ret.to_affine()
+    }
+}
+
+macro_rules! impl_affine_scalar_mul {
+    ($($t:ty),*) => {
+        $(
+            impl ScalarMul for $t {
+                fn scalar_mul<U>(point: Point<$t>, scalar: U) -> Point<$t>
+                where
+                    U: Div<Output = U> + Rem<Output = U> + From<u8> + PartialOrd + Clone,
+                {
+                    let zero = U::from(0);
+
+                    let mut counter = scalar;
+                    let mut current = point;
+                    let mut ret = Point::Infinity;
+
+                    while counter > zero {
+                        if counter.clone() % U::from(2) == U::from(1) {
+                            ret += &current;
+                        }
+                        counter = counter / U::from(2);
+                        if counter > zero {
+                            current = current.double();
+                        }
+                    }
+                    ret
+                }
+            }
+        )*
+    };
+}
+
+impl_affine_scalar_mul!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+impl<T, U> Mul<U> for Point<T>
+where
+    T: ScalarMul,
+    U: Div<Output = U> + Rem<Output = U> + From<u8> + PartialOrd + Clone,
+{
+    type Output = Self;
+
+    fn mul(self, other: U) -> Self::Output {
+        T::scalar_mul(self, other)
+    }
+}
+
+impl<T> Point<T>
+where
+    Point<T>: Add<Output = Point<T>> + Clone,
+{
+    /// Iterates over `G, 2G, 3G, ...` for `G = self`, stopping before the
+    /// point at infinity is reached.
+    ///
+    /// Meant for the small teaching curves (e.g. over F_223): every point
+    /// of a finite group reaches infinity eventually, but on secp256k1 that
+    /// takes `N` steps. (Clippy's `infinite_iter` lint mistakes this for
+    /// `Iterator::cycle` and has to be allowed where the result is consumed.)
+    pub fn cycle(&self) -> Cycle<T> {
+        Cycle {
+            generator: self.clone(),
+            current: self.clone(),
+        }
+    }
+
+    /// Size of the subgroup generated by `self`: the smallest `n > 0` with
+    /// `n * self` at infinity.
+    pub fn order(&self) -> usize {
+        let mut order = 1;
+        for _ in self.cycle() {
+            order += 1;
+        }
+        order
+    }
+}
+
+/// Iterator returned by `Point::cycle`.
+pub struct Cycle<T> {
+    generator: Point<T>,
+    current: Point<T>,
+}
+
+impl<T> Iterator for Cycle<T>
+where
+    Point<T>: Add<Output = Point<T>> + Clone,
+{
+    type Item = Point<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if let Point::Infinity = self.current {
+            return None;
+        }
+        let next = self.current.clone() + self.generator.clone();
+        Some(std::mem::replace(&mut self.current, next))
+    }
+}
+
+// 本と同じく e * G とも書けるように、スカラーを左に置いた積
+macro_rules! impl_scalar_mul_point {
+    ($($t:ty),*) => {
+        $(
+            impl<T> Mul<Point<T>> for $t
+            where
+                Point<T>: Mul<$t, Output = Point<T>>,
+            {
+                type Output = Point<T>;
+
+                fn mul(self, point: Point<T>) -> Self::Output {
+                    point * self
+                }
+            }
+
+            impl<T> Mul<&Point<T>> for $t
+            where
+                Point<T>: Mul<$t, Output = Point<T>> + Clone,
+            {
+                type Output = Point<T>;
+
+                fn mul(self, point: &Point<T>) -> Self::Output {
+                    point.clone() * self
+                }
+            }
+        )*
+    };
+}
+
+impl_scalar_mul_point!(u8, u16, u32, u64, u128, i32, i64, U256);
+
+/// Serialized as `None` for the point at infinity and `Some((x, y, a, b))`
+/// otherwise; deserialization rejects coordinates that are off the curve.
+#[cfg(feature = "serde")]
+impl<T> serde::Serialize for Point<T>
+where
+    T: serde::Serialize,
+{
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            Point::Coordinate { x, y, a, b } => serializer.serialize_some(&(x, y, a, b)),
+            Point::Infinity => serializer.serialize_none(),
+        }
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de, T> serde::Deserialize<'de> for Point<T>
+where
+    T: serde::Deserialize<'de> + Add<Output = T> + Mul<Output = T> + PartialEq + Clone,
+{
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        match <Option<(T, T, T, T)>>::deserialize(deserializer)? {
+            Some((x, y, a, b)) => Point::try_new(x, y, a, b).map_err(serde::de::Error::custom),
+            None => Ok(Point::Infinity),
         }
-        ret
     }
 }
 
 #[cfg(test)]
 mod tests {
     use super::Point;
+    use crate::ct::{Choice, ConditionallySelectable};
     use crate::field_element::FieldElement;
-    use primitive_types::{U256, U512};
-    use sha2::Sha256;
+    use crate::secret::SecretScalar;
+    use primitive_types::U256;
 
     #[test]
     fn new() {
         let _ = Point::new(U256::from(18), U256::from(77), U256::from(5), U256::from(7));
     }
 
+    #[test]
+    fn try_new() {
+        use crate::error::PointError;
+
+        assert!(Point::try_new(-1, -1, 5, 7).is_ok());
+        assert_eq!(Point::try_new(-1, -2, 5, 7), Err(PointError::NotOnCurve));
+    }
+
     #[test]
     fn eq_elliptic() {
         let a = Point::new(U256::from(18), U256::from(77), U256::from(5), U256::from(7));
@@ -159,6 +683,155 @@ mod tests {
         assert_eq!(y * y, x * x * x + a * x + b);
     }
 
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ops_on_references() {
+        let p0 = Point::new(-1, -1, 5, 7);
+        let p1 = Point::new(2, 5, 5, 7);
+
+        assert_eq!(&p0 + &p1, p0.clone() + p1.clone());
+        assert_eq!(&p0 + p1.clone(), p0.clone() + &p1);
+        assert_eq!(&p1 * 3, p1 * 3);
+
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+        assert_eq!(&g * 3, g * 3);
+    }
+
+    #[cfg(feature = "num-bigint")]
+    #[test]
+    fn add_with_biguint() {
+        use num_bigint::BigUint;
+
+        let prime = BigUint::from(223u32);
+        let fe = |n: u32| FieldElement::new(BigUint::from(n), prime.clone());
+        let p0 = Point::new(fe(192), fe(105), fe(0), fe(7));
+        let p1 = Point::new(fe(17), fe(56), fe(0), fe(7));
+
+        assert_eq!(&p0 + &p1, Point::new(fe(170), fe(142), fe(0), fe(7)));
+        assert_eq!(&p0 + &p0, Point::new(fe(49), fe(71), fe(0), fe(7)));
+    }
+
+    #[test]
+    fn add_assign() {
+        let p0 = Point::new(-1, -1, 5, 7);
+        let mut acc = Point::Infinity;
+        acc += &p0;
+        acc += p0.clone();
+
+        assert_eq!(acc, p0.clone() + p0);
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let p = Point::new(
+            FieldElement::new(U256::from(192), U256::from(223)),
+            FieldElement::new(U256::from(105), U256::from(223)),
+            FieldElement::new(U256::from(0), U256::from(223)),
+            FieldElement::new(U256::from(7), U256::from(223)),
+        );
+        let json = serde_json::to_string(&p).unwrap();
+        assert_eq!(serde_json::from_str::<Point<_>>(&json).unwrap(), p);
+
+        let infinity: Point<FieldElement<U256>> = Point::Infinity;
+        assert_eq!(serde_json::to_string(&infinity).unwrap(), "null");
+        assert_eq!(
+            serde_json::from_str::<Point<FieldElement<U256>>>("null").unwrap(),
+            infinity
+        );
+
+        let off_curve = json.replacen("c0", "c1", 1);
+        assert!(serde_json::from_str::<Point<FieldElement<U256>>>(&off_curve).is_err());
+    }
+
+    #[test]
+    fn hash_and_ord() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let p0 = Point::new(-1, -1, 5, 7);
+        let p1 = Point::new(2, 5, 5, 7);
+        let p2 = Point::new(2, -5, 5, 7);
+        let points = vec![
+            p1.clone(),
+            Point::Infinity,
+            p0.clone(),
+            p2.clone(),
+            p1.clone(),
+        ];
+
+        let hashed: HashSet<_> = points.iter().cloned().collect();
+        assert_eq!(hashed.len(), 4);
+
+        let ordered: Vec<_> = points
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(ordered, vec![p0, p2, p1, Point::Infinity]);
+    }
+
+    #[test]
+    fn mul_large_scalar() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(15), fe(86), fe(0), fe(7));
+
+        // この点の位数は 7
+        assert_eq!(g.clone() * U256::from(7), Point::Infinity);
+        assert_eq!(g.clone() * U256::from(7 * 1_000_003 + 1), g);
+        assert_eq!(
+            g.clone() * (U256::MAX - U256::MAX % U256::from(7)),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn double() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let p = Point::new(fe(192), fe(105), fe(0), fe(7));
+
+        assert_eq!(p.double(), Point::new(fe(49), fe(71), fe(0), fe(7)));
+        assert_eq!(p.double(), p.clone() + p);
+        assert_eq!(
+            Point::<FieldElement<U256>>::Infinity.double(),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn double_with_zero_y() {
+        // y^2 = x^3 + 7 上で y = 0 となる点 (x^3 ≡ -7)
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let x = (0..223u64)
+            .map(fe)
+            .find(|x| x.cube() + fe(7) == fe(0))
+            .unwrap();
+        let p = Point::new(x, fe(0), fe(0), fe(7));
+
+        assert_eq!(p.double(), Point::Infinity);
+        assert_eq!(p.clone() + p, Point::Infinity);
+        assert_eq!(Point::new(-1, 0, 0, 1).double(), Point::Infinity);
+    }
+
+    #[test]
+    fn neg_and_sub() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let p = Point::new(fe(192), fe(105), fe(0), fe(7));
+        let q = Point::new(fe(17), fe(56), fe(0), fe(7));
+
+        assert_eq!(-&p, Point::new(fe(192), fe(118), fe(0), fe(7)));
+        assert_eq!(&p + -&p, Point::Infinity);
+        assert_eq!((&p + &q) - &q, p);
+        assert_eq!(&p - &p, Point::Infinity);
+        assert_eq!(-Point::<FieldElement<U256>>::Infinity, Point::Infinity);
+        assert_eq!(-Point::new(2, 5, 5, 7), Point::new(2, -5, 5, 7));
+    }
+
     #[test]
     fn mul() {
         let p0 = Point::new(2, 5, 5, 7);
@@ -167,61 +840,200 @@ mod tests {
         assert_ne!(p0, p1);
         assert_eq!(p0.clone() * 3, p1);
         assert_eq!(p0 * U256::from(3), p1);
+
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+
+        // 本の練習問題: (47, 71) の倍数
+        assert_eq!(g.clone() * 2, Point::new(fe(36), fe(111), fe(0), fe(7)));
+        assert_eq!(g.clone() * 3, Point::new(fe(15), fe(137), fe(0), fe(7)));
+        assert_eq!(
+            g.clone() * U256::from(10),
+            Point::new(fe(154), fe(150), fe(0), fe(7))
+        );
+        assert_eq!(g.clone() * 21, Point::Infinity);
+        assert_eq!(g.clone() * U256::zero(), Point::Infinity);
+
+        // Jacobian 経由の結果がアフィンの逐次加算と一致する
+        let mut acc = Point::Infinity;
+        for k in 1..=21u32 {
+            acc += &g;
+            assert_eq!(g.clone() * k, acc);
+        }
     }
 
     #[test]
-    fn on_the_curve() {
-        let p = U512::from_str_radix(
-            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
-            16,
-        )
-        .unwrap();
-        let x = U512::from_str_radix(
-            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
-            16,
-        )
-        .unwrap();
-        let y = U512::from_str_radix(
-            "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+    fn conditional_select() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let p = Point::new(fe(47), fe(71), fe(0), fe(7));
+        let q = Point::new(fe(36), fe(111), fe(0), fe(7));
+        let inf = Point::Infinity;
+
+        for (a, b) in [(&p, &q), (&p, &inf), (&inf, &q), (&inf, &inf)] {
+            assert_eq!(&Point::conditional_select(a, b, Choice::from(0)), a);
+            assert_eq!(&Point::conditional_select(a, b, Choice::from(1)), b);
+        }
+
+        let (mut a, mut b) = (p.clone(), inf.clone());
+        Point::conditional_swap(&mut a, &mut b, Choice::from(1));
+        assert_eq!((a, b), (inf, p));
+    }
+
+    #[test]
+    fn mul_secret() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+
+        for k in [0u64, 1, 2, 7, 20, 21, 22, 1_000_003] {
+            assert_eq!(
+                g.mul_secret(&SecretScalar::new(U256::from(k))),
+                g.clone() * k
+            );
+        }
+
+        // secp256k1 の生成元でも double-and-add と一致する
+        use crate::s256_field::S256Field;
+        let g = Point::new(
+            S256Field::from_hex("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")
+                .unwrap(),
+            S256Field::from_hex("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8")
+                .unwrap(),
+            S256Field::from(0),
+            S256Field::from(7),
+        );
+        let k = U256::from_str_radix(
+            "3D8A1C5E9F70B2461E0D7C93A5B8F2106C4E9D7A2B1F3E5C8D0A6B4927F1E3C5",
             16,
         )
         .unwrap();
-        let n = U512::from_str_radix(
-            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141",
+        assert_eq!(g.mul_secret(&SecretScalar::new(k)), g * k);
+    }
+
+    #[test]
+    fn mul_wnaf() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+
+        for k in [0u64, 1, 2, 7, 20, 21, 22, 1_000_003] {
+            assert_eq!(g.mul_wnaf(U256::from(k), 4), g.clone() * k);
+        }
+    }
+
+    #[test]
+    fn multi_mul() {
+        use crate::secp256k1::generator;
+
+        let g = generator();
+        let p = g.clone() * U256::from(0xdeadbeefu64);
+        let u = U256::from_str_radix(
+            "3D8A1C5E9F70B2461E0D7C93A5B8F2106C4E9D7A2B1F3E5C8D0A6B4927F1E3C5",
             16,
         )
         .unwrap();
+        let v = U256::MAX - U256::from(42);
 
-        let a = FieldElement::new(U512::from(0), p);
-        let b = FieldElement::new(U512::from(7), p);
-        let gx = FieldElement::new(x, p);
-        let gy = FieldElement::new(y, p);
+        let expected = g.clone() * u + p.clone() * v;
+        assert_eq!(
+            Point::multi_mul(&[(u, g.clone()), (v, p.clone())]),
+            expected
+        );
+        assert_eq!(
+            Point::multi_mul(&[(u, g.clone()), (v, p.clone()), (U256::one(), g.clone())]),
+            expected + g.clone()
+        );
+        assert_eq!(Point::multi_mul(&[(u, g.clone())]), g.clone() * u);
 
-        fn make_hash(source: &[u8]) -> U512 {
-            let mut hasher = Sha256::new();
-            hasher.update(source);
-            U512::from(&hasher.finalize()[..])
-        }
+        // 閾値以上は Pippenger
+        let terms: Vec<_> = (1..=crate::jacobian::PIPPENGER_THRESHOLD as u64)
+            .map(|i| (U256::from(i), g.clone()))
+            .collect();
+        let n = crate::jacobian::PIPPENGER_THRESHOLD as u64;
+        assert_eq!(Point::multi_mul(&terms), g * U256::from(n * (n + 1) / 2));
+        assert_eq!(
+            Point::<crate::s256_field::S256Field>::multi_mul(&[]),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn accessors() {
+        let p = Point::new(-1, -1, 5, 7);
+
+        assert!(p.is_on_curve());
+        assert!(!p.is_infinity());
+        assert_eq!(p.x(), Some(-1));
+        assert_eq!(p.y(), Some(-1));
+
+        let infinity = Point::<i32>::Infinity;
+        assert!(infinity.is_on_curve());
+        assert!(infinity.is_infinity());
+        assert_eq!(infinity.x(), None);
+        assert_eq!(infinity.y(), None);
 
-        // 署名ハッシュ作成
-        let z = FieldElement::new(make_hash(b"This is my sign"), n);
+        // コンストラクタを通さない点は検査で弾かれる
+        let off_curve = Point::Coordinate {
+            x: -1,
+            y: -2,
+            a: 5,
+            b: 7,
+        };
+        assert!(!off_curve.is_on_curve());
+    }
+
+    #[test]
+    fn scalar_on_the_left() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+
+        assert_eq!(3 * g.clone(), g.clone() * 3);
+        assert_eq!(3u64 * &g, g.clone() * 3u64);
+        assert_eq!(U256::from(10) * &g, g * U256::from(10));
+    }
 
-        // 秘密鍵作成
-        let e = FieldElement::new(make_hash(b"This is my secret"), n);
+    #[test]
+    // clippy は名前だけで Iterator::cycle と誤認する
+    #[allow(clippy::infinite_iter)]
+    fn cycle_and_order() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
 
-        // 乱数kを生成
-        use rand::Rng;
-        let mut rng = rand::thread_rng();
-        let i: i32 = rng.gen();
-        let k = FieldElement::new(U512::from(rng.gen::<i32>()), n);
+        // 本の練習問題: (15, 86) の位数は 7
+        let g = Point::new(fe(15), fe(86), fe(0), fe(7));
+        let points: Vec<_> = g.cycle().collect();
+        assert_eq!(points.len(), 6);
+        assert_eq!(points[0], g);
+        assert_eq!(points[1], g.double());
+        assert_eq!(points[5], -g.clone());
+        assert_eq!(g.order(), 7);
 
-        let powed = FieldElement::new(n - U512::from(2), n);
+        assert_eq!(Point::new(fe(47), fe(71), fe(0), fe(7)).order(), 21);
+        assert_eq!(Point::<FieldElement<U256>>::Infinity.order(), 1);
+    }
 
-        let G = Point::new(gx, gy, a, b);
-        let r = (G * k).x;
-        let mut k_inv = (FieldElement::new(k, n)).pow(powed);
-        let s = (z + r * e) * k_inv;
+    #[test]
+    fn batch_add() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        let g = Point::new(fe(47), fe(71), fe(0), fe(7));
+        let p = Point::new(fe(192), fe(105), fe(0), fe(7));
+        let q = Point::new(fe(17), fe(56), fe(0), fe(7));
 
-        let P = G * e;
+        let pairs = vec![
+            (p.clone(), q.clone()),
+            (g.clone(), g.clone()),
+            (p.clone(), -p.clone()),
+            (Point::Infinity, q.clone()),
+            (g.clone(), Point::Infinity),
+            (g.clone() * 5, g.clone() * 9),
+            (Point::Infinity, Point::Infinity),
+        ];
+        let expected: Vec<_> = pairs.iter().map(|(a, b)| a + b).collect();
+        assert_eq!(Point::batch_add(&pairs), expected);
+        assert_eq!(Point::<FieldElement<U256>>::batch_add(&[]), vec![]);
     }
 }
@@ -0,0 +1,173 @@
+//! Bitcoin's wire encodings of integers: little-endian fixed-width
+//! integers and the variable-length integer (CompactSize) that prefixes
+//! every list and byte string in transactions, scripts and network
+//! messages.
+
+use std::io::{self, Read, Write};
+
+/// Bitcoin's variable-length integer (CompactSize): one byte below `0xfd`,
+/// otherwise a marker byte followed by 2, 4 or 8 little-endian bytes.
+pub fn encode_varint(n: u64) -> Vec<u8> {
+    match n {
+        0..=0xfc => vec![n as u8],
+        0xfd..=0xffff => {
+            let mut ret = vec![0xfd];
+            ret.extend_from_slice(&(n as u16).to_le_bytes());
+            ret
+        }
+        0x1_0000..=0xffff_ffff => {
+            let mut ret = vec![0xfe];
+            ret.extend_from_slice(&(n as u32).to_le_bytes());
+            ret
+        }
+        _ => {
+            let mut ret = vec![0xff];
+            ret.extend_from_slice(&n.to_le_bytes());
+            ret
+        }
+    }
+}
+
+/// Reads a variable-length integer written by `encode_varint`.
+///
+/// As in Bitcoin Core, a value encoded with more bytes than necessary is
+/// rejected with `ErrorKind::InvalidData`, so every integer has exactly
+/// one encoding.
+pub fn read_varint<R: Read + ?Sized>(reader: &mut R) -> io::Result<u64> {
+    let (n, min) = match read_u8(reader)? {
+        0xfd => (read_u16_le(reader)? as u64, 0xfd),
+        0xfe => (read_u32_le(reader)? as u64, 0x1_0000),
+        0xff => (read_u64_le(reader)?, 0x1_0000_0000),
+        n => return Ok(n as u64),
+    };
+    if n < min {
+        return Err(io::Error::new(
+            io::ErrorKind::InvalidData,
+            "non-canonical varint",
+        ));
+    }
+    Ok(n)
+}
+
+/// Writes `encode_varint(n)`.
+pub fn write_varint<W: Write + ?Sized>(writer: &mut W, n: u64) -> io::Result<()> {
+    writer.write_all(&encode_varint(n))
+}
+
+pub fn read_u8<R: Read + ?Sized>(reader: &mut R) -> io::Result<u8> {
+    let mut buf = [0u8; 1];
+    reader.read_exact(&mut buf)?;
+    Ok(buf[0])
+}
+
+pub fn read_u16_le<R: Read + ?Sized>(reader: &mut R) -> io::Result<u16> {
+    let mut buf = [0u8; 2];
+    reader.read_exact(&mut buf)?;
+    Ok(u16::from_le_bytes(buf))
+}
+
+pub fn read_u32_le<R: Read + ?Sized>(reader: &mut R) -> io::Result<u32> {
+    let mut buf = [0u8; 4];
+    reader.read_exact(&mut buf)?;
+    Ok(u32::from_le_bytes(buf))
+}
+
+pub fn read_u64_le<R: Read + ?Sized>(reader: &mut R) -> io::Result<u64> {
+    let mut buf = [0u8; 8];
+    reader.read_exact(&mut buf)?;
+    Ok(u64::from_le_bytes(buf))
+}
+
+pub fn write_u16_le<W: Write + ?Sized>(writer: &mut W, n: u16) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+pub fn write_u32_le<W: Write + ?Sized>(writer: &mut W, n: u32) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+pub fn write_u64_le<W: Write + ?Sized>(writer: &mut W, n: u64) -> io::Result<()> {
+    writer.write_all(&n.to_le_bytes())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        encode_varint, read_u16_le, read_u32_le, read_u64_le, read_varint, write_u16_le,
+        write_u32_le, write_u64_le, write_varint,
+    };
+    use std::io::{Cursor, ErrorKind};
+
+    #[test]
+    fn varint_boundaries() {
+        assert_eq!(encode_varint(0), [0x00]);
+        assert_eq!(encode_varint(0xfc), [0xfc]);
+        assert_eq!(encode_varint(0xfd), [0xfd, 0xfd, 0x00]);
+        assert_eq!(encode_varint(0xffff), [0xfd, 0xff, 0xff]);
+        assert_eq!(encode_varint(0x1_0000), [0xfe, 0x00, 0x00, 0x01, 0x00]);
+        assert_eq!(
+            encode_varint(0x1_0000_0000),
+            [0xff, 0x00, 0x00, 0x00, 0x00, 0x01, 0x00, 0x00, 0x00]
+        );
+    }
+
+    #[test]
+    fn varint_roundtrip() {
+        for n in [
+            0,
+            1,
+            0xfc,
+            0xfd,
+            0xffff,
+            0x1_0000,
+            0xffff_ffff,
+            0x1_0000_0000,
+            u64::MAX,
+        ] {
+            let mut buf = Vec::new();
+            write_varint(&mut buf, n).unwrap();
+            assert_eq!(buf, encode_varint(n));
+            let mut reader = Cursor::new(&buf);
+            assert_eq!(read_varint(&mut reader).unwrap(), n);
+            assert_eq!(reader.position() as usize, buf.len());
+        }
+    }
+
+    #[test]
+    fn varint_errors() {
+        // 必要以上に長い表現は受け付けない
+        for bytes in [
+            &[0xfd, 0xfc, 0x00][..],
+            &[0xfe, 0xff, 0xff, 0x00, 0x00],
+            &[0xff, 0xff, 0xff, 0xff, 0xff, 0x00, 0x00, 0x00, 0x00],
+        ] {
+            let err = read_varint(&mut Cursor::new(bytes)).unwrap_err();
+            assert_eq!(err.kind(), ErrorKind::InvalidData);
+        }
+        let err = read_varint(&mut Cursor::new([0xfe, 0x00])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+        let err = read_varint(&mut Cursor::new([])).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn fixed_width() {
+        let mut buf = Vec::new();
+        write_u16_le(&mut buf, 0x0102).unwrap();
+        write_u32_le(&mut buf, 0x0304_0506).unwrap();
+        write_u64_le(&mut buf, 0x0708_090a_0b0c_0d0e).unwrap();
+        assert_eq!(
+            buf,
+            [0x02, 0x01, 0x06, 0x05, 0x04, 0x03, 0x0e, 0x0d, 0x0c, 0x0b, 0x0a, 0x09, 0x08, 0x07]
+        );
+
+        let mut reader = Cursor::new(&buf);
+        assert_eq!(read_u16_le(&mut reader).unwrap(), 0x0102);
+        assert_eq!(read_u32_le(&mut reader).unwrap(), 0x0304_0506);
+        assert_eq!(read_u64_le(&mut reader).unwrap(), 0x0708_090a_0b0c_0d0e);
+        assert_eq!(
+            read_u16_le(&mut reader).unwrap_err().kind(),
+            ErrorKind::UnexpectedEof
+        );
+    }
+}
@@ -0,0 +1,378 @@
+use std::error::Error;
+use std::fmt;
+
+use crate::script::ScriptType;
+
+/// Errors raised while constructing or combining field elements.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FieldError {
+    /// The number is not in the range `0..prime`.
+    NotInRange,
+    /// The element belongs to a field with a different prime.
+    PrimeMismatch,
+    /// The input is not a hex string of at most 64 digits.
+    InvalidHex,
+    /// A byte encoding did not have the expected length.
+    InvalidLength,
+}
+
+impl fmt::Display for FieldError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FieldError::NotInRange => write!(f, "number is not in the field range"),
+            FieldError::PrimeMismatch => write!(f, "prime numbers do not match"),
+            FieldError::InvalidHex => write!(f, "invalid hex string"),
+            FieldError::InvalidLength => write!(f, "invalid byte length"),
+        }
+    }
+}
+
+impl Error for FieldError {}
+
+/// Errors raised while constructing or combining elliptic curve points.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum PointError {
+    /// The coordinates do not satisfy `y^2 = x^3 + a*x + b`.
+    NotOnCurve,
+}
+
+impl fmt::Display for PointError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            PointError::NotOnCurve => write!(f, "point is not on the curve"),
+        }
+    }
+}
+
+impl Error for PointError {}
+
+/// Errors raised while decoding serialized keys, points and signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ParseError {
+    /// The input is too short or too long for its encoding.
+    InvalidLength,
+    /// The leading byte does not name a known encoding.
+    InvalidPrefix,
+    /// A coordinate is not below the field prime.
+    InvalidCoordinate,
+    /// The decoded coordinates are not a point on the curve.
+    NotOnCurve,
+    /// A character outside the alphabet of the encoding.
+    InvalidCharacter,
+    /// The embedded checksum does not match the payload.
+    InvalidChecksum,
+    /// The trailing byte of a compressed WIF key is not `0x01`.
+    InvalidCompressionFlag,
+    /// A secret scalar is zero or not below `N`.
+    InvalidScalar,
+    /// A depth-0 extended key whose parent fingerprint or child number is
+    /// not zero.
+    InvalidMasterKey,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ParseError::InvalidLength => write!(f, "invalid encoding length"),
+            ParseError::InvalidPrefix => write!(f, "invalid encoding prefix"),
+            ParseError::InvalidCoordinate => write!(f, "coordinate is not in the field range"),
+            ParseError::NotOnCurve => write!(f, "point is not on the curve"),
+            ParseError::InvalidCharacter => write!(f, "invalid character"),
+            ParseError::InvalidChecksum => write!(f, "checksum mismatch"),
+            ParseError::InvalidCompressionFlag => write!(f, "invalid compression flag"),
+            ParseError::InvalidScalar => write!(f, "scalar is not in the range 1..N"),
+            ParseError::InvalidMasterKey => {
+                write!(f, "master key has a parent fingerprint or child number")
+            }
+        }
+    }
+}
+
+impl Error for ParseError {}
+
+/// Errors raised while tweaking keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TweakError {
+    /// Multiplying by a zero tweak would lose the key.
+    ZeroTweak,
+    /// The tweaked key is zero or the point at infinity.
+    InvalidResult,
+}
+
+impl fmt::Display for TweakError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TweakError::ZeroTweak => write!(f, "tweak must not be zero"),
+            TweakError::InvalidResult => write!(f, "tweaked key is invalid"),
+        }
+    }
+}
+
+impl Error for TweakError {}
+
+/// Errors raised while deriving BIP32 extended keys.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HdError {
+    /// Seeds must be between 16 and 64 bytes long.
+    InvalidSeedLength,
+    /// The derived key is invalid (probability below 2^-127); BIP32 says to
+    /// proceed with the next index.
+    InvalidKey,
+    /// Hardened children cannot be derived from a public key.
+    HardenedFromPublic,
+    /// The depth would exceed 255.
+    MaxDepth,
+    /// A derivation path is not of the form `m/44'/0'/0'/0/5`.
+    InvalidPath,
+}
+
+impl fmt::Display for HdError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HdError::InvalidSeedLength => write!(f, "seed must be 16 to 64 bytes long"),
+            HdError::InvalidKey => write!(f, "derived key is invalid"),
+            HdError::HardenedFromPublic => {
+                write!(f, "cannot derive a hardened child from a public key")
+            }
+            HdError::MaxDepth => write!(f, "maximum derivation depth exceeded"),
+            HdError::InvalidPath => write!(f, "invalid derivation path"),
+        }
+    }
+}
+
+impl Error for HdError {}
+
+/// Errors raised while building or parsing BIP39 mnemonics.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MnemonicError {
+    /// Entropy must be 128 to 256 bits in steps of 32.
+    InvalidEntropyLength,
+    /// A mnemonic has 12, 15, 18, 21 or 24 words.
+    InvalidWordCount,
+    /// The word at this position is not in the wordlist.
+    UnknownWord(usize),
+    /// The checksum bits do not match the entropy.
+    InvalidChecksum,
+}
+
+impl fmt::Display for MnemonicError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MnemonicError::InvalidEntropyLength => {
+                write!(f, "entropy must be 128 to 256 bits in steps of 32")
+            }
+            MnemonicError::InvalidWordCount => write!(f, "invalid number of words"),
+            MnemonicError::UnknownWord(i) => write!(f, "word {} is not in the wordlist", i),
+            MnemonicError::InvalidChecksum => write!(f, "checksum mismatch"),
+        }
+    }
+}
+
+impl Error for MnemonicError {}
+
+/// Errors raised by MuSig2 key aggregation and signing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MusigError {
+    /// The public key at this index is the point at infinity.
+    InvalidPublicKey(usize),
+    /// The aggregate key is the point at infinity.
+    InvalidAggregateKey,
+    /// The signer's public key is not among the aggregated keys.
+    UnknownSigner,
+    /// The secret nonce was generated for a different key.
+    SecNonceMismatch,
+}
+
+impl fmt::Display for MusigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            MusigError::InvalidPublicKey(i) => write!(f, "invalid public key at index {}", i),
+            MusigError::InvalidAggregateKey => write!(f, "aggregate key is the point at infinity"),
+            MusigError::UnknownSigner => write!(f, "signer is not part of the aggregate key"),
+            MusigError::SecNonceMismatch => write!(f, "secret nonce belongs to a different key"),
+        }
+    }
+}
+
+impl Error for MusigError {}
+
+/// Errors raised by FROST key generation and threshold signing.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FrostError {
+    /// The threshold must be between 1 and the number of participants.
+    InvalidThreshold,
+    /// Two commitments carry the same participant index.
+    DuplicateSigner,
+    /// Fewer signers took part than the threshold requires.
+    TooFewSigners,
+    /// The signer did not publish a commitment for this session, or the
+    /// nonces belong to a different participant.
+    UnknownSigner,
+    /// The signing package's commitment for this signer is not the one
+    /// made for their nonces.
+    CommitmentMismatch,
+}
+
+impl fmt::Display for FrostError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FrostError::InvalidThreshold => write!(f, "invalid threshold"),
+            FrostError::DuplicateSigner => write!(f, "duplicate participant index"),
+            FrostError::TooFewSigners => write!(f, "fewer signers than the threshold"),
+            FrostError::UnknownSigner => write!(f, "signer has no commitment in this session"),
+            FrostError::CommitmentMismatch => write!(f, "commitment does not match the nonces"),
+        }
+    }
+}
+
+impl Error for FrostError {}
+
+/// Errors raised while splitting or recovering a Shamir-shared secret.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ShamirError {
+    /// The threshold must be between 1 and the number of shares, and at
+    /// most 16 shares can be made.
+    InvalidThreshold,
+    /// Secrets must be at least 16 bytes and an even number of bytes.
+    InvalidSecretLength,
+    /// Fewer shares were given than the threshold requires.
+    NotEnoughShares,
+    /// The shares disagree on threshold or length, or repeat an index.
+    InconsistentShares,
+    /// The recovered secret does not match its digest: a share is wrong or
+    /// belongs to another secret.
+    InvalidDigest,
+}
+
+impl fmt::Display for ShamirError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            ShamirError::InvalidThreshold => write!(f, "invalid threshold or share count"),
+            ShamirError::InvalidSecretLength => {
+                write!(f, "secret must be an even number of bytes, at least 16")
+            }
+            ShamirError::NotEnoughShares => write!(f, "not enough shares"),
+            ShamirError::InconsistentShares => write!(f, "shares are inconsistent"),
+            ShamirError::InvalidDigest => write!(f, "shared secret digest mismatch"),
+        }
+    }
+}
+
+impl Error for ShamirError {}
+
+/// Errors raised while constructing or parsing ECDSA signatures.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SigError {
+    /// `r` or `s` is not in the range `1..N`.
+    OutOfRange,
+    /// A DER length field disagrees with the data, or an integer is empty.
+    InvalidLength,
+    /// A DER sequence or integer tag is missing.
+    InvalidMarker,
+    /// A DER integer has its sign bit set.
+    NegativeInteger,
+    /// A DER integer starts with a zero byte it does not need.
+    ExcessPadding,
+}
+
+impl fmt::Display for SigError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SigError::OutOfRange => write!(f, "r and s must be in [1, N - 1]"),
+            SigError::InvalidLength => write!(f, "invalid DER length"),
+            SigError::InvalidMarker => write!(f, "invalid DER tag"),
+            SigError::NegativeInteger => write!(f, "negative DER integer"),
+            SigError::ExcessPadding => write!(f, "DER integer has excess padding"),
+        }
+    }
+}
+
+impl Error for SigError {}
+
+/// Errors raised while decoding hex strings.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum HexError {
+    /// The string has an odd number of digits.
+    OddLength,
+    /// `character` at byte offset `index` is not a hex digit.
+    InvalidCharacter { character: char, index: usize },
+    /// The decoded bytes do not fit a fixed-size value.
+    InvalidLength { expected: usize, actual: usize },
+}
+
+impl fmt::Display for HexError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            HexError::OddLength => write!(f, "odd number of hex digits"),
+            HexError::InvalidCharacter { character, index } => {
+                write!(f, "invalid hex character {:?} at {}", character, index)
+            }
+            HexError::InvalidLength { expected, actual } => {
+                write!(f, "expected {} bytes, got {}", expected, actual)
+            }
+        }
+    }
+}
+
+impl Error for HexError {}
+
+/// Errors raised while converting between scripts and addresses.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressError {
+    /// The script does not match any standard template.
+    NonStandardScript,
+    /// The script is standard but its type (P2PK, `OP_RETURN`) has no
+    /// address.
+    NoAddress(ScriptType),
+    /// Segwit outputs only commit to compressed public keys (BIP143).
+    UncompressedKey,
+    /// The bytes are not a SEC public key.
+    InvalidKey,
+}
+
+impl fmt::Display for AddressError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            AddressError::NonStandardScript => write!(f, "non-standard script"),
+            AddressError::NoAddress(script_type) => {
+                write!(f, "{:?} scripts have no address", script_type)
+            }
+            AddressError::UncompressedKey => write!(f, "segwit requires a compressed key"),
+            AddressError::InvalidKey => write!(f, "invalid public key"),
+        }
+    }
+}
+
+impl Error for AddressError {}
+
+/// Errors raised while parsing a BIP21 payment URI.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum UriError {
+    /// The URI does not start with `bitcoin:`.
+    InvalidScheme,
+    /// The address part does not parse.
+    InvalidAddress(ParseError),
+    /// The amount is not a decimal number of bitcoin within the supply.
+    InvalidAmount,
+    /// A malformed `%XX` escape, or a value that is not UTF-8.
+    InvalidEncoding,
+    /// A parameter appears more than once.
+    DuplicateParameter,
+    /// A `req-` parameter this parser does not understand.
+    UnknownRequiredParameter,
+}
+
+impl fmt::Display for UriError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            UriError::InvalidScheme => write!(f, "not a bitcoin: URI"),
+            UriError::InvalidAddress(e) => write!(f, "invalid address: {}", e),
+            UriError::InvalidAmount => write!(f, "invalid amount"),
+            UriError::InvalidEncoding => write!(f, "invalid percent-encoding"),
+            UriError::DuplicateParameter => write!(f, "duplicate parameter"),
+            UriError::UnknownRequiredParameter => write!(f, "unknown required parameter"),
+        }
+    }
+}
+
+impl Error for UriError {}
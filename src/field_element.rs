@@ -1,6 +1,18 @@
+use std::cmp::Ordering;
 use std::fmt;
-use std::fmt::{Debug, Display};
-use std::ops::{Add, Div, Mul, Rem, Sub};
+use std::fmt::Debug;
+use std::hash::{Hash, Hasher};
+use std::iter::{Product, Sum};
+use std::ops::{Add, AddAssign, Div, DivAssign, Mul, MulAssign, Neg, Rem, Sub, SubAssign};
+
+use primitive_types::U256;
+use rand::RngCore;
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::error::FieldError;
+use crate::macros::{forward_op_assign, forward_ref_binop};
+use crate::modular::{Exponent, MulMod};
+use crate::random;
 
 #[derive(Clone, Copy, Debug)]
 pub struct FieldElement<T> {
@@ -12,12 +24,70 @@ impl<T> FieldElement<T>
 where
     T: PartialOrd + Debug,
 {
-    pub fn new(num: T, prime: T) -> Self {
+    pub fn try_new(num: T, prime: T) -> Result<Self, FieldError> {
         if num >= prime {
-            panic!("Num {:?} not in field range o to {:?}", num, prime);
+            return Err(FieldError::NotInRange);
         }
-        Self { num, prime }
+        Ok(Self { num, prime })
+    }
+
+    pub fn new(num: T, prime: T) -> Self {
+        match Self::try_new(num, prime) {
+            Ok(element) => element,
+            Err(err) => panic!("{}", err),
+        }
+    }
+}
+
+/// Field types that can produce their multiplicative identity from any
+/// element of the same field. A `FieldElement` only knows its prime at
+/// runtime, so the identity has to be derived from an existing value.
+pub trait One {
+    fn one(&self) -> Self;
+}
+
+impl<T> One for FieldElement<T>
+where
+    T: From<u8> + Clone,
+{
+    fn one(&self) -> Self {
+        Self {
+            num: T::from(1),
+            prime: self.prime.clone(),
+        }
+    }
+}
+
+/// Inverts every element in place with a single field inversion
+/// (Montgomery's trick), for any field type: `3(n-1)` multiplications plus
+/// one division.
+///
+/// Panics if any element is zero.
+pub fn batch_invert<F>(elements: &mut [F])
+where
+    F: Mul<Output = F> + Div<Output = F> + One + Clone,
+{
+    let Some(first) = elements.first() else {
+        return;
+    };
+
+    // prefix[i] = elements[0] * ... * elements[i]
+    let mut prefix = Vec::with_capacity(elements.len());
+    let mut acc = first.clone();
+    prefix.push(acc.clone());
+    for element in &elements[1..] {
+        acc = acc * element.clone();
+        prefix.push(acc.clone());
     }
+
+    // inv = (elements[0] * ... * elements[i])^-1 を後ろから剥がしていく
+    let mut inv = acc.one() / acc;
+    for i in (1..elements.len()).rev() {
+        let next = inv.clone() * elements[i].clone();
+        elements[i] = inv.clone() * prefix[i - 1].clone();
+        inv = next;
+    }
+    elements[0] = inv;
 }
 
 impl<T> fmt::Display for FieldElement<T>
@@ -29,9 +99,81 @@ where
     }
 }
 
+impl FieldElement<U256> {
+    /// Parses a big-endian hex string (optionally `0x`-prefixed, at most 64
+    /// digits) into an element of the field with the given prime.
+    pub fn from_hex(hex: &str, prime: U256) -> Result<Self, FieldError> {
+        let digits = hex.strip_prefix("0x").unwrap_or(hex);
+        if digits.is_empty() || digits.len() > 64 {
+            return Err(FieldError::InvalidHex);
+        }
+        let num = U256::from_str_radix(digits, 16).map_err(|_| FieldError::InvalidHex)?;
+        Self::try_new(num, prime)
+    }
+
+    /// Parses exactly 32 big-endian bytes.
+    pub fn from_be_bytes(bytes: &[u8], prime: U256) -> Result<Self, FieldError> {
+        if bytes.len() != 32 {
+            return Err(FieldError::InvalidLength);
+        }
+        Self::try_new(U256::from_big_endian(bytes), prime)
+    }
+
+    /// `num` as 32 big-endian bytes.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.num.to_big_endian(&mut bytes);
+        bytes
+    }
+
+    /// Uniformly random element of the field, without modulo bias.
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R, prime: U256) -> Self {
+        Self::new(random::uniform_below(rng, prime), prime)
+    }
+}
+
+/// Builds an element from a `(num, prime)` literal pair.
+impl TryFrom<(u64, u64)> for FieldElement<U256> {
+    type Error = FieldError;
+
+    fn try_from((num, prime): (u64, u64)) -> Result<Self, Self::Error> {
+        Self::try_new(U256::from(num), U256::from(prime))
+    }
+}
+
+/// Compares both `num` and `prime` limb by limb without early exit, so the
+/// running time does not depend on where two secret values first differ.
+impl ConstantTimeEq for FieldElement<U256> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.num.ct_eq(&other.num) & self.prime.ct_eq(&other.prime)
+    }
+}
+
+impl ConditionallySelectable for FieldElement<U256> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            num: U256::conditional_select(&a.num, &b.num, choice),
+            prime: U256::conditional_select(&a.prime, &b.prime, choice),
+        }
+    }
+}
+
+/// Formats `num` as 64 zero-padded hex digits, the inverse of `from_hex`.
+impl fmt::LowerHex for FieldElement<U256> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:064x}", self.num)
+    }
+}
+
+impl fmt::UpperHex for FieldElement<U256> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:064X}", self.num)
+    }
+}
+
 impl<T> Add for FieldElement<T>
 where
-    T: PartialEq + Add<Output = T> + Rem<Output = T> + PartialOrd + Debug + Copy,
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + PartialOrd + Debug + Clone,
 {
     type Output = Self;
 
@@ -39,8 +181,11 @@ where
         if self.prime != other.prime {
             panic!("Prime number should be same")
         }
-        if self.num + other.num >= self.prime {
-            Self::new((self.num + other.num) % self.prime, self.prime)
+        // num + other.num は p が型の上限に近いと溢れるので、
+        // p - other.num と比較して和を作らずに剰余を求める
+        let gap = self.prime.clone() - other.num.clone();
+        if self.num >= gap {
+            Self::new(self.num - gap, self.prime)
         } else {
             Self::new(self.num + other.num, self.prime)
         }
@@ -49,7 +194,7 @@ where
 
 impl<T> Sub for FieldElement<T>
 where
-    T: PartialEq + Sub<Output = T> + Rem<Output = T> + PartialOrd + Debug + Copy,
+    T: PartialEq + Sub<Output = T> + PartialOrd + Debug + Clone,
 {
     type Output = Self;
 
@@ -57,37 +202,40 @@ where
         if self.prime != other.prime {
             panic!("Prime number should be same")
         }
-        Self::new((self.num - other.num) % self.prime, self.prime)
+        // 符号なし整数でアンダーフローしないよう p から引き戻す
+        if self.num >= other.num {
+            Self::new(self.num - other.num, self.prime)
+        } else {
+            Self::new(self.prime.clone() - (other.num - self.num), self.prime)
+        }
+    }
+}
+
+impl<T> Neg for FieldElement<T>
+where
+    T: PartialEq + Sub<Output = T> + From<u8> + PartialOrd + Debug + Clone,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.num == T::from(0) {
+            self
+        } else {
+            Self::new(self.prime.clone() - self.num, self.prime)
+        }
     }
 }
 
 impl<T> Mul for FieldElement<T>
 where
-    T: PartialEq
-        + Add<Output = T>
-        + Sub<Output = T>
-        + Rem<Output = T>
-        + Div<Output = T>
-        + PartialOrd
-        + Debug
-        + Copy,
+    T: PartialEq + MulMod + PartialOrd + Debug + Clone,
 {
     type Output = Self;
     fn mul(self, other: Self) -> Self::Output {
         if self.prime != other.prime {
             panic!("Prime number should be same")
         }
-
-        let zero = self.prime - other.prime;
-        let one = self.prime / other.prime;
-        let mut counter = other.num;
-
-        let mut ret = FieldElement::new(zero, self.prime);
-        while counter > zero {
-            ret = ret + self;
-            counter = counter - one;
-        }
-        ret
+        Self::new(self.num.mul_mod(other.num, self.prime.clone()), self.prime)
     }
 }
 
@@ -98,17 +246,81 @@ where
         + Div<Output = T>
         + Mul<Output = T>
         + Rem<Output = T>
+        + MulMod
+        + From<u8>
         + PartialOrd
         + Debug
-        + Display
-        + Copy,
+        + Clone,
 {
     type Output = Self;
 
+    // 割り算は逆元との掛け算
+    #[allow(clippy::suspicious_arithmetic_impl)]
     fn div(self, other: Self) -> Self::Output {
-        let p = self.prime;
-        let one = p / p;
-        self * other.pow(p - one - one)
+        self * other.inverse()
+    }
+}
+
+forward_ref_binop!(impl Add, add for FieldElement);
+forward_ref_binop!(impl Sub, sub for FieldElement);
+forward_ref_binop!(impl Mul, mul for FieldElement);
+forward_ref_binop!(impl Div, div for FieldElement);
+
+forward_op_assign!(impl AddAssign, add_assign, Add, add for FieldElement);
+forward_op_assign!(impl SubAssign, sub_assign, Sub, sub for FieldElement);
+forward_op_assign!(impl MulAssign, mul_assign, Mul, mul for FieldElement);
+forward_op_assign!(impl DivAssign, div_assign, Div, div for FieldElement);
+
+impl<T> Neg for &FieldElement<T>
+where
+    FieldElement<T>: Neg<Output = FieldElement<T>> + Clone,
+{
+    type Output = FieldElement<T>;
+
+    fn neg(self) -> Self::Output {
+        -self.clone()
+    }
+}
+
+/// Panics on an empty iterator: without an element there is no prime to
+/// build the additive identity from.
+impl<T> Sum for FieldElement<T>
+where
+    FieldElement<T>: Add<Output = FieldElement<T>>,
+{
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc + x)
+            .expect("Cannot sum an empty iterator of field elements")
+    }
+}
+
+impl<'a, T> Sum<&'a FieldElement<T>> for FieldElement<T>
+where
+    FieldElement<T>: Add<Output = FieldElement<T>> + Clone,
+{
+    fn sum<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.cloned().sum()
+    }
+}
+
+/// Panics on an empty iterator: without an element there is no prime to
+/// build the multiplicative identity from.
+impl<T> Product for FieldElement<T>
+where
+    FieldElement<T>: Mul<Output = FieldElement<T>>,
+{
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.reduce(|acc, x| acc * x)
+            .expect("Cannot multiply an empty iterator of field elements")
+    }
+}
+
+impl<'a, T> Product<&'a FieldElement<T>> for FieldElement<T>
+where
+    FieldElement<T>: Mul<Output = FieldElement<T>> + Clone,
+{
+    fn product<I: Iterator<Item = &'a Self>>(iter: I) -> Self {
+        iter.cloned().product()
     }
 }
 
@@ -123,6 +335,37 @@ where
 
 impl<T> Eq for FieldElement<T> where T: Eq + Add<Output = T> {}
 
+impl<T> Hash for FieldElement<T>
+where
+    T: Hash,
+{
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.prime.hash(state);
+        self.num.hash(state);
+    }
+}
+
+/// Orders by prime first, so elements of one field are ordered by `num`.
+impl<T> PartialOrd for FieldElement<T>
+where
+    T: Ord + Add<Output = T>,
+{
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<T> Ord for FieldElement<T>
+where
+    T: Ord + Add<Output = T>,
+{
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.prime
+            .cmp(&other.prime)
+            .then_with(|| self.num.cmp(&other.num))
+    }
+}
+
 impl<T> FieldElement<T>
 where
     T: Add<Output = T>
@@ -130,22 +373,170 @@ where
         + Sub<Output = T>
         + Div<Output = T>
         + Rem<Output = T>
+        + MulMod
+        + From<u8>
         + PartialOrd
         + Debug
-        + Copy,
+        + Clone,
 {
-    fn pow(self, exponent: T) -> Self {
-        let zero = self.prime - self.prime;
-        let one = self.prime / self.prime;
-        let mut ret = FieldElement::new(one, self.prime);
-        let mut counter = exponent % (self.prime - one);
+    /// `self^exponent`, where the exponent may be negative: it is reduced
+    /// modulo `p - 1` first, so `a.pow(-3i64)` is `a.pow(p - 4)`, the
+    /// inverse of `a^3`.
+    pub fn pow<E: Exponent<T>>(&self, exponent: E) -> Self {
+        self.pow_unsigned(exponent.reduce(self.prime.clone() - T::from(1)))
+    }
+
+    /// `self * self`, using the backend's dedicated squaring.
+    pub fn square(&self) -> Self {
+        Self::new(
+            self.num.clone().square_mod(self.prime.clone()),
+            self.prime.clone(),
+        )
+    }
+
+    /// `self * self * self`.
+    pub fn cube(&self) -> Self {
+        &self.square() * self
+    }
 
+    fn pow_unsigned(&self, exponent: T) -> Self {
+        let zero = T::from(0);
+        let one = T::from(1);
+        let mut ret = FieldElement::new(T::from(1), self.prime.clone());
+        let mut base = self.clone();
+        let mut counter = exponent % (self.prime.clone() - T::from(1));
+
+        // 二乗と掛け算を繰り返す（バイナリ法）
         while counter > zero {
-            ret = ret * self;
-            counter = counter - one;
+            if counter.clone() % T::from(2) == one {
+                ret = &ret * &base;
+            }
+            base = base.square();
+            counter = counter / T::from(2);
         }
         ret
     }
+
+    /// Multiplicative inverse via Fermat's little theorem: `self^(p-2)`.
+    ///
+    /// Panics when called on zero, which has no inverse.
+    pub fn inverse(&self) -> Self {
+        if self.num == T::from(0) {
+            panic!("Zero has no multiplicative inverse")
+        }
+        self.pow_unsigned(self.prime.clone() - T::from(2))
+    }
+
+    /// Inverts every element in place with a single field inversion
+    /// (Montgomery's trick): `3(n-1)` multiplications plus one `inverse`.
+    ///
+    /// Panics if any element is zero.
+    pub fn batch_invert(elements: &mut [Self]) {
+        batch_invert(elements)
+    }
+
+    /// Euler's criterion: `true` when `self` is zero or has a square root mod `p`.
+    pub fn is_square(&self) -> bool {
+        if self.num == T::from(0) || self.prime == T::from(2) {
+            return true;
+        }
+        self.pow_unsigned((self.prime.clone() - T::from(1)) / T::from(2))
+            .num
+            == T::from(1)
+    }
+
+    /// Square root modulo `p`, or `None` when `self` is not a quadratic residue.
+    ///
+    /// Primes with `p % 4 == 3` (secp256k1 among them) take the single
+    /// exponentiation `self^((p+1)/4)`; every other odd prime goes through
+    /// Tonelli–Shanks. Either of the two roots may be returned.
+    pub fn sqrt(&self) -> Option<Self> {
+        let zero = T::from(0);
+        let p = self.prime.clone();
+        let unit = FieldElement::new(T::from(1), p.clone());
+
+        if self.num == zero || p == T::from(2) {
+            return Some(self.clone());
+        }
+
+        if p.clone() % T::from(4) == T::from(3) {
+            // p ≡ 3 (mod 4) なら (p+1)/4 乗で平方根が求まる
+            let root = self.pow_unsigned(p / T::from(4) + T::from(1));
+            return if root.square() == *self {
+                Some(root)
+            } else {
+                None
+            };
+        }
+
+        if !self.is_square() {
+            return None;
+        }
+
+        // Tonelli–Shanks: p - 1 = q * 2^s (q は奇数)
+        let mut q = p.clone() - T::from(1);
+        let mut s = 0usize;
+        while q.clone() % T::from(2) == zero {
+            q = q / T::from(2);
+            s += 1;
+        }
+
+        // 平方非剰余 z を探す
+        let mut candidate = T::from(2);
+        let z = loop {
+            let z = FieldElement::new(candidate.clone(), p.clone());
+            if !z.is_square() {
+                break z;
+            }
+            candidate = candidate + T::from(1);
+        };
+
+        let mut m = s;
+        let mut c = z.pow_unsigned(q.clone());
+        let mut t = self.pow_unsigned(q.clone());
+        let mut r = self.pow_unsigned((q + T::from(1)) / T::from(2));
+        while t != unit {
+            // t^(2^i) == 1 となる最小の i を探す
+            let mut i = 0;
+            let mut t_pow = t.clone();
+            while t_pow != unit {
+                t_pow = t_pow.square();
+                i += 1;
+                if i == m {
+                    return None;
+                }
+            }
+            let mut b = c;
+            for _ in 0..(m - i - 1) {
+                b = b.square();
+            }
+            m = i;
+            c = b.square();
+            t *= &c;
+            r *= b;
+        }
+        Some(r)
+    }
+}
+
+/// Serialized as the `(num, prime)` pair, each one a hex string or 32 raw bytes.
+#[cfg(feature = "serde")]
+impl serde::Serialize for FieldElement<U256> {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::serde_hex::HexU256;
+
+        serde::Serialize::serialize(&(HexU256(self.num), HexU256(self.prime)), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for FieldElement<U256> {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use crate::serde_hex::HexU256;
+
+        let (HexU256(num), HexU256(prime)) = serde::Deserialize::deserialize(deserializer)?;
+        Self::try_new(num, prime).map_err(serde::de::Error::custom)
+    }
 }
 
 #[cfg(test)]
@@ -165,6 +556,78 @@ mod tests {
         assert_ne!(a, c);
     }
 
+    #[test]
+    fn try_new() {
+        use crate::error::FieldError;
+
+        assert!(FieldElement::try_new(U256::from(6), U256::from(7)).is_ok());
+        assert_eq!(
+            FieldElement::try_new(U256::from(7), U256::from(7)),
+            Err(FieldError::NotInRange)
+        );
+    }
+
+    #[test]
+    fn ct_eq() {
+        use crate::ct::ConstantTimeEq;
+
+        let a = FieldElement::new(U256::from(2), U256::from(3));
+        let b = FieldElement::new(U256::from(2), U256::from(3));
+        let c = FieldElement::new(U256::from(1), U256::from(3));
+        let d = FieldElement::new(U256::from(2), U256::from(5));
+
+        assert!(bool::from(a.ct_eq(&b)));
+        assert!(!bool::from(a.ct_eq(&c)));
+        assert!(!bool::from(a.ct_eq(&d)));
+    }
+
+    #[test]
+    fn conversions() {
+        use crate::error::FieldError;
+
+        let a = FieldElement::try_from((7u64, 13u64)).unwrap();
+        assert_eq!(a, FieldElement::new(U256::from(7), U256::from(13)));
+        assert_eq!(
+            FieldElement::<U256>::try_from((13u64, 13u64)),
+            Err(FieldError::NotInRange)
+        );
+
+        let bytes = a.to_be_bytes();
+        assert_eq!(bytes[31], 7);
+        assert_eq!(FieldElement::from_be_bytes(&bytes, U256::from(13)), Ok(a));
+        assert_eq!(
+            FieldElement::from_be_bytes(&bytes[1..], U256::from(13)),
+            Err(FieldError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn hash_and_ord() {
+        use std::collections::{BTreeSet, HashSet};
+
+        let p = U256::from(19);
+        let elements: Vec<_> = [5u64, 3, 5, 18]
+            .iter()
+            .map(|n| FieldElement::new(U256::from(*n), p))
+            .collect();
+
+        let hashed: HashSet<_> = elements.iter().cloned().collect();
+        assert_eq!(hashed.len(), 3);
+
+        let ordered: Vec<_> = elements
+            .into_iter()
+            .collect::<BTreeSet<_>>()
+            .into_iter()
+            .collect();
+        assert_eq!(
+            ordered.iter().map(|e| e.num.as_u64()).collect::<Vec<_>>(),
+            vec![3, 5, 18]
+        );
+        assert!(
+            FieldElement::new(U256::from(18), p) < FieldElement::new(U256::from(0), U256::from(23))
+        );
+    }
+
     #[test]
     fn add() {
         let a = FieldElement::new(U256::from(2), U256::from(7));
@@ -176,6 +639,38 @@ mod tests {
         assert_eq!(a + b, c);
     }
 
+    #[test]
+    fn add_near_type_max() {
+        // 2^256 - 189 は 256 ビットに収まる最大の素数
+        let p = U256::MAX - U256::from(188);
+        let a = FieldElement::new(p - U256::from(1), p);
+        let b = FieldElement::new(p - U256::from(2), p);
+
+        assert_eq!(a + b, FieldElement::new(p - U256::from(3), p));
+        assert_eq!(a * b, FieldElement::new(U256::from(2), p));
+        assert_eq!(a - b, FieldElement::new(U256::from(1), p));
+    }
+
+    #[test]
+    fn sub() {
+        let a = FieldElement::new(U256::from(6), U256::from(19));
+        let b = FieldElement::new(U256::from(13), U256::from(19));
+
+        assert_eq!(a - b, FieldElement::new(U256::from(12), U256::from(19)));
+        assert_eq!(b - a, FieldElement::new(U256::from(7), U256::from(19)));
+        assert_eq!(a - a, FieldElement::new(U256::from(0), U256::from(19)));
+    }
+
+    #[test]
+    fn neg() {
+        let a = FieldElement::new(U256::from(9), U256::from(19));
+        let zero = FieldElement::new(U256::from(0), U256::from(19));
+
+        assert_eq!(-a, FieldElement::new(U256::from(10), U256::from(19)));
+        assert_eq!(a + -a, zero);
+        assert_eq!(-zero, zero);
+    }
+
     #[test]
     fn mul() {
         let a = FieldElement::new(U256::from(3), U256::from(13));
@@ -187,12 +682,308 @@ mod tests {
         assert_eq!(a * b, c);
     }
 
+    #[test]
+    fn mul_secp256k1_sized() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(p - U256::from(2), p);
+        let b = FieldElement::new(p - U256::from(3), p);
+        let c = FieldElement::new(U256::from(6), p);
+
+        assert_eq!(a * b, c);
+    }
+
+    #[test]
+    #[allow(clippy::op_ref)]
+    fn ops_on_references() {
+        let a = FieldElement::new(U256::from(7), U256::from(19));
+        let b = FieldElement::new(U256::from(5), U256::from(19));
+
+        assert_eq!(&a + &b, a + b);
+        assert_eq!(&a - &b, a - b);
+        assert_eq!(&a * &b, a * b);
+        assert_eq!(&a / &b, a / b);
+        assert_eq!(a + &b, &a + b);
+        assert_eq!(-&a, -a);
+    }
+
+    #[test]
+    fn compound_assignment() {
+        let p = U256::from(19);
+        let a = FieldElement::new(U256::from(7), p);
+        let b = FieldElement::new(U256::from(5), p);
+
+        let mut c = a;
+        c += b;
+        assert_eq!(c, a + b);
+        c -= &b;
+        assert_eq!(c, a);
+        c *= b;
+        assert_eq!(c, a * b);
+        c /= b;
+        assert_eq!(c, a);
+
+        // ホーナー法: 3x^2 + 2x + 1 (x = 5)
+        let mut acc = FieldElement::new(U256::from(0), p);
+        for coefficient in [3u64, 2, 1] {
+            acc *= b;
+            acc += FieldElement::new(U256::from(coefficient), p);
+        }
+        assert_eq!(acc, FieldElement::new(U256::from(86 % 19), p));
+    }
+
+    #[test]
+    fn sum_and_product() {
+        let p = U256::from(19);
+        let elements: Vec<_> = [3u64, 7, 11]
+            .iter()
+            .map(|n| FieldElement::new(U256::from(*n), p))
+            .collect();
+
+        assert_eq!(
+            elements.iter().sum::<FieldElement<_>>(),
+            FieldElement::new(U256::from(21 % 19), p)
+        );
+        assert_eq!(
+            elements.into_iter().product::<FieldElement<_>>(),
+            FieldElement::new(U256::from(231 % 19), p)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn sum_of_empty_iterator() {
+        let _ = Vec::<FieldElement<U256>>::new()
+            .into_iter()
+            .sum::<FieldElement<_>>();
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let a = FieldElement::new(U256::from(0xab), U256::from(223));
+        let json = serde_json::to_string(&a).unwrap();
+
+        assert_eq!(json, format!("[\"{:064x}\",\"{:064x}\"]", 0xab, 223));
+        assert_eq!(
+            serde_json::from_str::<FieldElement<U256>>(&json).unwrap(),
+            a
+        );
+
+        let out_of_range = format!("[\"{:064x}\",\"{:064x}\"]", 300, 223);
+        assert!(serde_json::from_str::<FieldElement<U256>>(&out_of_range).is_err());
+    }
+
+    #[test]
+    fn hex_round_trip() {
+        use crate::error::FieldError;
+
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let hex = "79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798";
+        let a = FieldElement::from_hex(hex, p).unwrap();
+
+        assert_eq!(format!("{:x}", a), hex);
+        assert_eq!(format!("{:X}", a), hex.to_uppercase());
+        assert_eq!(FieldElement::from_hex(&format!("0x{:x}", a), p), Ok(a));
+        assert_eq!(
+            format!("{:x}", FieldElement::new(U256::from(1), p)),
+            format!("{:0>64}", "1")
+        );
+        assert_eq!(FieldElement::from_hex("zz", p), Err(FieldError::InvalidHex));
+        assert_eq!(
+            FieldElement::from_hex("ff", U256::from(223)),
+            Err(FieldError::NotInRange)
+        );
+    }
+
+    #[test]
+    fn random() {
+        use rand::rngs::StdRng;
+        use rand::SeedableRng;
+
+        let mut rng = StdRng::seed_from_u64(3);
+        let p = U256::from(223);
+        let a = FieldElement::random(&mut rng, p);
+        let b = FieldElement::random(&mut rng, p);
+
+        assert!(a.num < p && b.num < p);
+        assert_ne!(a, b);
+    }
+
+    #[test]
+    fn square_and_cube() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(p - U256::from(0x1234_5678u64), p);
+        let b = FieldElement::new(U256::from(5), U256::from(19));
+
+        assert_eq!(a.square(), a * a);
+        assert_eq!(a.cube(), a * a * a);
+        assert_eq!(b.square(), FieldElement::new(U256::from(6), U256::from(19)));
+        assert_eq!(b.cube(), FieldElement::new(U256::from(11), U256::from(19)));
+    }
+
     #[test]
     fn pow() {
         let a = FieldElement::new(U256::from(3), U256::from(13));
         let b = FieldElement::new(U256::from(1), U256::from(13));
 
         assert_eq!(a.pow(U256::from(3)), b);
+        assert_eq!(a.pow(3u32), b);
+        assert_eq!(a.pow(15u8), b);
+
+        let c = FieldElement::new(7u32, 13u32);
+        assert_eq!(c.pow(2u32), FieldElement::new(10u32, 13u32));
+        assert_eq!(c.pow(2u8), FieldElement::new(10u32, 13u32));
+    }
+
+    #[test]
+    fn pow_negative_exponent() {
+        let p = U256::from(31);
+        let a = FieldElement::new(U256::from(17), p);
+        let b = FieldElement::new(U256::from(29), p);
+
+        // 本の演習: 17^-3 = 29 (F_31)
+        assert_eq!(a.pow(-3i64), b);
+        assert_eq!(
+            a.pow(-3i64) * a.pow(U256::from(3)),
+            FieldElement::new(U256::from(1), p)
+        );
+        assert_eq!(a.pow(-30i32), FieldElement::new(U256::from(1), p));
+        assert_eq!(a.pow(0i64), FieldElement::new(U256::from(1), p));
+    }
+
+    #[test]
+    fn inverse() {
+        let a = FieldElement::new(U256::from(3), U256::from(31));
+        let b = FieldElement::new(U256::from(21), U256::from(31));
+        let one = FieldElement::new(U256::from(1), U256::from(31));
+
+        assert_eq!(a.inverse(), b);
+        assert_eq!(a * a.inverse(), one);
+    }
+
+    #[test]
+    fn inverse_secp256k1_sized() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = FieldElement::new(U256::from(0xdeadbeefu64), p);
+        let one = FieldElement::new(U256::from(1), p);
+
+        assert_eq!(a * a.inverse(), one);
+    }
+
+    #[test]
+    #[should_panic]
+    fn inverse_of_zero() {
+        let _ = FieldElement::new(U256::from(0), U256::from(31)).inverse();
+    }
+
+    #[test]
+    fn is_square() {
+        let p = U256::from(223);
+        let squares: Vec<_> = (0..223u64)
+            .map(|n| FieldElement::new(U256::from(n), p))
+            .filter(|a| a.is_square())
+            .collect();
+
+        // 0 と (p-1)/2 個の平方剰余
+        assert_eq!(squares.len(), 112);
+        for a in squares {
+            assert!(a.sqrt().is_some());
+        }
+        assert!(!FieldElement::new(U256::from(5), p).is_square());
+    }
+
+    #[test]
+    fn sqrt_p_3_mod_4() {
+        let p = U256::from(223);
+        let a = FieldElement::new(U256::from(105), p);
+        let root = (a * a).sqrt().unwrap();
+
+        assert!(root == a || root == -a);
+        assert_eq!(FieldElement::new(U256::from(5), p).sqrt(), None);
+    }
+
+    #[test]
+    fn sqrt_tonelli_shanks() {
+        // 97 ≡ 1 (mod 4), 97 - 1 = 3 * 2^5
+        let p = U256::from(97);
+        for n in 1..97u64 {
+            let a = FieldElement::new(U256::from(n), p);
+            let root = (a * a).sqrt().unwrap();
+            assert!(root == a || root == -a);
+        }
+        assert_eq!(FieldElement::new(U256::from(5), p).sqrt(), None);
+    }
+
+    #[test]
+    fn sqrt_secp256k1_sized() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let x = FieldElement::new(
+            U256::from_str_radix(
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                16,
+            )
+            .unwrap(),
+            p,
+        );
+        let y = FieldElement::new(
+            U256::from_str_radix(
+                "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+                16,
+            )
+            .unwrap(),
+            p,
+        );
+        let seven = FieldElement::new(U256::from(7), p);
+        let root = (x * x * x + seven).sqrt().unwrap();
+
+        assert!(root == y || root == -y);
+    }
+
+    #[test]
+    fn batch_invert() {
+        let p = U256::from(223);
+        let mut elements: Vec<_> = [3u64, 17, 105, 222, 1]
+            .iter()
+            .map(|n| FieldElement::new(U256::from(*n), p))
+            .collect();
+        let expected: Vec<_> = elements.iter().map(|e| e.inverse()).collect();
+
+        FieldElement::batch_invert(&mut elements);
+        assert_eq!(elements, expected);
+
+        let mut empty: Vec<FieldElement<U256>> = vec![];
+        FieldElement::batch_invert(&mut empty);
+    }
+
+    #[test]
+    #[should_panic]
+    fn batch_invert_with_zero() {
+        let p = U256::from(223);
+        let mut elements = vec![
+            FieldElement::new(U256::from(3), p),
+            FieldElement::new(U256::from(0), p),
+        ];
+        FieldElement::batch_invert(&mut elements);
     }
 
     #[test]
@@ -0,0 +1,258 @@
+use std::fmt;
+use std::iter::{Product, Sum};
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::error::FieldError;
+use crate::field_element::{FieldElement, One};
+use crate::modular::MulMod;
+
+/// Element of F_P with the prime fixed at compile time.
+///
+/// Meant for the book's small teaching fields such as F_223: the prime never
+/// has to be passed around at runtime, mixing fields is a type error, and a
+/// composite `P` fails to compile as soon as an element is constructed.
+///
+/// ```
+/// use programming_bitcoin_in_rust::fp::Fp;
+///
+/// type F223 = Fp<223>;
+/// assert_eq!(F223::new(192) + F223::new(105), F223::new(74));
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Fp<const P: u64>(u64);
+
+const fn is_prime(n: u64) -> bool {
+    if n < 2 {
+        return false;
+    }
+    let mut i = 2;
+    while i * i <= n {
+        if n.is_multiple_of(i) {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+impl<const P: u64> Fp<P> {
+    const PRIME_CHECK: () = assert!(is_prime(P), "the modulus of Fp must be prime");
+
+    pub const fn try_new(num: u64) -> Result<Self, FieldError> {
+        #[allow(clippy::let_unit_value)]
+        let () = Self::PRIME_CHECK;
+        if num >= P {
+            return Err(FieldError::NotInRange);
+        }
+        Ok(Self(num))
+    }
+
+    /// Usable in `const` items, e.g. for the constants of a `Curve`.
+    pub const fn new(num: u64) -> Self {
+        match Self::try_new(num) {
+            Ok(element) => element,
+            Err(_) => panic!("number is not in the field range"),
+        }
+    }
+
+    pub fn num(&self) -> u64 {
+        self.0
+    }
+
+    pub fn pow(&self, exponent: u64) -> Self {
+        let mut ret = Self::new(1);
+        let mut base = *self;
+        let mut counter = exponent % (P - 1);
+        while counter > 0 {
+            if counter & 1 == 1 {
+                ret = ret * base;
+            }
+            base = base * base;
+            counter >>= 1;
+        }
+        ret
+    }
+
+    pub fn inverse(&self) -> Self {
+        if self.0 == 0 {
+            panic!("Zero has no multiplicative inverse")
+        }
+        self.pow(P - 2)
+    }
+}
+
+impl<const P: u64> ConstantTimeEq for Fp<P> {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl<const P: u64> ConditionallySelectable for Fp<P> {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(u64::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl<const P: u64> One for Fp<P> {
+    fn one(&self) -> Self {
+        Self(1)
+    }
+}
+
+impl<const P: u64> TryFrom<u64> for Fp<P> {
+    type Error = FieldError;
+
+    fn try_from(num: u64) -> Result<Self, Self::Error> {
+        Self::try_new(num)
+    }
+}
+
+impl<const P: u64> From<Fp<P>> for FieldElement<u64> {
+    fn from(element: Fp<P>) -> Self {
+        FieldElement::new(element.0, P)
+    }
+}
+
+impl<const P: u64> TryFrom<FieldElement<u64>> for Fp<P> {
+    type Error = FieldError;
+
+    fn try_from(element: FieldElement<u64>) -> Result<Self, Self::Error> {
+        if element.prime != P {
+            return Err(FieldError::PrimeMismatch);
+        }
+        Self::try_new(element.num)
+    }
+}
+
+impl<const P: u64> fmt::Display for Fp<P> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "FieldElement_{}({})", P, self.0)
+    }
+}
+
+impl<const P: u64> Add for Fp<P> {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(((self.0 as u128 + other.0 as u128) % P as u128) as u64)
+    }
+}
+
+impl<const P: u64> Sub for Fp<P> {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        self + -other
+    }
+}
+
+impl<const P: u64> Neg for Fp<P> {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        if self.0 == 0 {
+            self
+        } else {
+            Self(P - self.0)
+        }
+    }
+}
+
+impl<const P: u64> Mul for Fp<P> {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self(self.0.mul_mod(other.0, P))
+    }
+}
+
+impl<const P: u64> Div for Fp<P> {
+    type Output = Self;
+
+    // 割り算は逆元との掛け算
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl<const P: u64> Sum for Fp<P> {
+    fn sum<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(0), |acc, x| acc + x)
+    }
+}
+
+impl<const P: u64> Product for Fp<P> {
+    fn product<I: Iterator<Item = Self>>(iter: I) -> Self {
+        iter.fold(Self::new(1), |acc, x| acc * x)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Fp;
+    use crate::elliptic::Point;
+    use crate::error::FieldError;
+    use crate::field_element::FieldElement;
+
+    type F223 = Fp<223>;
+
+    #[test]
+    fn arithmetic() {
+        let a = Fp::<19>::new(7);
+        let b = Fp::<19>::new(5);
+
+        assert_eq!(a + b, Fp::new(12));
+        assert_eq!(b - a, Fp::new(17));
+        assert_eq!(a * b, Fp::new(16));
+        assert_eq!(a / b, Fp::new(9));
+        assert_eq!(-a, Fp::new(12));
+        assert_eq!(Fp::<13>::new(3).pow(3), Fp::new(1));
+    }
+
+    #[test]
+    fn lagrange_interpolation() {
+        // f(x) = 2x^2 + 3x + 5 を 3 点から復元して f(0) を求める
+        let f = |x: u64| F223::new((2 * x * x + 3 * x + 5) % 223);
+        let xs = [F223::new(1), F223::new(2), F223::new(3)];
+        let ys: Vec<_> = [1, 2, 3].iter().map(|x| f(*x)).collect();
+
+        let at_zero: F223 = (0..3)
+            .map(|i| {
+                let basis: F223 = (0..3)
+                    .filter(|j| *j != i)
+                    .map(|j| -xs[j] / (xs[i] - xs[j]))
+                    .product();
+                ys[i] * basis
+            })
+            .sum();
+        assert_eq!(at_zero, F223::new(5));
+        assert_eq!(Vec::<F223>::new().into_iter().sum::<F223>(), F223::new(0));
+    }
+
+    #[test]
+    fn try_new() {
+        assert_eq!(F223::try_new(223), Err(FieldError::NotInRange));
+    }
+
+    #[test]
+    fn point_on_f223() {
+        let a = F223::new(0);
+        let b = F223::new(7);
+        let p0 = Point::new(F223::new(192), F223::new(105), a, b);
+        let p1 = Point::new(F223::new(17), F223::new(56), a, b);
+
+        assert_eq!(p0 + p1, Point::new(F223::new(170), F223::new(142), a, b));
+    }
+
+    #[test]
+    fn conversions() {
+        let a = F223::new(42);
+        let generic: FieldElement<u64> = a.into();
+
+        assert_eq!(generic, FieldElement::new(42, 223));
+        assert_eq!(F223::try_from(generic), Ok(a));
+        assert_eq!(Fp::<19>::try_from(generic), Err(FieldError::PrimeMismatch));
+    }
+}
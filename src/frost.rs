@@ -0,0 +1,444 @@
+//! FROST threshold Schnorr signatures with a trusted dealer.
+//!
+//! A dealer splits a private key into `n` Shamir shares so that any `t` of
+//! the participants can jointly produce a BIP340 signature for the group's
+//! x-only key, while fewer than `t` learn nothing about the key.
+//!
+//! Signing takes two rounds: every signer publishes a pair of nonce
+//! commitments, then each computes a signature share over the collected
+//! commitments. The hashes follow the structure of RFC 9591 but use tagged
+//! hashes, so signatures are not interoperable with its ciphersuites.
+
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::error::FrostError;
+use crate::hash::tagged_hash;
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::scalar::{hash_to_scalar, Scalar};
+use crate::schnorr::{self, SchnorrSignature};
+use crate::secp256k1::generator_mul;
+use crate::secret::SecretScalar;
+use crate::xonly::XOnlyPublicKey;
+
+/// Lagrange coefficient of participant `index` for interpolating at zero
+/// over the participants `indices`: `prod_{j != i} j / (j - i)`.
+fn lagrange_coefficient(index: u32, indices: &[u32]) -> Scalar {
+    let i = Scalar::from(index as u64);
+    let (num, den) = indices.iter().filter(|&&j| j != index).fold(
+        (Scalar::from(1), Scalar::from(1)),
+        |(num, den), &j| {
+            let j = Scalar::from(j as u64);
+            (num * j, den * (j - i))
+        },
+    );
+    num / den
+}
+
+/// One participant's share `f(index)` of the group key, together with the
+/// public data every signer needs. The share is wiped from memory when
+/// dropped.
+#[derive(Clone, PartialEq, Eq)]
+pub struct KeyShare {
+    index: u32,
+    threshold: usize,
+    secret: SecretScalar,
+    group_key: XOnlyPublicKey,
+}
+
+impl KeyShare {
+    /// The participant's index, starting at 1.
+    pub fn index(&self) -> u32 {
+        self.index
+    }
+
+    pub fn threshold(&self) -> usize {
+        self.threshold
+    }
+
+    /// `f(index) * G`, against which signature shares are verified.
+    pub fn public_share(&self) -> S256Point {
+        generator_mul(&self.secret)
+    }
+
+    pub fn group_key(&self) -> XOnlyPublicKey {
+        self.group_key
+    }
+}
+
+impl Zeroize for KeyShare {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl Drop for KeyShare {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for KeyShare {}
+
+impl fmt::Debug for KeyShare {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "KeyShare({}, <redacted>)", self.index)
+    }
+}
+
+/// Splits `key` into `n` shares with threshold `t` (trusted dealer key
+/// generation). Shares are indexed `1..=n`.
+///
+/// BIP340 keys have an even y, so a key with an odd y is negated first;
+/// the group key is `key`'s x-only public key either way.
+pub fn trusted_dealer<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    key: &PrivateKey,
+    t: usize,
+    n: usize,
+) -> Result<Vec<KeyShare>, FrostError> {
+    if t == 0 || t > n || n > u32::MAX as usize {
+        return Err(FrostError::InvalidThreshold);
+    }
+    let point = key.point();
+    let secret = if point.has_even_y() {
+        key.to_scalar()
+    } else {
+        -key.to_scalar()
+    };
+    let group_key = XOnlyPublicKey::from_point(&point).expect("never the point at infinity");
+
+    // f(x) = secret + a_1 x + ... + a_{t-1} x^{t-1}
+    let mut coefficients = vec![SecretScalar::new(secret.num())];
+    coefficients.extend((1..t).map(|_| SecretScalar::new(Scalar::random(rng).num())));
+
+    Ok((1..=n as u32)
+        .map(|index| {
+            let x = Scalar::from(index as u64);
+            // ホーナー法で f(x) を評価する
+            let secret = coefficients.iter().rev().fold(Scalar::from(0), |acc, a| {
+                acc * x + Scalar::new(*a.expose_secret())
+            });
+            KeyShare {
+                index,
+                threshold: t,
+                secret: SecretScalar::new(secret.num()),
+                group_key,
+            }
+        })
+        .collect())
+}
+
+/// A signer's secret nonces `(d, e)` for one signing session, with the
+/// commitment published for them.
+///
+/// `SigningPackage::sign` consumes them, so they cannot be reused. Wiped
+/// from memory when dropped.
+pub struct SigningNonces {
+    index: u32,
+    d: SecretScalar,
+    e: SecretScalar,
+    commitment: SigningCommitment,
+}
+
+impl fmt::Debug for SigningNonces {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SigningNonces({}, <redacted>)", self.index)
+    }
+}
+
+/// The public commitments `(d * G, e * G)` a signer publishes in the first
+/// round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SigningCommitment {
+    pub index: u32,
+    pub hiding: S256Point,
+    pub binding: S256Point,
+}
+
+/// First round: draws fresh nonces for `share`'s participant.
+pub fn commit<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    share: &KeyShare,
+) -> (SigningNonces, SigningCommitment) {
+    let d = SecretScalar::new(Scalar::random(rng).num());
+    let e = SecretScalar::new(Scalar::random(rng).num());
+    let commitment = SigningCommitment {
+        index: share.index,
+        hiding: generator_mul(&d),
+        binding: generator_mul(&e),
+    };
+    let nonces = SigningNonces {
+        index: share.index,
+        d,
+        e,
+        commitment,
+    };
+    (nonces, commitment)
+}
+
+/// The commitments of the participating signers and the message, plus
+/// the values every signer derives from them.
+#[derive(Clone, Debug)]
+pub struct SigningPackage {
+    commitments: Vec<SigningCommitment>,
+    binding_factors: Vec<Scalar>,
+    group_key: XOnlyPublicKey,
+    r: S256Point,
+    challenge: Scalar,
+}
+
+impl SigningPackage {
+    pub fn new(
+        group_key: &XOnlyPublicKey,
+        commitments: &[SigningCommitment],
+        msg: &[u8],
+    ) -> Result<Self, FrostError> {
+        let mut commitments = commitments.to_vec();
+        commitments.sort_by_key(|c| c.index);
+        if commitments.windows(2).any(|w| w[0].index == w[1].index) {
+            return Err(FrostError::DuplicateSigner);
+        }
+
+        let mut encoded = Vec::with_capacity(commitments.len() * 70);
+        for c in &commitments {
+            encoded.extend_from_slice(&c.index.to_be_bytes());
+            encoded.extend_from_slice(&c.hiding.sec_compressed());
+            encoded.extend_from_slice(&c.binding.sec_compressed());
+        }
+        let commitments_hash = tagged_hash("FROST/commitments", &encoded);
+        let msg_hash = tagged_hash("FROST/message", msg);
+
+        // 束縛係数 rho_i は参加者・メッセージ・全コミットメントに依存する
+        let binding_factors: Vec<Scalar> = commitments
+            .iter()
+            .map(|c| {
+                let mut data = group_key.serialize().to_vec();
+                data.extend_from_slice(&msg_hash);
+                data.extend_from_slice(&commitments_hash);
+                data.extend_from_slice(&c.index.to_be_bytes());
+                hash_to_scalar("FROST/binding", &data)
+            })
+            .collect();
+
+        let r = commitments
+            .iter()
+            .zip(&binding_factors)
+            .fold(S256Point::INFINITY, |acc, (c, &rho)| {
+                acc + c.hiding + c.binding * rho
+            });
+        let r_x = r.x().ok_or(FrostError::TooFewSigners)?;
+
+        let challenge = schnorr::challenge(&r_x, group_key, msg);
+
+        Ok(Self {
+            commitments,
+            binding_factors,
+            group_key: *group_key,
+            r,
+            challenge,
+        })
+    }
+
+    fn indices(&self) -> Vec<u32> {
+        self.commitments.iter().map(|c| c.index).collect()
+    }
+
+    fn position(&self, index: u32) -> Result<usize, FrostError> {
+        self.commitments
+            .binary_search_by_key(&index, |c| c.index)
+            .map_err(|_| FrostError::UnknownSigner)
+    }
+
+    /// `1` or `-1`, so that the nonce points sum to the even-y `R` of the
+    /// final signature.
+    fn nonce_parity(&self) -> Scalar {
+        if self.r.has_even_y() {
+            Scalar::from(1)
+        } else {
+            -Scalar::from(1)
+        }
+    }
+
+    /// Second round: this signer's share
+    /// `z_i = d_i + e_i * rho_i + lambda_i * c * s_i`.
+    ///
+    /// Fails if the package carries a different commitment for this signer
+    /// than the one made for `nonces`, since the share would then be for
+    /// a nonce point the signer never agreed to.
+    pub fn sign(&self, nonces: SigningNonces, share: &KeyShare) -> Result<Scalar, FrostError> {
+        if nonces.index != share.index || share.group_key != self.group_key {
+            return Err(FrostError::UnknownSigner);
+        }
+        if self.commitments.len() < share.threshold {
+            return Err(FrostError::TooFewSigners);
+        }
+        let pos = self.position(share.index)?;
+        if self.commitments[pos] != nonces.commitment {
+            return Err(FrostError::CommitmentMismatch);
+        }
+
+        let d = Scalar::new(*nonces.d.expose_secret());
+        let e = Scalar::new(*nonces.e.expose_secret());
+        let lambda = lagrange_coefficient(share.index, &self.indices());
+
+        Ok(self.nonce_parity() * (d + e * self.binding_factors[pos])
+            + lambda * self.challenge * Scalar::new(*share.secret.expose_secret()))
+    }
+
+    /// Checks participant `index`'s signature share against their public
+    /// share, to identify a misbehaving signer.
+    pub fn verify_share(&self, index: u32, z: &Scalar, public_share: &S256Point) -> bool {
+        let pos = match self.position(index) {
+            Ok(pos) => pos,
+            Err(_) => return false,
+        };
+        let c = &self.commitments[pos];
+        let r = (c.hiding + c.binding * self.binding_factors[pos]) * self.nonce_parity();
+        let lambda = lagrange_coefficient(index, &self.indices());
+
+        S256Point::generator() * *z == r + *public_share * (lambda * self.challenge)
+    }
+
+    /// Sums the signature shares `z_i` into the BIP340 signature.
+    pub fn aggregate(&self, shares: &[Scalar]) -> SchnorrSignature {
+        SchnorrSignature {
+            r: self.r.x().expect("not at infinity"),
+            s: shares.iter().fold(Scalar::from(0), |acc, &z| acc + z),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{commit, lagrange_coefficient, trusted_dealer, KeyShare, SigningPackage};
+    use crate::error::FrostError;
+    use crate::private_key::PrivateKey;
+    use crate::scalar::Scalar;
+    use crate::schnorr::{self, SchnorrSignature};
+    use crate::xonly::XOnlyPublicKey;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+    use zeroize::Zeroize;
+
+    fn threshold_sign(rng: &mut StdRng, signers: &[&KeyShare], msg: &[u8]) -> SchnorrSignature {
+        let (nonces, commitments): (Vec<_>, Vec<_>) =
+            signers.iter().map(|share| commit(rng, share)).unzip();
+        let package = SigningPackage::new(&signers[0].group_key(), &commitments, msg).unwrap();
+
+        let shares: Vec<Scalar> = nonces
+            .into_iter()
+            .zip(signers)
+            .map(|(nonces, share)| package.sign(nonces, share).unwrap())
+            .collect();
+        for (z, share) in shares.iter().zip(signers) {
+            assert!(package.verify_share(share.index(), z, &share.public_share()));
+        }
+        package.aggregate(&shares)
+    }
+
+    #[test]
+    fn lagrange_interpolates_constant_term() {
+        // f(x) = 5 + 3x の f(1), f(3) から f(0) を復元する
+        let f = |x: u64| Scalar::from(5 + 3 * x);
+        let indices = [1, 3];
+        let secret =
+            lagrange_coefficient(1, &indices) * f(1) + lagrange_coefficient(3, &indices) * f(3);
+        assert_eq!(secret, Scalar::from(5));
+    }
+
+    #[test]
+    fn any_threshold_subset_signs() {
+        let mut rng = StdRng::seed_from_u64(70);
+        let key = PrivateKey::generate(&mut rng);
+        let shares = trusted_dealer(&mut rng, &key, 2, 3).unwrap();
+        let group_key = XOnlyPublicKey::from_point(&key.point()).unwrap();
+        assert!(shares.iter().all(|share| share.group_key() == group_key));
+
+        let msg = b"FROST test message";
+        for signers in [[0, 1], [0, 2], [2, 1]] {
+            let signers: Vec<&KeyShare> = signers.iter().map(|&i| &shares[i]).collect();
+            let sig = threshold_sign(&mut rng, &signers, msg);
+            assert!(schnorr::verify(&group_key, msg, &sig));
+            assert!(!schnorr::verify(&group_key, b"other message", &sig));
+        }
+
+        let all: Vec<&KeyShare> = shares.iter().collect();
+        assert!(schnorr::verify(
+            &group_key,
+            msg,
+            &threshold_sign(&mut rng, &all, msg)
+        ));
+
+        let mut share = shares[0].clone();
+        share.zeroize();
+        assert!(share.secret.expose_secret().is_zero());
+        assert_eq!(format!("{:?}", share), "KeyShare(1, <redacted>)");
+    }
+
+    #[test]
+    fn odd_key_is_normalized() {
+        let mut rng = StdRng::seed_from_u64(3);
+        let key = loop {
+            let key = PrivateKey::generate(&mut rng);
+            if !key.point().has_even_y() {
+                break key;
+            }
+        };
+        let shares = trusted_dealer(&mut rng, &key, 3, 5).unwrap();
+        let signers: Vec<&KeyShare> = shares[1..4].iter().collect();
+
+        let sig = threshold_sign(&mut rng, &signers, b"msg");
+        assert!(schnorr::verify(&shares[0].group_key(), b"msg", &sig));
+    }
+
+    #[test]
+    fn rejects_bad_sessions() {
+        let mut rng = StdRng::seed_from_u64(4);
+        let key = PrivateKey::generate(&mut rng);
+        assert_eq!(
+            trusted_dealer(&mut rng, &key, 4, 3).unwrap_err(),
+            FrostError::InvalidThreshold
+        );
+        assert_eq!(
+            trusted_dealer(&mut rng, &key, 0, 3).unwrap_err(),
+            FrostError::InvalidThreshold
+        );
+
+        let shares = trusted_dealer(&mut rng, &key, 2, 3).unwrap();
+        let group_key = shares[0].group_key();
+        let (nonces, commitment) = commit(&mut rng, &shares[0]);
+
+        // しきい値に満たない
+        let package = SigningPackage::new(&group_key, &[commitment], b"msg").unwrap();
+        assert_eq!(
+            package.sign(nonces, &shares[0]).unwrap_err(),
+            FrostError::TooFewSigners
+        );
+
+        assert_eq!(
+            SigningPackage::new(&group_key, &[commitment, commitment], b"msg").unwrap_err(),
+            FrostError::DuplicateSigner
+        );
+
+        // コミットメントを出していない参加者
+        let (_, other) = commit(&mut rng, &shares[1]);
+        let package = SigningPackage::new(&group_key, &[commitment, other], b"msg").unwrap();
+        let (nonces, _) = commit(&mut rng, &shares[2]);
+        assert_eq!(
+            package.sign(nonces, &shares[2]).unwrap_err(),
+            FrostError::UnknownSigner
+        );
+
+        // コーディネータがコミットメントを差し替えた
+        let (nonces, _) = commit(&mut rng, &shares[0]);
+        let package = SigningPackage::new(&group_key, &[commitment, other], b"msg").unwrap();
+        assert_eq!(
+            package.sign(nonces, &shares[0]).unwrap_err(),
+            FrostError::CommitmentMismatch
+        );
+    }
+}
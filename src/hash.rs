@@ -0,0 +1,593 @@
+use std::io::{self, Write};
+use std::sync::OnceLock;
+
+use sha2::digest::core_api::BlockSizeUser;
+use sha2::{Digest, Sha256, Sha512};
+use zeroize::Zeroize;
+
+/// `SHA256(SHA256(data))`, the hash Bitcoin uses for checksums, txids and
+/// block hashes.
+pub fn hash256(data: &[u8]) -> [u8; 32] {
+    let first = Sha256::digest(data);
+    Sha256::digest(first).into()
+}
+
+/// `RIPEMD160(SHA256(data))`, the hash behind addresses and key
+/// fingerprints.
+pub fn hash160(data: &[u8]) -> [u8; 20] {
+    ripemd160(&Sha256::digest(data))
+}
+
+/// Tags hashed often enough that their midstate is worth caching.
+const CACHED_TAGS: [&str; 6] = [
+    "BIP0340/aux",
+    "BIP0340/nonce",
+    "BIP0340/challenge",
+    "TapLeaf",
+    "TapBranch",
+    "TapTweak",
+];
+
+static MIDSTATES: OnceLock<Vec<Sha256>> = OnceLock::new();
+
+/// BIP340 tagged hash `SHA256(SHA256(tag) || SHA256(tag) || data)`, which
+/// keeps hashes made for different purposes from colliding.
+///
+/// The 64-byte prefix is exactly one SHA256 block, so for the common
+/// BIP340/BIP341 tags the hasher state after it is computed once and
+/// cloned, saving two compressions per call.
+pub fn tagged_hash(tag: &str, data: &[u8]) -> [u8; 32] {
+    tagged_hasher(tag).chain_update(data).finalize().into()
+}
+
+/// A SHA256 hasher that has already absorbed the prefix of `tag`.
+fn tagged_hasher(tag: &str) -> Sha256 {
+    match CACHED_TAGS.iter().position(|&cached| cached == tag) {
+        Some(i) => MIDSTATES
+            .get_or_init(|| CACHED_TAGS.iter().map(|tag| prefixed_hasher(tag)).collect())[i]
+            .clone(),
+        None => prefixed_hasher(tag),
+    }
+}
+
+fn prefixed_hasher(tag: &str) -> Sha256 {
+    let tag_hash = Sha256::digest(tag.as_bytes());
+    Sha256::new().chain_update(tag_hash).chain_update(tag_hash)
+}
+
+/// An `io::Write` sink that hashes whatever is serialized into it, so a
+/// txid or sighash can be computed without buffering the serialization.
+#[derive(Clone)]
+pub struct HashWriter(Sha256);
+
+impl HashWriter {
+    pub fn new() -> Self {
+        Self(Sha256::new())
+    }
+
+    /// A writer for `tagged_hash(tag, ..)`.
+    pub fn tagged(tag: &str) -> Self {
+        Self(tagged_hasher(tag))
+    }
+
+    /// SHA256 of everything written (the tagged hash for `tagged`).
+    pub fn sha256(self) -> [u8; 32] {
+        self.0.finalize().into()
+    }
+
+    /// `hash256` of everything written.
+    pub fn hash256(self) -> [u8; 32] {
+        Sha256::digest(self.0.finalize()).into()
+    }
+}
+
+impl Default for HashWriter {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Write for HashWriter {
+    fn write(&mut self, buf: &[u8]) -> io::Result<usize> {
+        self.0.update(buf);
+        Ok(buf.len())
+    }
+
+    fn flush(&mut self) -> io::Result<()> {
+        Ok(())
+    }
+}
+
+/// HMAC-SHA256 (RFC 2104), as used by RFC 6979 and SLIP-39.
+pub fn hmac_sha256(key: &[u8], data: &[u8]) -> [u8; 32] {
+    let mut mac = HmacSha256::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+/// HMAC-SHA512 (RFC 2104), as used by BIP32 and BIP39.
+pub fn hmac_sha512(key: &[u8], data: &[u8]) -> [u8; 64] {
+    let mut mac = HmacSha512::new(key);
+    mac.update(data);
+    mac.finalize()
+}
+
+/// Incremental HMAC-SHA256. Cloning a keyed instance reuses the padded key
+/// hashing, which pays off when one key MACs many messages.
+#[derive(Clone)]
+pub struct HmacSha256 {
+    inner: Sha256,
+    outer: Sha256,
+}
+
+impl HmacSha256 {
+    pub fn new(key: &[u8]) -> Self {
+        let (inner, outer) = keyed(key);
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 32] {
+        self.outer
+            .chain_update(self.inner.finalize())
+            .finalize()
+            .into()
+    }
+}
+
+/// Incremental HMAC-SHA512; see `HmacSha256`.
+#[derive(Clone)]
+pub struct HmacSha512 {
+    inner: Sha512,
+    outer: Sha512,
+}
+
+impl HmacSha512 {
+    pub fn new(key: &[u8]) -> Self {
+        let (inner, outer) = keyed(key);
+        Self { inner, outer }
+    }
+
+    pub fn update(&mut self, data: &[u8]) {
+        self.inner.update(data);
+    }
+
+    pub fn finalize(self) -> [u8; 64] {
+        self.outer
+            .chain_update(self.inner.finalize())
+            .finalize()
+            .into()
+    }
+}
+
+/// PBKDF2 (RFC 8018) with HMAC-SHA512, filling `output` from `password`,
+/// `salt` and `rounds` iterations. BIP39 seeds use 2048 rounds; deriving
+/// an encryption key from a passphrase should use many more.
+///
+/// Panics if `rounds` is zero.
+pub fn pbkdf2_hmac_sha512(password: &[u8], salt: &[u8], rounds: u32, output: &mut [u8]) {
+    assert!(rounds > 0, "PBKDF2 needs at least one round");
+    // 鍵は毎回同じなのでパディング済みの状態を使い回す
+    let keyed = HmacSha512::new(password);
+    for (i, chunk) in output.chunks_mut(64).enumerate() {
+        let mut mac = keyed.clone();
+        mac.update(salt);
+        mac.update(&(i as u32 + 1).to_be_bytes());
+        let mut u = mac.finalize();
+        let mut block = u;
+        for _ in 1..rounds {
+            let mut mac = keyed.clone();
+            mac.update(&u);
+            u = mac.finalize();
+            for (b, x) in block.iter_mut().zip(u) {
+                *b ^= x;
+            }
+        }
+        chunk.copy_from_slice(&block[..chunk.len()]);
+        u.zeroize();
+        block.zeroize();
+    }
+}
+
+/// The inner and outer hashers after absorbing `key ^ ipad` and
+/// `key ^ opad`.
+fn keyed<D: Digest + BlockSizeUser>(key: &[u8]) -> (D, D) {
+    // ブロック長より長い鍵は先にハッシュする
+    let mut block = vec![0u8; D::block_size()];
+    if key.len() > block.len() {
+        let digest = D::digest(key);
+        block[..digest.len()].copy_from_slice(&digest);
+    } else {
+        block[..key.len()].copy_from_slice(key);
+    }
+
+    let mut pad: Vec<u8> = block.iter().map(|b| b ^ 0x36).collect();
+    let inner = D::new().chain_update(&pad);
+    pad = block.iter().map(|b| b ^ 0x5c).collect();
+    let outer = D::new().chain_update(&pad);
+    block.zeroize();
+    pad.zeroize();
+    (inner, outer)
+}
+
+/// MurmurHash3 (x86, 32-bit) of `data` with `seed`, the non-cryptographic
+/// hash BIP37 bloom filters use.
+pub fn murmur3_32(data: &[u8], seed: u32) -> u32 {
+    const C1: u32 = 0xcc9e_2d51;
+    const C2: u32 = 0x1b87_3593;
+
+    let mut h = seed;
+    let mut chunks = data.chunks_exact(4);
+    for chunk in &mut chunks {
+        let k = u32::from_le_bytes(chunk.try_into().expect("4 bytes"));
+        h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+        h = h.rotate_left(13).wrapping_mul(5).wrapping_add(0xe654_6b64);
+    }
+
+    // 4 バイトに満たない残りをリトルエンディアンで詰める
+    let tail = chunks.remainder();
+    if !tail.is_empty() {
+        let k = tail.iter().rev().fold(0u32, |acc, &b| acc << 8 | b as u32);
+        h ^= k.wrapping_mul(C1).rotate_left(15).wrapping_mul(C2);
+    }
+
+    h ^= data.len() as u32;
+    h ^= h >> 16;
+    h = h.wrapping_mul(0x85eb_ca6b);
+    h ^= h >> 13;
+    h = h.wrapping_mul(0xc2b2_ae35);
+    h ^ h >> 16
+}
+
+// RIPEMD-160 の各ステップで使うメッセージワードの添字と回転量
+const R_LEFT: [usize; 80] = [
+    0, 1, 2, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 7, 4, 13, 1, 10, 6, 15, 3, 12, 0, 9, 5,
+    2, 14, 11, 8, 3, 10, 14, 4, 9, 15, 8, 1, 2, 7, 0, 6, 13, 11, 5, 12, 1, 9, 11, 10, 0, 8, 12, 4,
+    13, 3, 7, 15, 14, 5, 6, 2, 4, 0, 5, 9, 7, 12, 2, 10, 14, 1, 3, 8, 11, 6, 15, 13,
+];
+const R_RIGHT: [usize; 80] = [
+    5, 14, 7, 0, 9, 2, 11, 4, 13, 6, 15, 8, 1, 10, 3, 12, 6, 11, 3, 7, 0, 13, 5, 10, 14, 15, 8, 12,
+    4, 9, 1, 2, 15, 5, 1, 3, 7, 14, 6, 9, 11, 8, 12, 2, 10, 0, 4, 13, 8, 6, 4, 1, 3, 11, 15, 0, 5,
+    12, 2, 13, 9, 7, 10, 14, 12, 15, 10, 4, 1, 5, 8, 7, 6, 2, 13, 14, 0, 3, 9, 11,
+];
+const S_LEFT: [u32; 80] = [
+    11, 14, 15, 12, 5, 8, 7, 9, 11, 13, 14, 15, 6, 7, 9, 8, 7, 6, 8, 13, 11, 9, 7, 15, 7, 12, 15,
+    9, 11, 7, 13, 12, 11, 13, 6, 7, 14, 9, 13, 15, 14, 8, 13, 6, 5, 12, 7, 5, 11, 12, 14, 15, 14,
+    15, 9, 8, 9, 14, 5, 6, 8, 6, 5, 12, 9, 15, 5, 11, 6, 8, 13, 12, 5, 12, 13, 14, 11, 8, 5, 6,
+];
+const S_RIGHT: [u32; 80] = [
+    8, 9, 9, 11, 13, 15, 15, 5, 7, 7, 8, 11, 14, 14, 12, 6, 9, 13, 15, 7, 12, 8, 9, 11, 7, 7, 12,
+    7, 6, 15, 13, 11, 9, 7, 15, 11, 8, 6, 6, 14, 12, 13, 5, 14, 13, 13, 7, 5, 15, 5, 8, 11, 14, 14,
+    6, 14, 6, 9, 12, 9, 12, 5, 15, 8, 8, 5, 12, 9, 12, 5, 14, 6, 8, 13, 6, 5, 15, 13, 11, 11,
+];
+const K_LEFT: [u32; 5] = [
+    0x0000_0000,
+    0x5A82_7999,
+    0x6ED9_EBA1,
+    0x8F1B_BCDC,
+    0xA953_FD4E,
+];
+const K_RIGHT: [u32; 5] = [
+    0x50A2_8BE6,
+    0x5C4D_D124,
+    0x6D70_3EF3,
+    0x7A6D_76E9,
+    0x0000_0000,
+];
+
+fn ripemd_f(round: usize, x: u32, y: u32, z: u32) -> u32 {
+    match round {
+        0 => x ^ y ^ z,
+        1 => (x & y) | (!x & z),
+        2 => (x | !y) ^ z,
+        3 => (x & z) | (y & !z),
+        _ => x ^ (y | !z),
+    }
+}
+
+/// RIPEMD-160. Only used through `hash160`, so it is not optimised.
+fn ripemd160(data: &[u8]) -> [u8; 20] {
+    let mut h: [u32; 5] = [
+        0x6745_2301,
+        0xEFCD_AB89,
+        0x98BA_DCFE,
+        0x1032_5476,
+        0xC3D2_E1F0,
+    ];
+
+    // MD4 系のパディング: 0x80, 0 埋め, ビット長 (リトルエンディアン)
+    let mut message = data.to_vec();
+    message.push(0x80);
+    while message.len() % 64 != 56 {
+        message.push(0);
+    }
+    message.extend_from_slice(&((data.len() as u64) * 8).to_le_bytes());
+
+    for chunk in message.chunks(64) {
+        let mut x = [0u32; 16];
+        for (word, bytes) in x.iter_mut().zip(chunk.chunks(4)) {
+            *word = u32::from_le_bytes(bytes.try_into().unwrap());
+        }
+
+        let [mut al, mut bl, mut cl, mut dl, mut el] = h;
+        let [mut ar, mut br, mut cr, mut dr, mut er] = h;
+        for j in 0..80 {
+            let round = j / 16;
+
+            let t = al
+                .wrapping_add(ripemd_f(round, bl, cl, dl))
+                .wrapping_add(x[R_LEFT[j]])
+                .wrapping_add(K_LEFT[round])
+                .rotate_left(S_LEFT[j])
+                .wrapping_add(el);
+            al = el;
+            el = dl;
+            dl = cl.rotate_left(10);
+            cl = bl;
+            bl = t;
+
+            // 右の系列は関数を逆順に使う
+            let t = ar
+                .wrapping_add(ripemd_f(4 - round, br, cr, dr))
+                .wrapping_add(x[R_RIGHT[j]])
+                .wrapping_add(K_RIGHT[round])
+                .rotate_left(S_RIGHT[j])
+                .wrapping_add(er);
+            ar = er;
+            er = dr;
+            dr = cr.rotate_left(10);
+            cr = br;
+            br = t;
+        }
+
+        let t = h[1].wrapping_add(cl).wrapping_add(dr);
+        h[1] = h[2].wrapping_add(dl).wrapping_add(er);
+        h[2] = h[3].wrapping_add(el).wrapping_add(ar);
+        h[3] = h[4].wrapping_add(al).wrapping_add(br);
+        h[4] = h[0].wrapping_add(bl).wrapping_add(cr);
+        h[0] = t;
+    }
+
+    let mut ret = [0u8; 20];
+    for (bytes, word) in ret.chunks_mut(4).zip(h) {
+        bytes.copy_from_slice(&word.to_le_bytes());
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        hash160, hash256, hmac_sha256, hmac_sha512, murmur3_32, pbkdf2_hmac_sha512, ripemd160,
+        tagged_hash, HashWriter, HmacSha256, HmacSha512,
+    };
+
+    fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02x}", b)).collect()
+    }
+
+    #[test]
+    fn hash256_of_empty_input() {
+        assert_eq!(
+            encode(&hash256(b"")),
+            "5df6e0e2761359d30a8275058e299fcc0381534545f55cf43e41983f5d4c9456"
+        );
+    }
+
+    #[test]
+    fn ripemd160_vectors() {
+        let cases: [(&[u8], &str); 4] = [
+            (b"", "9c1185a5c5e9fc54612808977ee8f548b2258d31"),
+            (b"abc", "8eb208f7e05d987a9b044a8e98c6b087f15a0bfc"),
+            (
+                b"message digest",
+                "5d0689ef49d2fae572b881b123a85ffa21595f36",
+            ),
+            (
+                b"12345678901234567890123456789012345678901234567890123456789012345678901234567890",
+                "9b752e45573d4b39f4dbd3323cab82bf63326bfb",
+            ),
+        ];
+        for (input, expected) in cases {
+            assert_eq!(encode(&ripemd160(input)), expected);
+        }
+    }
+
+    #[test]
+    fn hash160_of_generator() {
+        // 生成元 G の圧縮 SEC の hash160 (秘密鍵 1 のアドレスのもと)
+        let sec = [
+            0x02, 0x79, 0xbe, 0x66, 0x7e, 0xf9, 0xdc, 0xbb, 0xac, 0x55, 0xa0, 0x62, 0x95, 0xce,
+            0x87, 0x0b, 0x07, 0x02, 0x9b, 0xfc, 0xdb, 0x2d, 0xce, 0x28, 0xd9, 0x59, 0xf2, 0x81,
+            0x5b, 0x16, 0xf8, 0x17, 0x98,
+        ];
+        assert_eq!(
+            encode(&hash160(&sec)),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+    }
+
+    #[test]
+    fn hmac_sha256_vectors() {
+        // RFC 4231 テストケース 2 と 6
+        assert_eq!(
+            encode(&hmac_sha256(b"Jefe", b"what do ya want for nothing?")),
+            "5bdcc146bf60754e6a042426089575c75a003f089d2739839dec58b964ec3843"
+        );
+        assert_eq!(
+            encode(&hmac_sha256(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "60e431591ee0b67f0d8a26aacbf5b77f8e0bc6213728c5140546040f0ee37f54"
+        );
+    }
+
+    #[test]
+    fn hmac_sha512_vectors() {
+        // RFC 4231 テストケース 2
+        assert_eq!(
+            encode(&hmac_sha512(b"Jefe", b"what do ya want for nothing?")),
+            "164b7a7bfcf819e2e395fbe73b56e0a387bd64222e831fd610270cd7ea250554\
+             9758bf75c05a994a6d034f65f8f0e6fdcaeab1a34d4a6b4b636e070a38bce737"
+        );
+        // RFC 4231 テストケース 6: ブロック長より長い鍵
+        assert_eq!(
+            encode(&hmac_sha512(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )),
+            "80b24263c7c1a3ebb71493c1dd7be8b49b46d1f41b4aeec1121b013783f8f352\
+             6b56d037e05f2598bd0fd2215d6a1e5295e64f73f63f0aec8b915a985d786598"
+        );
+    }
+
+    #[test]
+    fn hash_writer() {
+        use crate::encoding::{write_u32_le, write_varint};
+        use sha2::{Digest, Sha256};
+        use std::io::Write;
+
+        let mut buf = Vec::new();
+        let mut writer = HashWriter::new();
+        for sink in [&mut buf as &mut dyn Write, &mut writer] {
+            write_u32_le(sink, 1).unwrap();
+            write_varint(sink, 300).unwrap();
+            sink.write_all(&[0xab; 300]).unwrap();
+        }
+        assert_eq!(writer.clone().hash256(), hash256(&buf));
+        assert_eq!(writer.sha256(), <[u8; 32]>::from(Sha256::digest(&buf)));
+
+        let mut writer = HashWriter::tagged("TapSighash");
+        writer.write_all(&buf).unwrap();
+        assert_eq!(writer.sha256(), tagged_hash("TapSighash", &buf));
+        assert_eq!(HashWriter::default().hash256(), hash256(b""));
+    }
+
+    #[test]
+    fn incremental_hmac() {
+        let key = b"Jefe";
+        let mut keyed = HmacSha256::new(key);
+        keyed.update(b"what do ya ");
+        let mut other = keyed.clone();
+        keyed.update(b"want for nothing?");
+        assert_eq!(
+            keyed.finalize(),
+            hmac_sha256(key, b"what do ya want for nothing?")
+        );
+        other.update(b"need?");
+        assert_eq!(other.finalize(), hmac_sha256(key, b"what do ya need?"));
+
+        let mut keyed = HmacSha512::new(&[0xaa; 131]);
+        keyed.update(b"Test Using Larger Than Block-Size Key");
+        keyed.update(b" - Hash Key First");
+        assert_eq!(
+            keyed.finalize(),
+            hmac_sha512(
+                &[0xaa; 131],
+                b"Test Using Larger Than Block-Size Key - Hash Key First"
+            )
+        );
+    }
+
+    #[test]
+    fn pbkdf2_vectors() {
+        // PBKDF2-HMAC-SHA512 の既知の値 (password / salt)
+        let cases = [
+            (
+                1,
+                "867f70cf1ade02cff3752599a3a53dc4af34c7a669815ae5d513554e1c8cf252\
+                 c02d470a285a0501bad999bfe943c08f050235d7d68b1da55e63f73b60a57fce",
+            ),
+            (
+                2,
+                "e1d9c16aa681708a45f5c7c4e215ceb66e011a2e9f0040713f18aefdb866d53c\
+                 f76cab2868a39b9f7840edce4fef5a82be67335c77a6068e04112754f27ccf4e",
+            ),
+            (
+                4096,
+                "d197b1b33db0143e018b12f3d1d1479e6cdebdcc97c5c0f87f6902e072f457b5\
+                 143f30602641b3d55cd335988cb36b84376060ecd532e039b742a239434af2d5",
+            ),
+        ];
+        for (rounds, expected) in cases {
+            let mut output = [0u8; 64];
+            pbkdf2_hmac_sha512(b"password", b"salt", rounds, &mut output);
+            assert_eq!(encode(&output), expected);
+        }
+
+        // 64 バイトを超える出力は次のブロックに続き、短い出力はその先頭
+        let mut long = [0u8; 100];
+        pbkdf2_hmac_sha512(b"password", b"salt", 2, &mut long);
+        let mut short = [0u8; 20];
+        pbkdf2_hmac_sha512(b"password", b"salt", 2, &mut short);
+        assert_eq!(encode(&long[..64]), cases[1].1);
+        assert_eq!(short, long[..20]);
+    }
+
+    #[test]
+    fn murmur3_vectors() {
+        // Bitcoin Core の hash_tests から (期待値, シード, 入力)
+        let cases: [(u32, u32, &[u8]); 15] = [
+            (0x0000_0000, 0x0000_0000, b""),
+            (0x6a39_6f08, 0xfba4_c795, b""),
+            (0x81f1_6f39, 0xffff_ffff, b""),
+            (0x514e_28b7, 0x0000_0000, &[0x00]),
+            (0xea3f_0b17, 0xfba4_c795, &[0x00]),
+            (0xfd6c_f10d, 0x0000_0000, &[0xff]),
+            (0x16c6_b7ab, 0x0000_0000, &[0x00, 0x11]),
+            (0x8eb5_1c3d, 0x0000_0000, &[0x00, 0x11, 0x22]),
+            (0xb447_1bf8, 0x0000_0000, &[0x00, 0x11, 0x22, 0x33]),
+            (0xe230_1fa8, 0x0000_0000, &[0x00, 0x11, 0x22, 0x33, 0x44]),
+            (
+                0xfc2e_4a15,
+                0x0000_0000,
+                &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55],
+            ),
+            (
+                0xb074_502c,
+                0x0000_0000,
+                &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66],
+            ),
+            (
+                0x8034_d2a0,
+                0x0000_0000,
+                &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77],
+            ),
+            (
+                0xb469_8def,
+                0x0000_0000,
+                &[0x00, 0x11, 0x22, 0x33, 0x44, 0x55, 0x66, 0x77, 0x88],
+            ),
+            (0x5e92_8f0f, 0x0000_0000, b"hello world"),
+        ];
+        for (expected, seed, data) in cases {
+            assert_eq!(murmur3_32(data, seed), expected, "{:?}", data);
+        }
+    }
+
+    #[test]
+    fn tagged_hash_prefixes_tag_hash_twice() {
+        use sha2::{Digest, Sha256};
+
+        // キャッシュされるタグもされないタグも同じ定義どおりになる
+        for tag in ["BIP0340/challenge", "TapTweak", "TapSighash", ""] {
+            let tag_hash = Sha256::digest(tag.as_bytes());
+            let mut preimage = tag_hash.to_vec();
+            preimage.extend_from_slice(&tag_hash);
+            preimage.extend_from_slice(b"data");
+
+            let expected = <[u8; 32]>::from(Sha256::digest(&preimage));
+            assert_eq!(tagged_hash(tag, b"data"), expected);
+            // 2 回目はキャッシュから
+            assert_eq!(tagged_hash(tag, b"data"), expected);
+        }
+        assert_ne!(
+            tagged_hash("TapLeaf", b"data"),
+            tagged_hash("TapBranch", b"data")
+        );
+    }
+}
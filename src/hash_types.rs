@@ -0,0 +1,106 @@
+//! Newtypes for the double-SHA256 hashes that identify transactions and
+//! blocks.
+//!
+//! They are stored in the byte order `hash256` produces, but by convention
+//! shown (in explorers, RPC and Bitcoin Core) byte-reversed, so `Display`
+//! and `FromStr` use the reversed hex.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::HexError;
+use crate::hex;
+
+macro_rules! hash_newtype {
+    ($(#[$doc:meta])* $name:ident) => {
+        $(#[$doc])*
+        #[derive(Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+        pub struct $name([u8; 32]);
+
+        impl $name {
+            /// Wraps a hash in internal byte order.
+            pub fn from_bytes(bytes: [u8; 32]) -> Self {
+                Self(bytes)
+            }
+
+            /// The hash in internal byte order.
+            pub fn to_bytes(self) -> [u8; 32] {
+                self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                let mut bytes = self.0;
+                bytes.reverse();
+                f.write_str(&hex::encode(bytes))
+            }
+        }
+
+        impl fmt::Debug for $name {
+            fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "{}({})", stringify!($name), self)
+            }
+        }
+
+        impl FromStr for $name {
+            type Err = HexError;
+
+            fn from_str(s: &str) -> Result<Self, Self::Err> {
+                let mut bytes = hex::decode_array::<32>(s)?;
+                bytes.reverse();
+                Ok(Self(bytes))
+            }
+        }
+    };
+}
+
+hash_newtype!(
+    /// A transaction id: `hash256` of the transaction without witnesses.
+    Txid
+);
+
+hash_newtype!(
+    /// A block hash: `hash256` of the 80-byte block header.
+    BlockHash
+);
+
+#[cfg(test)]
+mod tests {
+    use super::{BlockHash, Txid};
+    use crate::error::HexError;
+    use crate::hash::hash256;
+    use crate::hex;
+
+    #[test]
+    fn genesis_block_hash() {
+        let header = hex::decode(
+            "0100000000000000000000000000000000000000000000000000000000000000\
+             000000003ba3edfd7a7b12b27ac72c3e67768f617fc81bc3888a51323a9fb8aa\
+             4b1e5e4a29ab5f49ffff001d1dac2b7c",
+        )
+        .unwrap();
+        let hash = BlockHash::from_bytes(hash256(&header));
+        let expected = "000000000019d6689c085ae165831e934ff763ae46a2a6c172b3f1b60a8ce26f";
+
+        // 表示はバイト順を反転した 16 進
+        assert_eq!(hash.to_string(), expected);
+        assert_eq!(expected.parse::<BlockHash>().unwrap(), hash);
+        assert_eq!(hash.to_bytes()[31], 0x00);
+        assert_eq!(format!("{:?}", hash), format!("BlockHash({})", expected));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b";
+        assert_eq!(txid.parse::<Txid>().unwrap().to_string(), txid);
+        assert_eq!(
+            txid[..62].parse::<Txid>(),
+            Err(HexError::InvalidLength {
+                expected: 32,
+                actual: 31
+            })
+        );
+        assert_eq!(txid[1..].parse::<Txid>(), Err(HexError::OddLength));
+    }
+}
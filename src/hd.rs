@@ -0,0 +1,594 @@
+use std::fmt;
+use std::str::FromStr;
+
+use zeroize::Zeroize;
+
+use crate::base58::{decode_base58check, encode_base58check};
+use crate::error::{HdError, ParseError};
+use crate::hash::hmac_sha512;
+use crate::network::Network;
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::scalar::Scalar;
+
+/// Child numbers from `HARDENED` on derive hardened children (`i'`).
+pub const HARDENED: u32 = 1 << 31;
+
+/// A BIP32 extended private key: a private key plus the chain code and
+/// position needed to derive its children.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ExtendedPrivKey {
+    pub network: Network,
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: [u8; 32],
+    pub private_key: PrivateKey,
+}
+
+/// A BIP32 extended public key, which can derive the public keys of all
+/// non-hardened children.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ExtendedPubKey {
+    pub network: Network,
+    pub depth: u8,
+    pub parent_fingerprint: [u8; 4],
+    pub child_number: u32,
+    pub chain_code: [u8; 32],
+    pub public_key: S256Point,
+}
+
+/// I = HMAC-SHA512(chain_code, data) を鍵の調整値と子のチェーンコードに分ける
+fn split_hmac(key: &[u8], data: &[u8]) -> Result<(Scalar, [u8; 32]), HdError> {
+    let i = hmac_sha512(key, data);
+    let tweak = Scalar::from_be_bytes(&i[..32]).map_err(|_| HdError::InvalidKey)?;
+    let mut chain_code = [0u8; 32];
+    chain_code.copy_from_slice(&i[32..]);
+    Ok((tweak, chain_code))
+}
+
+impl ExtendedPrivKey {
+    /// The master key of the tree grown from `seed`.
+    pub fn new_master(seed: &[u8], network: Network) -> Result<Self, HdError> {
+        if !(16..=64).contains(&seed.len()) {
+            return Err(HdError::InvalidSeedLength);
+        }
+        let (secret, chain_code) = split_hmac(b"Bitcoin seed", seed)?;
+        if secret.is_zero() {
+            return Err(HdError::InvalidKey);
+        }
+
+        Ok(Self {
+            network,
+            depth: 0,
+            parent_fingerprint: [0; 4],
+            child_number: 0,
+            chain_code,
+            private_key: PrivateKey::new(secret),
+        })
+    }
+
+    /// The child with number `index`; hardened if `index >= HARDENED`.
+    pub fn derive_child(&self, index: u32) -> Result<Self, HdError> {
+        let mut data = Vec::with_capacity(37);
+        if index >= HARDENED {
+            data.push(0);
+            data.extend_from_slice(&self.private_key.secret().to_be_bytes());
+        } else {
+            data.extend_from_slice(&self.private_key.point().sec_compressed());
+        }
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let hmac = split_hmac(&self.chain_code, &data);
+        // 強化導出では秘密鍵が入っている
+        data.zeroize();
+        let (tweak, chain_code) = hmac?;
+        let private_key = self
+            .private_key
+            .add_tweak(&tweak)
+            .map_err(|_| HdError::InvalidKey)?;
+
+        Ok(Self {
+            network: self.network,
+            depth: self.depth.checked_add(1).ok_or(HdError::MaxDepth)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            private_key,
+        })
+    }
+
+    /// First 4 bytes of the hash160 of the public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.private_key.point().fingerprint()
+    }
+}
+
+impl ExtendedPubKey {
+    /// The extended public key of `xprv`, with the same position in the
+    /// tree.
+    pub fn from_priv(xprv: &ExtendedPrivKey) -> Self {
+        Self {
+            network: xprv.network,
+            depth: xprv.depth,
+            parent_fingerprint: xprv.parent_fingerprint,
+            child_number: xprv.child_number,
+            chain_code: xprv.chain_code,
+            public_key: xprv.private_key.point(),
+        }
+    }
+
+    /// The non-hardened child with number `index`, whose public key equals
+    /// that of `ExtendedPrivKey::derive_child(index)`.
+    pub fn derive_child(&self, index: u32) -> Result<Self, HdError> {
+        if index >= HARDENED {
+            return Err(HdError::HardenedFromPublic);
+        }
+        let mut data = Vec::with_capacity(37);
+        data.extend_from_slice(&self.public_key.sec_compressed());
+        data.extend_from_slice(&index.to_be_bytes());
+
+        let (tweak, chain_code) = split_hmac(&self.chain_code, &data)?;
+        let public_key = self
+            .public_key
+            .add_tweak(&tweak)
+            .map_err(|_| HdError::InvalidKey)?;
+
+        Ok(Self {
+            network: self.network,
+            depth: self.depth.checked_add(1).ok_or(HdError::MaxDepth)?,
+            parent_fingerprint: self.fingerprint(),
+            child_number: index,
+            chain_code,
+            public_key,
+        })
+    }
+
+    /// First 4 bytes of the hash160 of the public key.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        self.public_key.fingerprint()
+    }
+}
+
+/// A BIP32 derivation path such as `m/84'/0'/0'/0/5`, stored as child
+/// numbers with `HARDENED` added for hardened steps.
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DerivationPath(Vec<u32>);
+
+impl DerivationPath {
+    /// `m`, the master key itself.
+    pub fn master() -> Self {
+        Self(Vec::new())
+    }
+
+    /// The path extended by one step.
+    pub fn child(&self, index: u32) -> Self {
+        let mut ret = self.clone();
+        ret.0.push(index);
+        ret
+    }
+
+    /// `m/44'/coin'/account'`, the account of a legacy (P2PKH) wallet.
+    pub fn bip44(network: Network, account: u32) -> Self {
+        Self::account(44, network, account)
+    }
+
+    /// `m/49'/coin'/account'`, the account of a P2SH-wrapped segwit wallet.
+    pub fn bip49(network: Network, account: u32) -> Self {
+        Self::account(49, network, account)
+    }
+
+    /// `m/84'/coin'/account'`, the account of a native segwit wallet.
+    pub fn bip84(network: Network, account: u32) -> Self {
+        Self::account(84, network, account)
+    }
+
+    /// `m/86'/coin'/account'`, the account of a taproot wallet.
+    pub fn bip86(network: Network, account: u32) -> Self {
+        Self::account(86, network, account)
+    }
+
+    fn account(purpose: u32, network: Network, account: u32) -> Self {
+        assert!(account < HARDENED, "The account number must be below 2^31.");
+        Self(vec![
+            purpose + HARDENED,
+            network.coin_type() + HARDENED,
+            account + HARDENED,
+        ])
+    }
+
+    pub fn as_slice(&self) -> &[u32] {
+        &self.0
+    }
+}
+
+/// Hardened steps are written with `'`.
+impl fmt::Display for DerivationPath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "m")?;
+        for &index in &self.0 {
+            if index >= HARDENED {
+                write!(f, "/{}'", index - HARDENED)?;
+            } else {
+                write!(f, "/{}", index)?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Accepts `'`, `h` and `H` as the hardened marker.
+impl FromStr for DerivationPath {
+    type Err = HdError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut steps = s.split('/');
+        if steps.next() != Some("m") {
+            return Err(HdError::InvalidPath);
+        }
+
+        steps
+            .map(|step| {
+                let (number, hardened) = match step.strip_suffix(['\'', 'h', 'H']) {
+                    Some(number) => (number, true),
+                    None => (step, false),
+                };
+                // "+1" のような符号付きの表記は受け付けない
+                if number.is_empty() || !number.bytes().all(|b| b.is_ascii_digit()) {
+                    return Err(HdError::InvalidPath);
+                }
+                let index: u32 = number.parse().map_err(|_| HdError::InvalidPath)?;
+                if index >= HARDENED {
+                    return Err(HdError::InvalidPath);
+                }
+                Ok(if hardened { index + HARDENED } else { index })
+            })
+            .collect::<Result<_, _>>()
+            .map(Self)
+    }
+}
+
+impl ExtendedPrivKey {
+    /// Derives every step of `path` in turn.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, HdError> {
+        path.0
+            .iter()
+            .try_fold(self.clone(), |key, &index| key.derive_child(index))
+    }
+}
+
+impl ExtendedPubKey {
+    /// Derives every step of `path` in turn; fails on hardened steps.
+    pub fn derive_path(&self, path: &DerivationPath) -> Result<Self, HdError> {
+        path.0
+            .iter()
+            .try_fold(*self, |key, &index| key.derive_child(index))
+    }
+}
+
+// 78 バイトの直列化: version | depth | 親の指紋 | 子番号 | チェーンコード | 鍵
+fn encode(
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: &[u8; 32],
+    key: &[u8; 33],
+) -> String {
+    let mut payload = Vec::with_capacity(78);
+    payload.extend_from_slice(&version);
+    payload.push(depth);
+    payload.extend_from_slice(&parent_fingerprint);
+    payload.extend_from_slice(&child_number.to_be_bytes());
+    payload.extend_from_slice(chain_code);
+    payload.extend_from_slice(key);
+    encode_base58check(&payload)
+}
+
+struct Decoded {
+    version: [u8; 4],
+    depth: u8,
+    parent_fingerprint: [u8; 4],
+    child_number: u32,
+    chain_code: [u8; 32],
+    key: [u8; 33],
+}
+
+fn decode(s: &str) -> Result<Decoded, ParseError> {
+    let payload = decode_base58check(s)?;
+    if payload.len() != 78 {
+        return Err(ParseError::InvalidLength);
+    }
+    let mut ret = Decoded {
+        version: [0; 4],
+        depth: payload[4],
+        parent_fingerprint: [0; 4],
+        child_number: u32::from_be_bytes(payload[9..13].try_into().unwrap()),
+        chain_code: [0; 32],
+        key: [0; 33],
+    };
+    ret.version.copy_from_slice(&payload[..4]);
+    ret.parent_fingerprint.copy_from_slice(&payload[5..9]);
+    ret.chain_code.copy_from_slice(&payload[13..45]);
+    ret.key.copy_from_slice(&payload[45..]);
+    // マスター鍵には親も子番号もない (BIP32 テストベクター 5)
+    if ret.depth == 0 && (ret.parent_fingerprint != [0; 4] || ret.child_number != 0) {
+        return Err(ParseError::InvalidMasterKey);
+    }
+    Ok(ret)
+}
+
+/// Base58Check serialization (`xprv...` / `tprv...`).
+impl fmt::Display for ExtendedPrivKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mut key = [0u8; 33];
+        key[1..].copy_from_slice(&self.private_key.secret().to_be_bytes());
+        let encoded = encode(
+            self.network.xprv_version(),
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &key,
+        );
+        key.zeroize();
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ExtendedPrivKey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = decode(s)?;
+        let network =
+            Network::from_xprv_version(decoded.version).ok_or(ParseError::InvalidPrefix)?;
+        if decoded.key[0] != 0 {
+            return Err(ParseError::InvalidPrefix);
+        }
+        let secret =
+            Scalar::from_be_bytes(&decoded.key[1..]).map_err(|_| ParseError::InvalidScalar)?;
+        if secret.is_zero() {
+            return Err(ParseError::InvalidScalar);
+        }
+
+        Ok(Self {
+            network,
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            private_key: PrivateKey::new(secret),
+        })
+    }
+}
+
+/// Base58Check serialization (`xpub...` / `tpub...`).
+impl fmt::Display for ExtendedPubKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let encoded = encode(
+            self.network.xpub_version(),
+            self.depth,
+            self.parent_fingerprint,
+            self.child_number,
+            &self.chain_code,
+            &self.public_key.sec_compressed(),
+        );
+        write!(f, "{}", encoded)
+    }
+}
+
+impl FromStr for ExtendedPubKey {
+    type Err = ParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let decoded = decode(s)?;
+        let network =
+            Network::from_xpub_version(decoded.version).ok_or(ParseError::InvalidPrefix)?;
+        let public_key = S256Point::parse(&decoded.key)?;
+
+        Ok(Self {
+            network,
+            depth: decoded.depth,
+            parent_fingerprint: decoded.parent_fingerprint,
+            child_number: decoded.child_number,
+            chain_code: decoded.chain_code,
+            public_key,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{DerivationPath, ExtendedPrivKey, ExtendedPubKey, HARDENED};
+    use crate::error::{HdError, ParseError};
+    use crate::network::Network;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn bip32_test_vector_1() {
+        let master =
+            ExtendedPrivKey::new_master(&hex("000102030405060708090a0b0c0d0e0f"), Network::Mainnet)
+                .unwrap();
+        assert_eq!(master.fingerprint(), [0x34, 0x42, 0x19, 0x3e]);
+
+        // (子番号, xpub, xprv)
+        let chain = [
+            (
+                None,
+                "xpub661MyMwAqRbcFtXgS5sYJABqqG9YLmC4Q1Rdap9gSE8NqtwybGhePY2gZ29ESFjqJoCu1Rupje8YtGqsefD265TMg7usUDFdp6W1EGMcet8",
+                "xprv9s21ZrQH143K3QTDL4LXw2F7HEK3wJUD2nW2nRk4stbPy6cq3jPPqjiChkVvvNKmPGJxWUtg6LnF5kejMRNNU3TGtRBeJgk33yuGBxrMPHi",
+            ),
+            (
+                Some(HARDENED),
+                "xpub68Gmy5EdvgibQVfPdqkBBCHxA5htiqg55crXYuXoQRKfDBFA1WEjWgP6LHhwBZeNK1VTsfTFUHCdrfp1bgwQ9xv5ski8PX9rL2dZXvgGDnw",
+                "xprv9uHRZZhk6KAJC1avXpDAp4MDc3sQKNxDiPvvkX8Br5ngLNv1TxvUxt4cV1rGL5hj6KCesnDYUhd7oWgT11eZG7XnxHrnYeSvkzY7d2bhkJ7",
+            ),
+            (
+                Some(1),
+                "xpub6ASuArnXKPbfEwhqN6e3mwBcDTgzisQN1wXN9BJcM47sSikHjJf3UFHKkNAWbWMiGj7Wf5uMash7SyYq527Hqck2AxYysAA7xmALppuCkwQ",
+                "xprv9wTYmMFdV23N2TdNG573QoEsfRrWKQgWeibmLntzniatZvR9BmLnvSxqu53Kw1UmYPxLgboyZQaXwTCg8MSY3H2EU4pWcQDnRnrVA1xe8fs",
+            ),
+            (
+                Some(2 + HARDENED),
+                "xpub6D4BDPcP2GT577Vvch3R8wDkScZWzQzMMUm3PWbmWvVJrZwQY4VUNgqFJPMM3No2dFDFGTsxxpG5uJh7n7epu4trkrX7x7DogT5Uv6fcLW5",
+                "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM",
+            ),
+        ];
+
+        let mut xprv = master;
+        for (index, xpub_str, xprv_str) in chain {
+            if let Some(index) = index {
+                xprv = xprv.derive_child(index).unwrap();
+            }
+            let xpub = ExtendedPubKey::from_priv(&xprv);
+            assert_eq!(xprv.to_string(), xprv_str);
+            assert_eq!(xpub.to_string(), xpub_str);
+            assert_eq!(xprv_str.parse::<ExtendedPrivKey>().unwrap(), xprv);
+            assert_eq!(xpub_str.parse::<ExtendedPubKey>().unwrap(), xpub);
+        }
+    }
+
+    #[test]
+    fn public_derivation_matches_private() {
+        let master = ExtendedPrivKey::new_master(&[0x42; 32], Network::Testnet).unwrap();
+        let xpub = ExtendedPubKey::from_priv(&master);
+
+        for index in [0, 1, 7, HARDENED - 1] {
+            assert_eq!(
+                xpub.derive_child(index).unwrap(),
+                ExtendedPubKey::from_priv(&master.derive_child(index).unwrap())
+            );
+        }
+        assert_eq!(
+            xpub.derive_child(HARDENED),
+            Err(HdError::HardenedFromPublic)
+        );
+        assert!(xpub.to_string().starts_with("tpub"));
+        assert!(master.to_string().starts_with("tprv"));
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            ExtendedPrivKey::new_master(&[0; 15], Network::Mainnet),
+            Err(HdError::InvalidSeedLength)
+        );
+        assert_eq!(
+            ExtendedPrivKey::new_master(&[0; 65], Network::Mainnet),
+            Err(HdError::InvalidSeedLength)
+        );
+
+        let master = ExtendedPrivKey::new_master(&[0x42; 32], Network::Mainnet).unwrap();
+        let xprv = master.to_string();
+        let xpub = ExtendedPubKey::from_priv(&master).to_string();
+
+        // xpub を xprv として読むと version が合わない
+        assert_eq!(
+            xpub.parse::<ExtendedPrivKey>(),
+            Err(ParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            xprv.parse::<ExtendedPubKey>(),
+            Err(ParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            xprv[..xprv.len() - 1].parse::<ExtendedPrivKey>(),
+            Err(ParseError::InvalidChecksum)
+        );
+
+        // BIP32 テストベクター 5: 深さ 0 で親の指紋や子番号が 0 でない
+        for s in [
+            "xprv9s2SPatNQ9Vc6GTbVMFPFo7jsaZySyzk7L8n2uqKXJen3KUmvQNTuLh3fhZMBoG3G4ZW1N2kZuHEPY53qmbZzCHshoQnNf4GvELZfqTUrcv",
+            "xprv9s21ZrQH4r4TsiLvyLXqM9P7k1K3EYhA1kkD6xuquB5i39AU8KF42acDyL3qsDbU9NmZn6MsGSUYZEsuoePmjzsB3eFKSUEh3Gu1N3cqVUN",
+        ] {
+            assert_eq!(s.parse::<ExtendedPrivKey>(), Err(ParseError::InvalidMasterKey));
+        }
+        for s in [
+            "xpub661no6RGEX3uJkY4bNnPcw4URcQTrSibUZ4NqJEw5eBkv7ovTwgiT91XX27VbEXGENhYRCf7hyEbWrR3FewATdCEebj6znwMfQkhRYHRLpJ",
+            "xpub661MyMwAuDcm6CRQ5N4qiHKrJ39Xe1R1NyfouMKTTWcguwVcfrZJaNvhpebzGerh7gucBvzEQWRugZDuDXjNDRmXzSZe4c7mnTK97pTvGS8",
+        ] {
+            assert_eq!(s.parse::<ExtendedPubKey>(), Err(ParseError::InvalidMasterKey));
+        }
+    }
+
+    #[test]
+    fn derivation_path() {
+        let path: DerivationPath = "m/84'/0'/0'/0/5".parse().unwrap();
+        assert_eq!(path.as_slice(), [84 + HARDENED, HARDENED, HARDENED, 0, 5]);
+        assert_eq!(path.to_string(), "m/84'/0'/0'/0/5");
+        assert_eq!("m/84h/0H/0'/0/5".parse::<DerivationPath>().unwrap(), path);
+        assert_eq!(
+            DerivationPath::bip84(Network::Mainnet, 0).child(0).child(5),
+            path
+        );
+        assert_eq!(
+            DerivationPath::bip44(Network::Testnet, 2).to_string(),
+            "m/44'/1'/2'"
+        );
+        assert_eq!(
+            DerivationPath::bip49(Network::Mainnet, 0).to_string(),
+            "m/49'/0'/0'"
+        );
+        assert_eq!(
+            DerivationPath::bip86(Network::Mainnet, 0).to_string(),
+            "m/86'/0'/0'"
+        );
+        assert_eq!(
+            DerivationPath::bip86(Network::Regtest, 0).to_string(),
+            "m/86'/1'/0'"
+        );
+        assert_eq!("m".parse::<DerivationPath>(), Ok(DerivationPath::master()));
+
+        for invalid in [
+            "",
+            "84'/0'",
+            "m/",
+            "m//1",
+            "m/x",
+            "m/+1",
+            "m/1''",
+            "m/2147483648",
+        ] {
+            assert_eq!(
+                invalid.parse::<DerivationPath>(),
+                Err(HdError::InvalidPath),
+                "{}",
+                invalid
+            );
+        }
+    }
+
+    #[test]
+    fn derive_path() {
+        let master =
+            ExtendedPrivKey::new_master(&hex("000102030405060708090a0b0c0d0e0f"), Network::Mainnet)
+                .unwrap();
+        let path = "m/0'/1/2'".parse().unwrap();
+        assert_eq!(
+            master.derive_path(&path).unwrap().to_string(),
+            "xprv9z4pot5VBttmtdRTWfWQmoH1taj2axGVzFqSb8C9xaxKymcFzXBDptWmT7FwuEzG3ryjH4ktypQSAewRiNMjANTtpgP4mLTj34bhnZX7UiM"
+        );
+        assert_eq!(
+            master.derive_path(&DerivationPath::master()).unwrap(),
+            master
+        );
+
+        let xpub = ExtendedPubKey::from_priv(&master);
+        let path = "m/1/2/3".parse().unwrap();
+        assert_eq!(
+            xpub.derive_path(&path).unwrap(),
+            ExtendedPubKey::from_priv(&master.derive_path(&path).unwrap())
+        );
+        assert_eq!(
+            xpub.derive_path(&"m/1/2'".parse().unwrap()),
+            Err(HdError::HardenedFromPublic)
+        );
+    }
+}
@@ -0,0 +1,92 @@
+//! Hex encoding and decoding of byte strings.
+
+use crate::error::HexError;
+
+const DIGITS: &[u8; 16] = b"0123456789abcdef";
+
+/// Lowercase hex of `bytes`.
+pub fn encode(bytes: impl AsRef<[u8]>) -> String {
+    let bytes = bytes.as_ref();
+    let mut ret = String::with_capacity(bytes.len() * 2);
+    for &b in bytes {
+        ret.push(DIGITS[(b >> 4) as usize] as char);
+        ret.push(DIGITS[(b & 0xf) as usize] as char);
+    }
+    ret
+}
+
+/// Decodes hex in either case; the error points at the first offending
+/// character.
+pub fn decode(s: &str) -> Result<Vec<u8>, HexError> {
+    let digits = s
+        .char_indices()
+        .map(|(index, character)| {
+            character
+                .to_digit(16)
+                .map(|d| d as u8)
+                .ok_or(HexError::InvalidCharacter { character, index })
+        })
+        .collect::<Result<Vec<_>, _>>()?;
+    if digits.len() % 2 != 0 {
+        return Err(HexError::OddLength);
+    }
+    Ok(digits
+        .chunks(2)
+        .map(|pair| pair[0] << 4 | pair[1])
+        .collect())
+}
+
+/// Decodes hex of exactly `N` bytes.
+pub fn decode_array<const N: usize>(s: &str) -> Result<[u8; N], HexError> {
+    let bytes = decode(s)?;
+    bytes
+        .try_into()
+        .map_err(|bytes: Vec<u8>| HexError::InvalidLength {
+            expected: N,
+            actual: bytes.len(),
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{decode, decode_array, encode};
+    use crate::error::HexError;
+
+    #[test]
+    fn round_trip() {
+        let bytes: Vec<u8> = (0..=255).collect();
+        assert_eq!(decode(&encode(&bytes)).unwrap(), bytes);
+        assert_eq!(encode([0xde, 0xad, 0xbe, 0xef]), "deadbeef");
+        assert_eq!(decode("DeadBEEF").unwrap(), [0xde, 0xad, 0xbe, 0xef]);
+        assert_eq!(encode([]), "");
+        assert_eq!(decode("").unwrap(), Vec::<u8>::new());
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(decode("abc"), Err(HexError::OddLength));
+        assert_eq!(
+            decode("00zz"),
+            Err(HexError::InvalidCharacter {
+                character: 'z',
+                index: 2
+            })
+        );
+        // 位置は文字ではなくバイト単位
+        assert_eq!(
+            decode("é0g"),
+            Err(HexError::InvalidCharacter {
+                character: 'é',
+                index: 0
+            })
+        );
+        assert_eq!(
+            decode_array::<4>("0011"),
+            Err(HexError::InvalidLength {
+                expected: 4,
+                actual: 2
+            })
+        );
+        assert_eq!(decode_array::<2>("0011").unwrap(), [0x00, 0x11]);
+    }
+}
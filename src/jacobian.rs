@@ -0,0 +1,926 @@
+use std::ops::{Add, Div, Mul, Neg, Sub};
+
+use primitive_types::{U256, U512};
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::elliptic::Point;
+use crate::field_element::One;
+
+/// A point in Jacobian projective coordinates `(X, Y, Z)`, standing for the
+/// affine point `(X / Z^2, Y / Z^3)`.
+///
+/// Addition and doubling need no field inversion in this form; the single
+/// inversion is deferred to `to_affine`. This makes it the representation
+/// of choice for long chains of group operations such as scalar
+/// multiplication.
+#[derive(Clone, Debug)]
+pub enum JacobianPoint<T> {
+    Coordinate { x: T, y: T, z: T, a: T, b: T },
+    Infinity,
+}
+
+// 2v == v となるのは v == 0 のときだけ
+fn is_zero<T>(v: &T) -> bool
+where
+    T: Add<Output = T> + PartialEq + Clone,
+{
+    v.clone() + v.clone() == *v
+}
+
+impl<T> JacobianPoint<T>
+where
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + One + Clone,
+{
+    /// Lifts an affine point with `Z = 1`.
+    pub fn from_affine(point: &Point<T>) -> Self {
+        match point {
+            Point::Coordinate { x, y, a, b } => JacobianPoint::Coordinate {
+                x: x.clone(),
+                y: y.clone(),
+                z: x.one(),
+                a: a.clone(),
+                b: b.clone(),
+            },
+            Point::Infinity => JacobianPoint::Infinity,
+        }
+    }
+
+    /// dbl-2007-bl (一般の a に対応)
+    pub fn double(&self) -> Self {
+        match self {
+            JacobianPoint::Coordinate { x, y, z, a, b } => {
+                if is_zero(y) {
+                    return JacobianPoint::Infinity;
+                }
+
+                let xx = x.clone() * x.clone();
+                let yy = y.clone() * y.clone();
+                let yyyy = yy.clone() * yy.clone();
+                let zz = z.clone() * z.clone();
+
+                // S = 4 * X * Y^2
+                let s = x.clone() * yy;
+                let s = s.clone() + s;
+                let s = s.clone() + s;
+                // M = 3 * X^2 + a * Z^4
+                let m = xx.clone() + xx.clone() + xx + a.clone() * zz.clone() * zz;
+
+                let x3 = m.clone() * m.clone() - s.clone() - s.clone();
+                let eight_yyyy = yyyy.clone() + yyyy;
+                let eight_yyyy = eight_yyyy.clone() + eight_yyyy;
+                let eight_yyyy = eight_yyyy.clone() + eight_yyyy;
+                let y3 = m * (s - x3.clone()) - eight_yyyy;
+                let z3 = (y.clone() + y.clone()) * z.clone();
+
+                JacobianPoint::Coordinate {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                    a: a.clone(),
+                    b: b.clone(),
+                }
+            }
+            JacobianPoint::Infinity => JacobianPoint::Infinity,
+        }
+    }
+}
+
+impl<T> JacobianPoint<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    /// Converts back to affine coordinates, paying the one field inversion.
+    pub fn to_affine(&self) -> Point<T> {
+        match self {
+            JacobianPoint::Coordinate { x, y, z, a, b } => {
+                let z_inv = z.one() / z.clone();
+                let z_inv2 = z_inv.clone() * z_inv.clone();
+                Point::Coordinate {
+                    x: x.clone() * z_inv2.clone(),
+                    y: y.clone() * z_inv2 * z_inv,
+                    a: a.clone(),
+                    b: b.clone(),
+                }
+            }
+            JacobianPoint::Infinity => Point::Infinity,
+        }
+    }
+}
+
+impl<T> From<Point<T>> for JacobianPoint<T>
+where
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + One + Clone,
+{
+    fn from(point: Point<T>) -> Self {
+        Self::from_affine(&point)
+    }
+}
+
+impl<T> From<JacobianPoint<T>> for Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    fn from(point: JacobianPoint<T>) -> Self {
+        point.to_affine()
+    }
+}
+
+impl<T> Add for JacobianPoint<T>
+where
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + One + Clone,
+{
+    type Output = Self;
+
+    /// add-2007-bl
+    fn add(self, other: Self) -> Self::Output {
+        use JacobianPoint::*;
+        match (&self, &other) {
+            (
+                Coordinate {
+                    x: x1,
+                    y: y1,
+                    z: z1,
+                    a: a1,
+                    b: b1,
+                },
+                Coordinate {
+                    x: x2,
+                    y: y2,
+                    z: z2,
+                    a: a2,
+                    b: b2,
+                },
+            ) => {
+                if a1 != a2 || b1 != b2 {
+                    panic!("Points are not on the same curve.")
+                }
+
+                let z1z1 = z1.clone() * z1.clone();
+                let z2z2 = z2.clone() * z2.clone();
+                let u1 = x1.clone() * z2z2.clone();
+                let u2 = x2.clone() * z1z1.clone();
+                let s1 = y1.clone() * z2.clone() * z2z2;
+                let s2 = y2.clone() * z1.clone() * z1z1;
+
+                let h = u2 - u1.clone();
+                let r = s2 - s1.clone();
+                if is_zero(&h) {
+                    // x が同じ: 同じ点なら2倍、逆元同士なら無限遠点
+                    if is_zero(&r) {
+                        return self.double();
+                    }
+                    return Infinity;
+                }
+
+                let hh = h.clone() * h.clone();
+                let hhh = h.clone() * hh.clone();
+                let v = u1 * hh;
+
+                let x3 = r.clone() * r.clone() - hhh.clone() - v.clone() - v.clone();
+                let y3 = r * (v - x3.clone()) - s1 * hhh;
+                let z3 = z1.clone() * z2.clone() * h;
+                Coordinate {
+                    x: x3,
+                    y: y3,
+                    z: z3,
+                    a: a1.clone(),
+                    b: b1.clone(),
+                }
+            }
+            (Coordinate { .. }, Infinity) => self,
+            (Infinity, _) => other,
+        }
+    }
+}
+
+impl<T> Add<&JacobianPoint<T>> for JacobianPoint<T>
+where
+    JacobianPoint<T>: Add<Output = JacobianPoint<T>> + Clone,
+{
+    type Output = JacobianPoint<T>;
+
+    fn add(self, other: &JacobianPoint<T>) -> Self::Output {
+        self + other.clone()
+    }
+}
+
+impl<T> Neg for JacobianPoint<T>
+where
+    T: Neg<Output = T>,
+{
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        match self {
+            JacobianPoint::Coordinate { x, y, z, a, b } => {
+                JacobianPoint::Coordinate { x, y: -y, z, a, b }
+            }
+            JacobianPoint::Infinity => JacobianPoint::Infinity,
+        }
+    }
+}
+
+/// Width-`window` non-adjacent form of `scalar`, least significant digit
+/// first.
+///
+/// Every non-zero digit is odd with `|d| < 2^(window - 1)`, and any two
+/// non-zero digits are at least `window` positions apart.
+pub fn wnaf(scalar: U256, window: usize) -> Vec<i64> {
+    assert!(
+        (2..=16).contains(&window),
+        "wNAF window must be between 2 and 16"
+    );
+    let modulus = 1i64 << window;
+    let half = modulus >> 1;
+
+    // 負の桁を引くと k が増えるので 512 ビットで扱う
+    let mut k = U512::from(scalar);
+    let mut digits = Vec::with_capacity(257);
+    while !k.is_zero() {
+        let mut digit = 0;
+        if k.bit(0) {
+            digit = (k.low_u64() & (modulus as u64 - 1)) as i64;
+            if digit >= half {
+                digit -= modulus;
+            }
+            if digit > 0 {
+                k -= U512::from(digit as u64);
+            } else {
+                k += U512::from(digit.unsigned_abs());
+            }
+        }
+        digits.push(digit);
+        k >>= 1;
+    }
+    digits
+}
+
+/// Scalar multiplication with a width-`window` NAF, for public scalars.
+///
+/// Only the odd multiples `P, 3P, ..., (2^(window-1) - 1)P` are precomputed;
+/// negative digits reuse them through a free negation. On average one
+/// addition is needed per `window + 1` bits instead of one per two bits.
+pub(crate) fn wnaf_mul<T>(point: &Point<T>, scalar: U256, window: usize) -> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + One
+        + Clone,
+{
+    let digits = wnaf(scalar, window);
+    let table = odd_multiples(point, window);
+
+    let mut ret = JacobianPoint::Infinity;
+    for &digit in digits.iter().rev() {
+        ret = add_wnaf_digit(ret.double(), &table, digit);
+    }
+    ret.to_affine()
+}
+
+/// `[P, 3P, 5P, ..., (2^(window-1) - 1)P]`, the table a wNAF digit indexes.
+fn odd_multiples<T>(point: &Point<T>, window: usize) -> Vec<JacobianPoint<T>>
+where
+    T: PartialEq + Add<Output = T> + Sub<Output = T> + Mul<Output = T> + One + Clone,
+{
+    let base = JacobianPoint::from_affine(point);
+    let twice = base.double();
+    let mut table = vec![base];
+    for i in 1..(1 << (window - 2)) {
+        let next = table[i - 1].clone() + &twice;
+        table.push(next);
+    }
+    table
+}
+
+fn add_wnaf_digit<T>(
+    acc: JacobianPoint<T>,
+    table: &[JacobianPoint<T>],
+    digit: i64,
+) -> JacobianPoint<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Neg<Output = T>
+        + One
+        + Clone,
+{
+    match digit {
+        0 => acc,
+        d if d > 0 => acc + &table[(d as usize) / 2],
+        d => acc + -table[(d.unsigned_abs() as usize) / 2].clone(),
+    }
+}
+
+/// Shamir's trick: `k0 * P0 + k1 * P1` in a single pass over the bits.
+///
+/// With `P0 + P1` precomputed, each bit costs one doubling and at most one
+/// addition, instead of the two full scalar multiplications done separately.
+pub(crate) fn shamir_mul<T>(k0: U256, p0: &Point<T>, k1: U256, p1: &Point<T>) -> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    let p0 = JacobianPoint::from_affine(p0);
+    let p1 = JacobianPoint::from_affine(p1);
+    let both = p0.clone() + &p1;
+
+    let bits = k0.bits().max(k1.bits());
+    let mut ret = JacobianPoint::Infinity;
+    for i in (0..bits).rev() {
+        ret = ret.double();
+        ret = match (k0.bit(i), k1.bit(i)) {
+            (true, true) => ret + &both,
+            (true, false) => ret + &p0,
+            (false, true) => ret + &p1,
+            (false, false) => ret,
+        };
+    }
+    ret.to_affine()
+}
+
+/// Strauss interleaving: `sum(k_i * P_i)` sharing one chain of doublings
+/// across all terms, each scalar recoded in width-`window` NAF.
+pub(crate) fn strauss_mul<T>(terms: &[(U256, Point<T>)], window: usize) -> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + Neg<Output = T>
+        + One
+        + Clone,
+{
+    let digits: Vec<_> = terms.iter().map(|(k, _)| wnaf(*k, window)).collect();
+    let tables: Vec<_> = terms
+        .iter()
+        .map(|(_, point)| odd_multiples(point, window))
+        .collect();
+    let len = digits.iter().map(Vec::len).max().unwrap_or(0);
+
+    let mut ret = JacobianPoint::Infinity;
+    for i in (0..len).rev() {
+        ret = ret.double();
+        for (digits, table) in digits.iter().zip(&tables) {
+            if let Some(&digit) = digits.get(i) {
+                ret = add_wnaf_digit(ret, table, digit);
+            }
+        }
+    }
+    ret.to_affine()
+}
+
+/// Number of terms from which `Point::multi_mul` switches from Strauss
+/// interleaving to Pippenger's bucket method.
+pub const PIPPENGER_THRESHOLD: usize = 64;
+
+/// Pippenger's bucket method for `sum(k_i * P_i)` over many terms.
+///
+/// Scalars are cut into `c`-bit windows. Within a window every point is
+/// added once into the bucket of its digit, and the buckets are combined by
+/// a running sum, so the cost per window is about `n + 2^(c+1)` additions
+/// regardless of how the digits are distributed.
+pub(crate) fn pippenger_mul<T>(terms: &[(U256, Point<T>)]) -> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    // 窓幅は項数の対数程度が最適
+    let c = (terms.len().max(1).ilog2() as usize)
+        .saturating_sub(1)
+        .clamp(2, 16);
+    let points: Vec<_> = terms
+        .iter()
+        .map(|(_, point)| JacobianPoint::from_affine(point))
+        .collect();
+    let bits = terms.iter().map(|(k, _)| k.bits()).max().unwrap_or(0);
+    let windows = bits.div_ceil(c);
+
+    let mut ret = JacobianPoint::Infinity;
+    for w in (0..windows).rev() {
+        for _ in 0..c {
+            ret = ret.double();
+        }
+
+        let mut buckets = vec![JacobianPoint::Infinity; (1 << c) - 1];
+        for ((k, _), point) in terms.iter().zip(&points) {
+            let digit = ((*k >> (w * c)).low_u64() & ((1 << c) - 1)) as usize;
+            if digit != 0 {
+                let bucket = std::mem::replace(&mut buckets[digit - 1], JacobianPoint::Infinity);
+                buckets[digit - 1] = bucket + point;
+            }
+        }
+
+        // sum(j * B_j) を累積和2本で求める
+        let mut running = JacobianPoint::Infinity;
+        let mut total = JacobianPoint::Infinity;
+        for bucket in buckets.into_iter().rev() {
+            running = running + bucket;
+            total = total + &running;
+        }
+        ret = ret + total;
+    }
+    ret.to_affine()
+}
+
+/// Jacobian point for the constant-time ladder: the point at infinity is a
+/// flag instead of an enum variant, so selecting between two points never
+/// branches on which one is infinity.
+#[derive(Clone)]
+struct LadderPoint<T> {
+    x: T,
+    y: T,
+    z: T,
+    infinity: Choice,
+}
+
+impl<T> ConditionallySelectable for LadderPoint<T>
+where
+    T: ConditionallySelectable,
+{
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self {
+            x: T::conditional_select(&a.x, &b.x, choice),
+            y: T::conditional_select(&a.y, &b.y, choice),
+            z: T::conditional_select(&a.z, &b.z, choice),
+            infinity: Choice::conditional_select(&a.infinity, &b.infinity, choice),
+        }
+    }
+}
+
+impl<T> LadderPoint<T>
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + One
+        + ConstantTimeEq
+        + ConditionallySelectable
+        + Clone,
+{
+    fn infinity(one: T) -> Self {
+        Self {
+            x: one.clone(),
+            y: one.clone(),
+            z: one.clone() - one,
+            infinity: Choice::from(1),
+        }
+    }
+
+    fn double(&self, a: &T) -> Self {
+        let zero = self.x.one() - self.x.one();
+
+        let xx = self.x.clone() * self.x.clone();
+        let yy = self.y.clone() * self.y.clone();
+        let yyyy = yy.clone() * yy.clone();
+        let zz = self.z.clone() * self.z.clone();
+
+        let s = self.x.clone() * yy;
+        let s = s.clone() + s;
+        let s = s.clone() + s;
+        let m = xx.clone() + xx.clone() + xx + a.clone() * zz.clone() * zz;
+
+        let x3 = m.clone() * m.clone() - s.clone() - s.clone();
+        let eight_yyyy = yyyy.clone() + yyyy;
+        let eight_yyyy = eight_yyyy.clone() + eight_yyyy;
+        let eight_yyyy = eight_yyyy.clone() + eight_yyyy;
+        let y3 = m * (s - x3.clone()) - eight_yyyy;
+        let z3 = (self.y.clone() + self.y.clone()) * self.z.clone();
+
+        Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            infinity: self.infinity | self.y.ct_eq(&zero),
+        }
+    }
+
+    /// Every case of the group law is computed and the right one picked with
+    /// `conditional_select`, so the work done is independent of the inputs.
+    fn add(&self, other: &Self, a: &T) -> Self {
+        let zero = self.x.one() - self.x.one();
+
+        let z1z1 = self.z.clone() * self.z.clone();
+        let z2z2 = other.z.clone() * other.z.clone();
+        let u1 = self.x.clone() * z2z2.clone();
+        let u2 = other.x.clone() * z1z1.clone();
+        let s1 = self.y.clone() * other.z.clone() * z2z2;
+        let s2 = other.y.clone() * self.z.clone() * z1z1;
+
+        let h = u2 - u1.clone();
+        let r = s2 - s1.clone();
+        let hh = h.clone() * h.clone();
+        let hhh = h.clone() * hh.clone();
+        let v = u1 * hh;
+
+        let x3 = r.clone() * r.clone() - hhh.clone() - v.clone() - v.clone();
+        let y3 = r.clone() * (v - x3.clone()) - s1 * hhh;
+        let z3 = self.z.clone() * other.z.clone() * h.clone();
+
+        let h_is_zero = h.ct_eq(&zero);
+        let r_is_zero = r.ct_eq(&zero);
+
+        let mut ret = Self {
+            x: x3,
+            y: y3,
+            z: z3,
+            infinity: Choice::from(0),
+        };
+        // 同じ点なら2倍、逆元同士なら無限遠点
+        ret = Self::conditional_select(&ret, &self.double(a), h_is_zero & r_is_zero);
+        ret.infinity = ret.infinity | (h_is_zero & !r_is_zero);
+        // どちらかが無限遠点ならもう一方を返す
+        ret = Self::conditional_select(&ret, other, self.infinity);
+        Self::conditional_select(&ret, self, other.infinity)
+    }
+}
+
+impl<T> LadderPoint<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + Clone,
+{
+    fn to_affine(&self, a: &T, b: &T) -> Point<T> {
+        if bool::from(self.infinity) {
+            return Point::Infinity;
+        }
+        JacobianPoint::Coordinate {
+            x: self.x.clone(),
+            y: self.y.clone(),
+            z: self.z.clone(),
+            a: a.clone(),
+            b: b.clone(),
+        }
+        .to_affine()
+    }
+}
+
+/// Constant-time scalar multiplication by a Montgomery ladder.
+///
+/// Always walks all 256 bits of `scalar` and performs exactly one addition
+/// and one doubling per bit; the bit only decides a `conditional_swap`. The
+/// running time is therefore independent of the scalar as long as the field
+/// arithmetic of `T` is itself constant time, as it is for `S256Field`.
+/// The generic `FieldElement` branches on values in `+` and `-`.
+pub(crate) fn ladder_mul<T>(point: &Point<T>, scalar: &U256) -> Point<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + ConstantTimeEq
+        + ConditionallySelectable
+        + Clone,
+{
+    let (x, y, a, b) = match point {
+        Point::Coordinate { x, y, a, b } => (x, y, a, b),
+        Point::Infinity => return Point::Infinity,
+    };
+    let mut r0 = LadderPoint::infinity(x.one());
+    let mut r1 = LadderPoint {
+        x: x.clone(),
+        y: y.clone(),
+        z: x.one(),
+        infinity: Choice::from(0),
+    };
+
+    // 不変条件: r1 = r0 + P
+    for i in (0..256).rev() {
+        let bit = Choice::from(((scalar.0[i / 64] >> (i % 64)) & 1) as u8);
+        LadderPoint::conditional_swap(&mut r0, &mut r1, bit);
+        r1 = r0.add(&r1, a);
+        r0 = r0.double(a);
+        LadderPoint::conditional_swap(&mut r0, &mut r1, bit);
+    }
+
+    r0.to_affine(a, b)
+}
+
+/// `j * 16^i * P` for every 4-bit window `i` of a 256-bit scalar and every
+/// digit `j`, so multiplying the fixed base `P` needs 64 additions and no
+/// doublings.
+///
+/// Lookups scan the whole row with `conditional_select` and additions use
+/// the constant-time group law, so `mul` is as safe for secret scalars as
+/// the ladder.
+pub struct FixedBaseTable<T> {
+    rows: Vec<Vec<LadderPoint<T>>>,
+    a: T,
+    b: T,
+}
+
+impl<T> FixedBaseTable<T>
+where
+    T: PartialEq
+        + Add<Output = T>
+        + Sub<Output = T>
+        + Mul<Output = T>
+        + Div<Output = T>
+        + One
+        + ConstantTimeEq
+        + ConditionallySelectable
+        + Clone,
+{
+    pub fn new(point: &Point<T>) -> Self {
+        let (x, y, a, b) = match point {
+            Point::Coordinate { x, y, a, b } => (x, y, a, b),
+            Point::Infinity => panic!("The base point must not be the point at infinity."),
+        };
+
+        let mut base = LadderPoint {
+            x: x.clone(),
+            y: y.clone(),
+            z: x.one(),
+            infinity: Choice::from(0),
+        };
+        let mut rows = Vec::with_capacity(64);
+        for _ in 0..64 {
+            let mut row = Vec::with_capacity(16);
+            row.push(LadderPoint::infinity(x.one()));
+            for j in 1..16 {
+                let next = row[j - 1].add(&base, a);
+                row.push(next);
+            }
+            // 次の行の基点は 16 倍
+            base = row[15].add(&base, a);
+            rows.push(row);
+        }
+
+        Self {
+            rows,
+            a: a.clone(),
+            b: b.clone(),
+        }
+    }
+
+    pub fn mul(&self, scalar: &U256) -> Point<T> {
+        let mut ret = LadderPoint::infinity(self.a.one());
+        for (i, row) in self.rows.iter().enumerate() {
+            let digit = (scalar.0[i / 16] >> ((i % 16) * 4)) & 0xF;
+
+            // 秘密の桁で添字アクセスしないよう行全体を走査する
+            let mut entry = row[0].clone();
+            for (j, candidate) in row.iter().enumerate().skip(1) {
+                entry =
+                    LadderPoint::conditional_select(&entry, candidate, digit.ct_eq(&(j as u64)));
+            }
+            ret = ret.add(&entry, &self.a);
+        }
+        ret.to_affine(&self.a, &self.b)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        ladder_mul, pippenger_mul, shamir_mul, strauss_mul, wnaf, wnaf_mul, FixedBaseTable,
+        JacobianPoint,
+    };
+    use crate::elliptic::Point;
+    use crate::field_element::FieldElement;
+    use crate::fp::Fp;
+    use primitive_types::{U256, U512};
+
+    type F223 = Fp<223>;
+
+    fn point(x: u64, y: u64) -> Point<F223> {
+        Point::new(F223::new(x), F223::new(y), F223::new(0), F223::new(7))
+    }
+
+    #[test]
+    fn round_trip() {
+        let p = point(192, 105);
+        assert_eq!(JacobianPoint::from_affine(&p).to_affine(), p);
+        assert_eq!(
+            JacobianPoint::<F223>::from(Point::Infinity).to_affine(),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn add_matches_affine() {
+        let cases = [
+            ((192, 105), (17, 56)),
+            ((170, 142), (60, 139)),
+            ((47, 71), (17, 56)),
+            ((143, 98), (76, 66)),
+        ];
+        for ((x0, y0), (x1, y1)) in cases {
+            let p0 = point(x0, y0);
+            let p1 = point(x1, y1);
+            let sum = JacobianPoint::from_affine(&p0) + JacobianPoint::from_affine(&p1);
+            assert_eq!(sum.to_affine(), p0 + p1);
+        }
+    }
+
+    #[test]
+    fn add_special_cases() {
+        let p = point(192, 105);
+        let j = JacobianPoint::from_affine(&p);
+
+        // P + P は2倍、P + (-P) は無限遠点
+        assert_eq!((j.clone() + &j).to_affine(), p.double());
+        assert_eq!(
+            (j.clone() + JacobianPoint::from_affine(&-p.clone())).to_affine(),
+            Point::Infinity
+        );
+        assert_eq!((JacobianPoint::Infinity + &j).to_affine(), p.clone(),);
+        assert_eq!((j + JacobianPoint::Infinity).to_affine(), p);
+    }
+
+    #[test]
+    fn double_matches_affine() {
+        let p = point(47, 71);
+        let mut j = JacobianPoint::from_affine(&p);
+        let mut affine = p;
+        for _ in 0..10 {
+            j = j.double();
+            affine = affine.double();
+            assert_eq!(j.to_affine(), affine);
+        }
+    }
+
+    #[test]
+    fn double_with_nonzero_a() {
+        let prime = U256::from(223);
+        let fe = |n: u64| FieldElement::new(U256::from(n), prime);
+        // y^2 = x^3 + 5x + 7 上の点を探す
+        let p = (1..223u64)
+            .flat_map(|x| (1..223u64).map(move |y| (x, y)))
+            .find_map(|(x, y)| Point::try_new(fe(x), fe(y), fe(5), fe(7)).ok())
+            .unwrap();
+
+        let j = JacobianPoint::from_affine(&p);
+        assert_eq!(j.double().to_affine(), p.double());
+        assert_eq!((j.double() + &j).to_affine(), p.double() + p.clone());
+    }
+
+    #[test]
+    fn ladder_matches_double_and_add() {
+        let g = point(47, 71);
+        for k in 0..=22u64 {
+            assert_eq!(ladder_mul(&g, &U256::from(k)), g.clone() * k);
+        }
+        assert_eq!(
+            ladder_mul(&g, &U256::MAX),
+            g.clone() * (U256::MAX % U256::from(21))
+        );
+        assert_eq!(
+            ladder_mul(&Point::<F223>::Infinity, &U256::from(5)),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn wnaf_digits() {
+        // 7 = 8 - 1
+        assert_eq!(wnaf(U256::from(7), 2), vec![-1, 0, 0, 1]);
+        assert_eq!(wnaf(U256::zero(), 4), Vec::<i64>::new());
+
+        for window in 2..=6 {
+            let scalar = U256::MAX - U256::from(12345);
+            let digits = wnaf(scalar, window);
+
+            // 桁から元の値を復元できる
+            let mut value = U512::zero();
+            for &d in digits.iter().rev() {
+                value <<= 1;
+                if d > 0 {
+                    value += U512::from(d as u64);
+                } else {
+                    value -= U512::from(d.unsigned_abs());
+                }
+            }
+            assert_eq!(value, U512::from(scalar));
+
+            // 非ゼロの桁は奇数で、window 桁以上離れている
+            let nonzero: Vec<_> = digits.iter().enumerate().filter(|(_, &d)| d != 0).collect();
+            for (_, &d) in &nonzero {
+                assert_eq!(d.abs() % 2, 1);
+                assert!(d.abs() < 1 << (window - 1));
+            }
+            for pair in nonzero.windows(2) {
+                assert!(pair[1].0 - pair[0].0 >= window);
+            }
+        }
+    }
+
+    #[test]
+    fn wnaf_mul_matches_double_and_add() {
+        let g = point(47, 71);
+        for window in 2..=5 {
+            for k in 0..=43u64 {
+                assert_eq!(wnaf_mul(&g, U256::from(k), window), g.clone() * k);
+            }
+        }
+        assert_eq!(
+            wnaf_mul(&g, U256::MAX, 4),
+            g.clone() * (U256::MAX % U256::from(21))
+        );
+    }
+
+    #[test]
+    fn fixed_base_table() {
+        let g = point(47, 71);
+        let table = FixedBaseTable::new(&g);
+
+        for k in 0..=43u64 {
+            assert_eq!(table.mul(&U256::from(k)), g.clone() * k);
+        }
+        assert_eq!(
+            table.mul(&U256::MAX),
+            g.clone() * (U256::MAX % U256::from(21))
+        );
+    }
+
+    #[test]
+    fn shamir_and_strauss() {
+        let g = point(47, 71);
+        let q = point(192, 105);
+        let r = point(17, 56);
+
+        for (k0, k1) in [
+            (0u64, 0u64),
+            (1, 0),
+            (0, 5),
+            (3, 4),
+            (20, 13),
+            (1_000_003, 77),
+        ] {
+            let expected = g.clone() * k0 + q.clone() * k1;
+            assert_eq!(shamir_mul(U256::from(k0), &g, U256::from(k1), &q), expected);
+            assert_eq!(
+                strauss_mul(
+                    &[(U256::from(k0), g.clone()), (U256::from(k1), q.clone())],
+                    4
+                ),
+                expected
+            );
+        }
+
+        let terms = [
+            (U256::from(12), g.clone()),
+            (U256::from(99), q.clone()),
+            (U256::MAX, r.clone()),
+        ];
+        let expected = g.clone() * 12 + q.clone() * 99 + r * U256::MAX;
+        assert_eq!(strauss_mul(&terms, 3), expected);
+        assert_eq!(strauss_mul::<F223>(&[], 4), Point::Infinity);
+        // P と -P の組み合わせ
+        assert_eq!(
+            shamir_mul(U256::from(5), &g, U256::from(5), &-g.clone()),
+            Point::Infinity
+        );
+    }
+
+    #[test]
+    fn pippenger_matches_strauss() {
+        let g = point(47, 71);
+        let q = point(192, 105);
+
+        for n in [0, 1, 2, 5, 70] {
+            let terms: Vec<_> = (0..n as u64)
+                .map(|i| {
+                    let base = if i % 2 == 0 { g.clone() } else { q.clone() };
+                    (U256::from(i * 7919 + 3) << (i as usize % 200), base)
+                })
+                .collect();
+            assert_eq!(pippenger_mul(&terms), strauss_mul(&terms, 4));
+        }
+    }
+}
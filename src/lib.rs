@@ -1,4 +1,51 @@
 extern crate core;
 
-mod elliptic;
-mod field_element;
+mod macros;
+
+pub mod adaptor;
+pub mod address;
+pub mod base58;
+pub mod base64;
+pub mod bech32;
+pub mod bip322;
+pub mod bip39;
+pub mod ct;
+pub mod curve;
+pub mod ecdh;
+pub mod elliptic;
+pub mod encoding;
+pub mod error;
+pub mod field_element;
+pub mod fp;
+pub mod frost;
+pub mod hash;
+pub mod hash_types;
+pub mod hd;
+pub mod hex;
+pub mod jacobian;
+pub mod message;
+pub mod modular;
+pub mod montgomery;
+pub mod musig;
+pub mod network;
+pub mod payment_uri;
+pub mod private_key;
+pub mod random;
+pub mod s256_field;
+pub mod s256_point;
+pub mod scalar;
+pub mod schnorr;
+pub mod script;
+pub mod secp256k1;
+pub mod secret;
+#[cfg(feature = "serde")]
+mod serde_hex;
+pub mod shamir;
+pub mod signature;
+#[cfg(any(test, feature = "proptest"))]
+pub mod strategy;
+pub mod taproot;
+pub mod tx;
+#[cfg(feature = "rayon")]
+pub mod vanity;
+pub mod xonly;
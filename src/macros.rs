@@ -0,0 +1,77 @@
+/// Implements a binary operator for every mix of owned and borrowed operands
+/// by forwarding to the owned `impl`, so `&a + &b`, `a + &b` and `&a + b`
+/// all behave like `a + b`.
+///
+/// Types whose parameter carries a bound are written `for Type<T: Bound>`.
+macro_rules! forward_ref_binop {
+    (impl $imp:ident, $method:ident for $t:ident) => {
+        $crate::macros::forward_ref_binop!(impl $imp, $method for $t<T: Sized>);
+    };
+    (impl $imp:ident, $method:ident for $t:ident<T: $bound:path>) => {
+        impl<'a, 'b, T: $bound> $imp<&'b $t<T>> for &'a $t<T>
+        where
+            $t<T>: $imp<Output = $t<T>> + Clone,
+        {
+            type Output = $t<T>;
+
+            fn $method(self, other: &'b $t<T>) -> Self::Output {
+                $imp::$method(self.clone(), other.clone())
+            }
+        }
+
+        impl<'b, T: $bound> $imp<&'b $t<T>> for $t<T>
+        where
+            $t<T>: $imp<Output = $t<T>> + Clone,
+        {
+            type Output = $t<T>;
+
+            fn $method(self, other: &'b $t<T>) -> Self::Output {
+                $imp::$method(self, other.clone())
+            }
+        }
+
+        impl<'a, T: $bound> $imp<$t<T>> for &'a $t<T>
+        where
+            $t<T>: $imp<Output = $t<T>> + Clone,
+        {
+            type Output = $t<T>;
+
+            fn $method(self, other: $t<T>) -> Self::Output {
+                $imp::$method(self.clone(), other)
+            }
+        }
+    };
+}
+
+pub(crate) use forward_ref_binop;
+
+/// Implements a compound assignment operator (`+=`, ...) for both owned and
+/// borrowed right-hand sides on top of the corresponding binary operator.
+macro_rules! forward_op_assign {
+    (impl $imp:ident, $method:ident, $bin_imp:ident, $bin_method:ident for $t:ident) => {
+        $crate::macros::forward_op_assign!(
+            impl $imp, $method, $bin_imp, $bin_method for $t<T: Sized>
+        );
+    };
+    (impl $imp:ident, $method:ident, $bin_imp:ident, $bin_method:ident for $t:ident<T: $bound:path>) => {
+        impl<T: $bound> $imp for $t<T>
+        where
+            $t<T>: $bin_imp<Output = $t<T>> + Clone,
+        {
+            fn $method(&mut self, other: $t<T>) {
+                *self = $bin_imp::$bin_method(self.clone(), other);
+            }
+        }
+
+        impl<'b, T: $bound> $imp<&'b $t<T>> for $t<T>
+        where
+            $t<T>: $bin_imp<Output = $t<T>> + Clone,
+        {
+            fn $method(&mut self, other: &'b $t<T>) {
+                *self = $bin_imp::$bin_method(self.clone(), other.clone());
+            }
+        }
+    };
+}
+
+pub(crate) use forward_op_assign;
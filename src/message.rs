@@ -0,0 +1,137 @@
+use primitive_types::U256;
+
+use crate::base58::decode_base58check;
+use crate::base64::{decode_base64, encode_base64};
+use crate::encoding::encode_varint;
+use crate::hash::hash256;
+use crate::network::Network;
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::signature::Signature;
+
+const MAGIC: &[u8] = b"Bitcoin Signed Message:\n";
+
+/// The hash that signed messages commit to:
+/// `hash256(len || "Bitcoin Signed Message:\n" || len || msg)`.
+///
+/// The prefix keeps a message signature from ever being a valid
+/// transaction signature.
+pub fn message_hash(msg: &[u8]) -> [u8; 32] {
+    let mut data = encode_varint(MAGIC.len() as u64);
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&encode_varint(msg.len() as u64));
+    data.extend_from_slice(msg);
+    hash256(&data)
+}
+
+/// Signs `msg` in the format of Bitcoin Core's `signmessage`: Base64 of a
+/// header byte followed by `r` and `s`, for the compressed P2PKH address of
+/// `key`.
+///
+/// The header is `27 + recovery id + 4`, the `4` marking a compressed key,
+/// so the verifier can recover the public key from the signature alone.
+pub fn sign_message(key: &PrivateKey, msg: &str) -> String {
+    let z = U256::from_big_endian(&message_hash(msg.as_bytes()));
+    let (sig, recovery_id) = key.sign_recoverable(z);
+
+    let mut bytes = [0u8; 65];
+    bytes[0] = 27 + 4 + recovery_id;
+    bytes[1..].copy_from_slice(&sig.serialize_compact());
+    encode_base64(&bytes)
+}
+
+/// Checks a `sign_message` signature against a P2PKH address, by
+/// recovering the public key and comparing its address.
+pub fn verify_message(address: &str, signature: &str, msg: &str) -> bool {
+    let network = match decode_base58check(address) {
+        Ok(payload) if payload.len() == 21 => match Network::from_p2pkh_prefix(payload[0]) {
+            Some(network) => network,
+            None => return false,
+        },
+        _ => return false,
+    };
+    let bytes = match decode_base64(signature) {
+        Ok(bytes) if bytes.len() == 65 && (27..=34).contains(&bytes[0]) => bytes,
+        _ => return false,
+    };
+
+    let compressed = bytes[0] >= 31;
+    let recovery_id = (bytes[0] - 27) & 3;
+    let sig = match Signature::parse_compact(&bytes[1..]) {
+        Ok(sig) => sig,
+        Err(_) => return false,
+    };
+    let z = U256::from_big_endian(&message_hash(msg.as_bytes()));
+
+    match S256Point::recover_pubkey(z, &sig, recovery_id) {
+        Some(point) => point.address(compressed, network) == address,
+        None => false,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{message_hash, sign_message, verify_message};
+    use crate::base64::{decode_base64, encode_base64};
+    use crate::network::Network;
+    use crate::private_key::PrivateKey;
+    use crate::scalar::Scalar;
+
+    #[test]
+    fn message_hash_uses_magic_prefix() {
+        use crate::hash::hash256;
+
+        let mut data = vec![24];
+        data.extend_from_slice(b"Bitcoin Signed Message:\n");
+        data.push(5);
+        data.extend_from_slice(b"hello");
+        assert_eq!(message_hash(b"hello"), hash256(&data));
+    }
+
+    #[test]
+    fn sign_and_verify() {
+        let key = PrivateKey::new(Scalar::from(0x12345deadbeefu64));
+        let address = key.point().address(true, Network::Mainnet);
+        assert_eq!(address, "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1");
+
+        for msg in ["", "Programming Bitcoin", "日本語のメッセージ"] {
+            let sig = sign_message(&key, msg);
+            assert!(verify_message(&address, &sig, msg));
+            assert!(!verify_message(&address, &sig, "another message"));
+        }
+
+        // 同じ鍵でも別ネットワークや非圧縮のアドレスとは一致しない
+        let sig = sign_message(&key, "hello");
+        assert!(verify_message(
+            &key.point().address(true, Network::Testnet),
+            &sig,
+            "hello"
+        ));
+        assert!(!verify_message(
+            &key.point().address(false, Network::Mainnet),
+            &sig,
+            "hello"
+        ));
+        let other = PrivateKey::new(Scalar::from(7)).point();
+        assert!(!verify_message(
+            &other.address(true, Network::Mainnet),
+            &sig,
+            "hello"
+        ));
+    }
+
+    #[test]
+    fn malformed_input() {
+        let key = PrivateKey::new(Scalar::from(7));
+        let address = key.point().address(true, Network::Mainnet);
+        let sig = sign_message(&key, "hello");
+
+        assert!(!verify_message("not an address", &sig, "hello"));
+        assert!(!verify_message(&address, "not base64!", "hello"));
+        assert!(!verify_message(&address, &sig[..sig.len() - 4], "hello"));
+
+        let mut bytes = decode_base64(&sig).unwrap();
+        bytes[0] = 26;
+        assert!(!verify_message(&address, &encode_base64(&bytes), "hello"));
+    }
+}
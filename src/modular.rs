@@ -0,0 +1,370 @@
+use primitive_types::{U256, U512};
+use std::ops::{Add, Div, Rem, Sub};
+
+use crate::ct::{Choice, ConditionallySelectable};
+use crate::secp256k1;
+
+/// Modular multiplication that never overflows the backing integer type.
+///
+/// `FieldElement` multiplication is defined in terms of this trait so every
+/// backend can pick the cheapest correct strategy: widen into a type twice
+/// as large when one exists, or fall back to double-and-add otherwise.
+pub trait MulMod: Sized {
+    /// Returns `(self * rhs) % modulus`.
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self;
+
+    /// Returns `(self * self) % modulus`. Backends with a cheaper squaring
+    /// than general multiplication override this.
+    fn square_mod(self, modulus: Self) -> Self
+    where
+        Self: Clone,
+    {
+        self.clone().mul_mod(self, modulus)
+    }
+}
+
+// 二倍幅の型に拡張してから剰余を取る
+macro_rules! impl_mul_mod_widening {
+    ($($t:ty => $wide:ty),*) => {
+        $(
+            impl MulMod for $t {
+                fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+                    ((self as $wide * rhs as $wide) % modulus as $wide) as $t
+                }
+            }
+        )*
+    };
+}
+
+impl_mul_mod_widening!(u8 => u16, u16 => u32, u32 => u64, u64 => u128);
+
+impl MulMod for U256 {
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+        reduce_u256_product(self.full_mul(rhs), modulus)
+    }
+
+    fn square_mod(self, modulus: Self) -> Self {
+        reduce_u256_product(square_u256(self), modulus)
+    }
+}
+
+fn reduce_u256_product(wide: U512, modulus: U256) -> U256 {
+    if modulus == secp256k1::P {
+        return reduce_secp256k1(wide);
+    }
+    U256::try_from(wide % U512::from(modulus)).expect("remainder is smaller than the modulus")
+}
+
+/// Full 512-bit square of a 256-bit value.
+///
+/// Each cross product `a[i] * a[j]` (i < j) appears twice in the square, so
+/// it is computed once and the sum doubled with a shift: 10 limb products
+/// instead of the 16 a general multiplication needs.
+pub fn square_u256(a: U256) -> U512 {
+    let limbs = a.0;
+    let mut ret = [0u64; 8];
+
+    // 交差項 a[i] * a[j] (i < j)
+    for i in 0..4 {
+        let mut carry = 0u128;
+        for j in (i + 1)..4 {
+            let t = ret[i + j] as u128 + limbs[i] as u128 * limbs[j] as u128 + carry;
+            ret[i + j] = t as u64;
+            carry = t >> 64;
+        }
+        ret[i + 4] = carry as u64;
+    }
+
+    // 交差項を2倍する
+    let mut high_bit = 0u64;
+    for limb in ret.iter_mut() {
+        let next = *limb >> 63;
+        *limb = (*limb << 1) | high_bit;
+        high_bit = next;
+    }
+
+    // 対角項 a[i]^2 を足す
+    let mut carry = 0u128;
+    for i in 0..4 {
+        let square = limbs[i] as u128 * limbs[i] as u128;
+        let low = ret[2 * i] as u128 + (square as u64) as u128 + carry;
+        ret[2 * i] = low as u64;
+        let high = ret[2 * i + 1] as u128 + (square >> 64) + (low >> 64);
+        ret[2 * i + 1] = high as u64;
+        carry = high >> 64;
+    }
+
+    U512(ret)
+}
+
+/// Reduces a 512-bit value modulo the secp256k1 prime without division.
+///
+/// Because `p = 2^256 - c` with `c = 2^32 + 977`, the high half can be folded
+/// back in as `hi * 2^256 ≡ hi * c (mod p)`. Three folds always suffice, so
+/// all of them are done regardless of the value, and the final subtraction
+/// of `p` is a mask: the running time does not depend on `wide`.
+pub fn reduce_secp256k1(wide: U512) -> U256 {
+    const C: u64 = 0x1_0000_03D1;
+
+    // 1回目: t < 2^256 * c + 2^256 < 2^290 なので上位は 34 ビットに収まる
+    let U512(limbs) = wide;
+    let low = U256([limbs[0], limbs[1], limbs[2], limbs[3]]);
+    let high = U256([limbs[4], limbs[5], limbs[6], limbs[7]]);
+    let U512(limbs) = high.full_mul(U256::from(C)) + U512::from(low);
+    let low = U256([limbs[0], limbs[1], limbs[2], limbs[3]]);
+
+    // 2回目: 繰り上がりは高々 1
+    let folded = U256::from(limbs[4] as u128 * C as u128);
+    let (t, carry) = low.overflowing_add(folded);
+    // 3回目: 繰り上がった場合 t < 2^67 なので溢れない
+    let (t, _) = t.overflowing_add(U256::from(carry as u64 * C));
+
+    // t < 2^256 < 2p なので p を引くのは高々1回
+    let (reduced, borrow) = t.overflowing_sub(secp256k1::P);
+    U256::conditional_select(&reduced, &t, Choice::from(borrow as u8))
+}
+
+impl MulMod for u128 {
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+        mul_mod_by_doubling(self, rhs, modulus)
+    }
+}
+
+impl MulMod for U512 {
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+        mul_mod_by_doubling(self, rhs, modulus)
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl MulMod for num_bigint::BigUint {
+    fn mul_mod(self, rhs: Self, modulus: Self) -> Self {
+        (self * rhs) % modulus
+    }
+}
+
+/// An exponent accepted by `FieldElement::pow`.
+///
+/// Exponents are reduced into `[0, order)` before exponentiation, where
+/// `order = p - 1` is the size of the multiplicative group. Negative
+/// exponents wrap around, mirroring Python's `exponent % (p - 1)`.
+pub trait Exponent<T> {
+    fn reduce(self, order: T) -> T;
+}
+
+impl Exponent<U256> for U256 {
+    fn reduce(self, order: U256) -> U256 {
+        self % order
+    }
+}
+
+impl Exponent<U512> for U512 {
+    fn reduce(self, order: U512) -> U512 {
+        self % order
+    }
+}
+
+impl Exponent<U512> for U256 {
+    fn reduce(self, order: U512) -> U512 {
+        U512::from(self) % order
+    }
+}
+
+#[cfg(feature = "num-bigint")]
+impl Exponent<num_bigint::BigUint> for num_bigint::BigUint {
+    fn reduce(self, order: num_bigint::BigUint) -> num_bigint::BigUint {
+        self % order
+    }
+}
+
+// プリミティブ整数は、それを受け取れる任意の型 T の指数として使える
+macro_rules! impl_exponent_unsigned {
+    ($($t:ty),*) => {
+        $(
+            impl<T> Exponent<T> for $t
+            where
+                T: From<$t> + Rem<Output = T>,
+            {
+                fn reduce(self, order: T) -> T {
+                    T::from(self) % order
+                }
+            }
+        )*
+    };
+}
+
+impl_exponent_unsigned!(u8, u16, u32, u64, u128);
+
+macro_rules! impl_exponent_signed {
+    ($($t:ty => $unsigned:ty),*) => {
+        $(
+            impl<T> Exponent<T> for $t
+            where
+                T: From<$unsigned> + Sub<Output = T> + Rem<Output = T> + PartialEq + Clone,
+            {
+                fn reduce(self, order: T) -> T {
+                    let magnitude = T::from(self.unsigned_abs()) % order.clone();
+                    if self < 0 && magnitude != T::from(0) {
+                        order - magnitude
+                    } else {
+                        magnitude
+                    }
+                }
+            }
+        )*
+    };
+}
+
+impl_exponent_signed!(i8 => u8, i16 => u16, i32 => u32, i64 => u64);
+
+/// `(a + b) % modulus` for `a, b < modulus` without ever computing `a + b`
+/// when it could overflow.
+fn add_mod<T>(a: T, b: T, modulus: T) -> T
+where
+    T: Add<Output = T> + Sub<Output = T> + PartialOrd + Copy,
+{
+    let gap = modulus - b;
+    if a >= gap {
+        a - gap
+    } else {
+        a + b
+    }
+}
+
+/// Binary double-and-add multiplication, used when no wider type is available.
+fn mul_mod_by_doubling<T>(a: T, b: T, modulus: T) -> T
+where
+    T: Add<Output = T>
+        + Sub<Output = T>
+        + Div<Output = T>
+        + Rem<Output = T>
+        + PartialOrd
+        + From<u8>
+        + Copy,
+{
+    let zero = T::from(0);
+    let one = T::from(1);
+    let two = T::from(2);
+
+    let mut base = a % modulus;
+    let mut counter = b;
+    let mut ret = zero;
+    while counter > zero {
+        if counter % two == one {
+            ret = add_mod(ret, base, modulus);
+        }
+        base = add_mod(base, base, modulus);
+        counter = counter / two;
+    }
+    ret
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MulMod;
+    use primitive_types::{U256, U512};
+
+    #[test]
+    fn mul_mod_small() {
+        assert_eq!(3u64.mul_mod(12, 13), 10);
+        assert_eq!(
+            u64::MAX.mul_mod(u64::MAX, 97),
+            ((u64::MAX % 97) * (u64::MAX % 97)) % 97
+        );
+    }
+
+    #[test]
+    fn mul_mod_u256_near_max() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let minus_one = p - U256::from(1);
+
+        // (-1) * (-1) = 1
+        assert_eq!(minus_one.mul_mod(minus_one, p), U256::from(1));
+    }
+
+    #[test]
+    fn reduce_secp256k1_matches_generic_remainder() {
+        use super::reduce_secp256k1;
+        use crate::secp256k1::P;
+
+        // 最終の引き算が要る/要らない境界と、折り返しが3回要る値
+        let edges = [
+            U512::MAX - U512::from(12345),
+            U512::MAX,
+            U512::from(P) - U512::one(),
+            U512::from(P),
+            U512::from(U256::MAX),
+            U512::from(U256::MAX) << 256,
+            U512::zero(),
+        ];
+        for wide in edges {
+            let expected = U256::try_from(wide % U512::from(P)).unwrap();
+            assert_eq!(reduce_secp256k1(wide), expected, "{:x}", wide);
+        }
+
+        let a = P - U256::from(3);
+        let b = U256::from_str_radix(
+            "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+            16,
+        )
+        .unwrap();
+        let expected = U256::try_from(a.full_mul(b) % U512::from(P)).unwrap();
+        assert_eq!(a.mul_mod(b, P), expected);
+    }
+
+    #[test]
+    fn reduce_exponent() {
+        use super::Exponent;
+
+        assert_eq!(Exponent::<u64>::reduce(-3i64, 30), 27);
+        assert_eq!(Exponent::<u64>::reduce(-30i64, 30), 0);
+        assert_eq!(
+            Exponent::<U256>::reduce(-61i32, U256::from(30)),
+            U256::from(29)
+        );
+        assert_eq!(U256::from(65).reduce(U256::from(30)), U256::from(5));
+        assert_eq!(
+            Exponent::<U256>::reduce(65u32, U256::from(30)),
+            U256::from(5)
+        );
+        assert_eq!(Exponent::<u32>::reduce(65u8, 30u32), 5);
+    }
+
+    #[test]
+    fn square_u256_matches_full_mul() {
+        use super::square_u256;
+
+        for a in [
+            U256::zero(),
+            U256::one(),
+            U256::MAX,
+            U256::MAX - U256::from(0x1234_5678u64),
+            U256::from_str_radix(
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                16,
+            )
+            .unwrap(),
+        ] {
+            assert_eq!(square_u256(a), a.full_mul(a));
+        }
+    }
+
+    #[test]
+    fn mul_mod_doubling_matches_widening() {
+        let p = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        let a = p - U256::from(12345);
+        let b = p - U256::from(67890);
+
+        let narrow = a.mul_mod(b, p);
+        let wide = U512::from(a).mul_mod(U512::from(b), U512::from(p));
+        assert_eq!(U512::from(narrow), wide);
+    }
+}
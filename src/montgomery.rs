@@ -0,0 +1,181 @@
+use primitive_types::{U256, U512};
+
+use crate::field_element::FieldElement;
+use crate::modular::MulMod;
+
+/// Precomputed constants for Montgomery arithmetic modulo an odd 256-bit prime,
+/// with `R = 2^256`.
+///
+/// Values are kept as `a * R mod p`, so a multiplication costs one 512-bit
+/// product plus a REDC step made of shifts and multiplications, instead of
+/// the 512-by-256-bit division the plain `%` path performs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MontgomeryContext {
+    prime: U256,
+    // -p^-1 mod R
+    prime_inv_neg: U256,
+    // R^2 mod p
+    r2: U256,
+}
+
+/// A field element in Montgomery form, tied to the context it was created by.
+///
+/// It remembers the modulus, so passing it to a context for another modulus
+/// panics instead of silently giving a wrong result.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MontgomeryElement {
+    value: U256,
+    prime: U256,
+}
+
+impl MontgomeryContext {
+    pub fn new(prime: U256) -> Self {
+        if prime.is_zero() || !prime.bit(0) {
+            panic!("Montgomery arithmetic needs an odd modulus")
+        }
+
+        // ニュートン法で p^-1 mod 2^256 を求める（1回ごとに精度が倍になる）
+        let two = U256::from(2);
+        let mut inv = prime;
+        for _ in 0..8 {
+            let (product, _) = prime.overflowing_mul(inv);
+            let (correction, _) = two.overflowing_sub(product);
+            inv = inv.overflowing_mul(correction).0;
+        }
+        let prime_inv_neg = (!inv).overflowing_add(U256::one()).0;
+
+        // R mod p = (2^256 - p) mod p
+        let r = ((!prime).overflowing_add(U256::one()).0) % prime;
+        let r2 = r.mul_mod(r, prime);
+
+        Self {
+            prime,
+            prime_inv_neg,
+            r2,
+        }
+    }
+
+    pub fn prime(&self) -> U256 {
+        self.prime
+    }
+
+    pub fn to_montgomery(&self, element: &FieldElement<U256>) -> MontgomeryElement {
+        if element.prime != self.prime {
+            panic!("Prime number should be same")
+        }
+        MontgomeryElement {
+            value: self.reduce(element.num.full_mul(self.r2)),
+            prime: self.prime,
+        }
+    }
+
+    pub fn from_montgomery(&self, element: MontgomeryElement) -> FieldElement<U256> {
+        self.check(&element);
+        FieldElement::new(self.reduce(U512::from(element.value)), self.prime)
+    }
+
+    pub fn mul(&self, a: MontgomeryElement, b: MontgomeryElement) -> MontgomeryElement {
+        self.check(&a);
+        self.check(&b);
+        MontgomeryElement {
+            value: self.reduce(a.value.full_mul(b.value)),
+            prime: self.prime,
+        }
+    }
+
+    /// `base^exponent`; a chain of multiplications that all stay in
+    /// Montgomery form, which is where the representation pays off.
+    pub fn pow(&self, base: MontgomeryElement, exponent: U256) -> MontgomeryElement {
+        let mut ret = self.to_montgomery(&FieldElement::new(U256::one(), self.prime));
+        for i in (0..exponent.bits()).rev() {
+            ret = self.mul(ret, ret);
+            if exponent.bit(i) {
+                ret = self.mul(ret, base);
+            }
+        }
+        ret
+    }
+
+    fn check(&self, element: &MontgomeryElement) {
+        if element.prime != self.prime {
+            panic!("Prime number should be same")
+        }
+    }
+
+    /// REDC: returns `t * R^-1 mod p` for `t < p * R`.
+    fn reduce(&self, t: U512) -> U256 {
+        let low = U256::try_from(t & U512::from(U256::MAX)).expect("masked to 256 bits");
+        let m = low.overflowing_mul(self.prime_inv_neg).0;
+        let (sum, carry) = t.overflowing_add(m.full_mul(self.prime));
+
+        // 下位256ビットは 0 になるので上位だけ取り出す（桁上がりも考慮）
+        let mut high = sum >> 256;
+        if carry {
+            high += U512::one() << 256;
+        }
+        let prime = U512::from(self.prime);
+        if high >= prime {
+            high -= prime;
+        }
+        U256::try_from(high).expect("reduced below the modulus")
+    }
+}
+
+impl FieldElement<U256> {
+    pub fn to_montgomery(&self, ctx: &MontgomeryContext) -> MontgomeryElement {
+        ctx.to_montgomery(self)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MontgomeryContext;
+    use crate::field_element::FieldElement;
+    use primitive_types::U256;
+
+    fn secp256k1_prime() -> U256 {
+        U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap()
+    }
+
+    #[test]
+    fn round_trip() {
+        let p = secp256k1_prime();
+        let ctx = MontgomeryContext::new(p);
+        let a = FieldElement::new(p - U256::from(5), p);
+
+        assert_eq!(ctx.from_montgomery(a.to_montgomery(&ctx)), a);
+    }
+
+    #[test]
+    fn mul_matches_plain_path() {
+        let p = secp256k1_prime();
+        let ctx = MontgomeryContext::new(p);
+        let a = FieldElement::new(p - U256::from(2), p);
+        let b = FieldElement::new(U256::from(0x1234_5678_9abc_def0u64), p);
+
+        let product = ctx.mul(ctx.to_montgomery(&a), ctx.to_montgomery(&b));
+        assert_eq!(ctx.from_montgomery(product), a * b);
+    }
+
+    #[test]
+    #[should_panic(expected = "Prime number should be same")]
+    fn rejects_element_of_other_context() {
+        let a = FieldElement::new(U256::from(5), U256::from(223));
+        let element = MontgomeryContext::new(U256::from(223)).to_montgomery(&a);
+        MontgomeryContext::new(U256::from(227)).from_montgomery(element);
+    }
+
+    #[test]
+    fn pow_matches_plain_path() {
+        let p = U256::from(223);
+        let ctx = MontgomeryContext::new(p);
+        let a = FieldElement::new(U256::from(47), p);
+
+        let powed = ctx.pow(ctx.to_montgomery(&a), U256::from(200));
+        assert_eq!(ctx.from_montgomery(powed), a.pow(U256::from(200)));
+    }
+}
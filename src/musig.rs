@@ -0,0 +1,638 @@
+//! MuSig2 multi-signatures (BIP327).
+//!
+//! `n` signers aggregate their keys into one x-only key and, in two rounds
+//! (exchange public nonces, then partial signatures), produce a single
+//! BIP340 signature for it that is indistinguishable from a one-key
+//! signature.
+//!
+//! `SecNonce` is neither `Clone` nor `Copy` and `Session::sign` takes it by
+//! value, so signing twice with the same nonce, which leaks the private
+//! key, is a compile error rather than a runtime check.
+
+use std::fmt;
+
+use rand::{CryptoRng, RngCore};
+
+use crate::error::{MusigError, ParseError, TweakError};
+use crate::hash::tagged_hash;
+use crate::private_key::PrivateKey;
+use crate::s256_point::S256Point;
+use crate::scalar::{hash_to_scalar, Scalar};
+use crate::schnorr::{self, SchnorrSignature};
+use crate::secp256k1::generator_mul;
+use crate::secret::SecretScalar;
+use crate::xonly::XOnlyPublicKey;
+
+/// Sorts keys by their compressed SEC encoding (`KeySort`), so every signer
+/// arrives at the same aggregate key regardless of the order keys were
+/// exchanged in.
+pub fn sort_keys(keys: &mut [S256Point]) {
+    keys.sort_by_key(|key| key.sec_compressed());
+}
+
+/// The aggregate key `Q = a_1 P_1 + ... + a_n P_n` together with the
+/// tweaks applied to it (`KeyAggContext`).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct KeyAggContext {
+    pubkeys: Vec<[u8; 33]>,
+    list_hash: [u8; 32],
+    second_key: Option<[u8; 33]>,
+    q: S256Point,
+    gacc: Scalar,
+    tacc: Scalar,
+}
+
+impl KeyAggContext {
+    /// Aggregates `pubkeys` in the given order (`KeyAgg`).
+    pub fn new(pubkeys: &[S256Point]) -> Result<Self, MusigError> {
+        if let Some(i) = pubkeys.iter().position(|key| key.is_infinity()) {
+            return Err(MusigError::InvalidPublicKey(i));
+        }
+        let pubkeys: Vec<[u8; 33]> = pubkeys.iter().map(|key| key.sec_compressed()).collect();
+        let list_hash = tagged_hash("KeyAgg list", &pubkeys.concat());
+        // 先頭と異なる最初の鍵だけは係数を 1 にする
+        let second_key = pubkeys.iter().find(|&key| *key != pubkeys[0]).copied();
+
+        let mut ctx = Self {
+            pubkeys,
+            list_hash,
+            second_key,
+            q: S256Point::INFINITY,
+            gacc: Scalar::from(1),
+            tacc: Scalar::from(0),
+        };
+        for key in &ctx.pubkeys {
+            let point = S256Point::parse(key).expect("encoded from a valid point");
+            ctx.q += point * ctx.coefficient(key);
+        }
+        if ctx.q.is_infinity() {
+            return Err(MusigError::InvalidAggregateKey);
+        }
+        Ok(ctx)
+    }
+
+    /// `KeyAggCoeff`: 1 for the second distinct key, otherwise a hash of
+    /// the key list and the key.
+    fn coefficient(&self, pubkey: &[u8; 33]) -> Scalar {
+        if self.second_key.as_ref() == Some(pubkey) {
+            return Scalar::from(1);
+        }
+        let mut data = self.list_hash.to_vec();
+        data.extend_from_slice(pubkey);
+        hash_to_scalar("KeyAgg coefficient", &data)
+    }
+
+    /// Adds `tweak * G` to the aggregate key (`ApplyTweak`). With
+    /// `is_xonly` the key is first negated if needed to have an even y, as
+    /// for a taproot tweak; otherwise it is a plain BIP32-style tweak.
+    pub fn apply_tweak(&mut self, tweak: &Scalar, is_xonly: bool) -> Result<(), TweakError> {
+        let g = if is_xonly && !self.q.has_even_y() {
+            -Scalar::from(1)
+        } else {
+            Scalar::from(1)
+        };
+        let q = (self.q * g).add_tweak(tweak)?;
+
+        self.q = q;
+        self.gacc = g * self.gacc;
+        self.tacc = *tweak + g * self.tacc;
+        Ok(())
+    }
+
+    /// The aggregate key, including any tweaks.
+    pub fn aggregate_key(&self) -> S256Point {
+        self.q
+    }
+
+    /// The x-only aggregate key that the final signature verifies under.
+    pub fn xonly_key(&self) -> XOnlyPublicKey {
+        XOnlyPublicKey::from_point(&self.q).expect("never the point at infinity")
+    }
+
+    /// `g = -1` when `Q` has an odd y, so that `g * Q` is the key BIP340
+    /// verifies against.
+    fn parity(&self) -> Scalar {
+        if self.q.has_even_y() {
+            Scalar::from(1)
+        } else {
+            -Scalar::from(1)
+        }
+    }
+}
+
+/// A signer's secret nonce pair `(k1, k2)`, bound to the signer's key.
+///
+/// Use it for exactly one `Session::sign`. Wiped from memory when dropped.
+pub struct SecNonce {
+    k1: SecretScalar,
+    k2: SecretScalar,
+    pubkey: [u8; 33],
+}
+
+impl fmt::Debug for SecNonce {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecNonce(<redacted>)")
+    }
+}
+
+/// A signer's public nonce pair `(k1 * G, k2 * G)`, sent to the other
+/// signers in the first round.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct PubNonce {
+    r1: S256Point,
+    r2: S256Point,
+}
+
+impl PubNonce {
+    /// Parses the 66-byte encoding of two compressed points.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != 66 {
+            return Err(ParseError::InvalidLength);
+        }
+        Ok(Self {
+            r1: S256Point::parse(&bytes[..33])?,
+            r2: S256Point::parse(&bytes[33..])?,
+        })
+    }
+
+    pub fn serialize(&self) -> [u8; 66] {
+        let mut ret = [0u8; 66];
+        ret[..33].copy_from_slice(&self.r1.sec_compressed());
+        ret[33..].copy_from_slice(&self.r2.sec_compressed());
+        ret
+    }
+}
+
+/// Generates a nonce pair for the signer with public key `pubkey`
+/// (`NonceGen`).
+///
+/// Only the randomness from `rng` is required for security. The optional
+/// private key, aggregate key, message and `extra_in` are mixed in as
+/// defence in depth against a weak `rng`.
+pub fn nonce_gen<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    key: Option<&PrivateKey>,
+    pubkey: &S256Point,
+    agg_key: Option<&XOnlyPublicKey>,
+    msg: Option<&[u8]>,
+    extra_in: &[u8],
+) -> (SecNonce, PubNonce) {
+    let mut rand = [0u8; 32];
+    rng.fill_bytes(&mut rand);
+    let agg_key = agg_key.map(XOnlyPublicKey::serialize);
+    nonce_gen_internal(rand, key, pubkey, agg_key.as_ref(), msg, extra_in)
+}
+
+fn nonce_gen_internal(
+    mut rand: [u8; 32],
+    key: Option<&PrivateKey>,
+    pubkey: &S256Point,
+    agg_key: Option<&[u8; 32]>,
+    msg: Option<&[u8]>,
+    extra_in: &[u8],
+) -> (SecNonce, PubNonce) {
+    if let Some(key) = key {
+        let aux = tagged_hash("MuSig/aux", &rand);
+        for (r, (d, a)) in rand
+            .iter_mut()
+            .zip(key.secret().to_be_bytes().iter().zip(aux.iter()))
+        {
+            *r = d ^ a;
+        }
+    }
+
+    let pubkey = pubkey.sec_compressed();
+    let mut data = rand.to_vec();
+    data.push(33);
+    data.extend_from_slice(&pubkey);
+    match agg_key {
+        Some(agg_key) => {
+            data.push(32);
+            data.extend_from_slice(agg_key);
+        }
+        None => data.push(0),
+    }
+    match msg {
+        Some(msg) => {
+            data.push(1);
+            data.extend_from_slice(&(msg.len() as u64).to_be_bytes());
+            data.extend_from_slice(msg);
+        }
+        None => data.push(0),
+    }
+    data.extend_from_slice(&(extra_in.len() as u32).to_be_bytes());
+    data.extend_from_slice(extra_in);
+
+    let k = |i: u8| {
+        let mut data = data.clone();
+        data.push(i);
+        hash_to_scalar("MuSig/nonce", &data)
+    };
+    let (k1, k2) = (k(0), k(1));
+    // ハッシュが 0 になる確率は無視できる
+    assert!(!k1.is_zero() && !k2.is_zero(), "nonce hash is zero");
+
+    let secnonce = SecNonce {
+        k1: SecretScalar::new(k1.num()),
+        k2: SecretScalar::new(k2.num()),
+        pubkey,
+    };
+    // 秘密のノンスなので定数時間の固定基底テーブルで掛ける
+    let pubnonce = PubNonce {
+        r1: generator_mul(&secnonce.k1),
+        r2: generator_mul(&secnonce.k2),
+    };
+    (secnonce, pubnonce)
+}
+
+/// The sum of all signers' public nonces (`NonceAgg`), computed by any
+/// party and sent back to the signers.
+///
+/// Either point may be at infinity, which is encoded as 33 zero bytes.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AggNonce {
+    r1: S256Point,
+    r2: S256Point,
+}
+
+impl AggNonce {
+    pub fn new(pubnonces: &[PubNonce]) -> Self {
+        let mut ret = Self {
+            r1: S256Point::INFINITY,
+            r2: S256Point::INFINITY,
+        };
+        for nonce in pubnonces {
+            ret.r1 += nonce.r1;
+            ret.r2 += nonce.r2;
+        }
+        ret
+    }
+
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != 66 {
+            return Err(ParseError::InvalidLength);
+        }
+        let point = |bytes: &[u8]| {
+            if bytes.iter().all(|&b| b == 0) {
+                Ok(S256Point::INFINITY)
+            } else {
+                S256Point::parse(bytes)
+            }
+        };
+        Ok(Self {
+            r1: point(&bytes[..33])?,
+            r2: point(&bytes[33..])?,
+        })
+    }
+
+    pub fn serialize(&self) -> [u8; 66] {
+        let point = |point: &S256Point| {
+            if point.is_infinity() {
+                [0u8; 33]
+            } else {
+                point.sec_compressed()
+            }
+        };
+        let mut ret = [0u8; 66];
+        ret[..33].copy_from_slice(&point(&self.r1));
+        ret[33..].copy_from_slice(&point(&self.r2));
+        ret
+    }
+}
+
+/// Everything the second round needs: the keys, the aggregate nonce and
+/// the message, plus the values derived from them (`GetSessionValues`).
+#[derive(Clone, Debug)]
+pub struct Session {
+    key_agg: KeyAggContext,
+    b: Scalar,
+    r: S256Point,
+    e: Scalar,
+}
+
+impl Session {
+    pub fn new(key_agg: &KeyAggContext, aggnonce: &AggNonce, msg: &[u8]) -> Self {
+        let q = key_agg.xonly_key().serialize();
+
+        let mut data = aggnonce.serialize().to_vec();
+        data.extend_from_slice(&q);
+        data.extend_from_slice(msg);
+        let b = hash_to_scalar("MuSig/noncecoef", &data);
+
+        // 最終ノンス R = R1 + b * R2 (無限遠点なら G で代用する)
+        let mut r = aggnonce.r1 + aggnonce.r2 * b;
+        if r.is_infinity() {
+            r = S256Point::generator();
+        }
+
+        let e = schnorr::challenge(&r.x().expect("not at infinity"), &key_agg.xonly_key(), msg);
+
+        Self {
+            key_agg: key_agg.clone(),
+            b,
+            r,
+            e,
+        }
+    }
+
+    fn signer_coefficient(&self, pubkey: &[u8; 33]) -> Result<Scalar, MusigError> {
+        if !self.key_agg.pubkeys.contains(pubkey) {
+            return Err(MusigError::UnknownSigner);
+        }
+        Ok(self.key_agg.coefficient(pubkey))
+    }
+
+    /// Produces this signer's partial signature (`Sign`), consuming the
+    /// secret nonce so it cannot be used again.
+    pub fn sign(&self, secnonce: SecNonce, key: &PrivateKey) -> Result<Scalar, MusigError> {
+        let pubkey = key.point().sec_compressed();
+        if secnonce.pubkey != pubkey {
+            return Err(MusigError::SecNonceMismatch);
+        }
+        let a = self.signer_coefficient(&pubkey)?;
+
+        let mut k1 = Scalar::new(*secnonce.k1.expose_secret());
+        let mut k2 = Scalar::new(*secnonce.k2.expose_secret());
+        if !self.r.has_even_y() {
+            k1 = -k1;
+            k2 = -k2;
+        }
+        let d = self.key_agg.parity() * self.key_agg.gacc * key.to_scalar();
+
+        Ok(k1 + self.b * k2 + self.e * a * d)
+    }
+
+    /// Checks another signer's partial signature against their public nonce
+    /// and key (`PartialSigVerify`), to identify a misbehaving signer.
+    pub fn verify_partial(&self, psig: &Scalar, pubnonce: &PubNonce, pubkey: &S256Point) -> bool {
+        if pubkey.is_infinity() {
+            return false;
+        }
+        let a = match self.signer_coefficient(&pubkey.sec_compressed()) {
+            Ok(a) => a,
+            Err(_) => return false,
+        };
+
+        let mut r = pubnonce.r1 + pubnonce.r2 * self.b;
+        if !self.r.has_even_y() {
+            r = -r;
+        }
+        let g = self.key_agg.parity() * self.key_agg.gacc;
+        S256Point::generator() * *psig == r + *pubkey * (self.e * a * g)
+    }
+
+    /// Sums the partial signatures into the BIP340 signature
+    /// (`PartialSigAgg`).
+    pub fn aggregate(&self, psigs: &[Scalar]) -> SchnorrSignature {
+        let s = psigs.iter().fold(
+            self.e * self.key_agg.parity() * self.key_agg.tacc,
+            |acc, psig| acc + *psig,
+        );
+        SchnorrSignature {
+            r: self.r.x().expect("not at infinity"),
+            s,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{
+        nonce_gen, nonce_gen_internal, sort_keys, AggNonce, KeyAggContext, PubNonce, SecNonce,
+        Session,
+    };
+    use crate::error::MusigError;
+    use crate::private_key::PrivateKey;
+    use crate::s256_point::S256Point;
+    use crate::scalar::Scalar;
+    use crate::schnorr;
+    use crate::secret::SecretScalar;
+    use primitive_types::U256;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    fn point(s: &str) -> S256Point {
+        S256Point::parse(&hex(s)).unwrap()
+    }
+
+    #[test]
+    fn key_agg_vectors() {
+        // BIP327 key_agg_vectors.json
+        let keys = [
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            point("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+        ];
+        let agg = |indices: &[usize]| {
+            let keys: Vec<S256Point> = indices.iter().map(|&i| keys[i]).collect();
+            encode(&KeyAggContext::new(&keys).unwrap().xonly_key().serialize())
+        };
+
+        assert_eq!(
+            agg(&[0, 1, 2]),
+            "90539EEDE565F5D054F32CC0C220126889ED1E5D193BAF15AEF344FE59D4610C"
+        );
+        assert_eq!(
+            agg(&[2, 1, 0]),
+            "6204DE8B083426DC6EAF9502D27024D53FC826BF7D2012148A0575435DF54B2B"
+        );
+        assert_eq!(
+            agg(&[0, 0, 0]),
+            "B436E3BAD62B8CD409969A224731C193D051162D8C5AE8B109306127DA3AA935"
+        );
+        assert_eq!(
+            agg(&[0, 0, 1, 1]),
+            "69BC22BFA5D106306E48A20679DE1D7389386124D07571D0D872686028C26A3E"
+        );
+
+        assert_eq!(
+            KeyAggContext::new(&[keys[0], S256Point::INFINITY]),
+            Err(MusigError::InvalidPublicKey(1))
+        );
+    }
+
+    #[test]
+    fn sort_keys_orders_by_sec() {
+        let mut keys = [
+            point("03DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659"),
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("023590A94E768F8E1815C2F24B4D80A8E3149316C3518CE7B7AD338368D038CA66"),
+        ];
+        let sorted = [keys[2], keys[1], keys[0]];
+        sort_keys(&mut keys);
+        assert_eq!(keys, sorted);
+    }
+
+    #[test]
+    fn nonce_gen_mixes_inputs() {
+        let key = PrivateKey::new(Scalar::from_be_bytes(&[0x02; 32]).unwrap());
+        let gen = |msg: &[u8]| {
+            nonce_gen_internal([0; 32], Some(&key), &key.point(), None, Some(msg), &[])
+        };
+
+        let (secnonce, pubnonce) = gen(b"a");
+        let g = S256Point::generator();
+        assert_eq!(g * *secnonce.k1.expose_secret(), pubnonce.r1);
+        assert_eq!(g * *secnonce.k2.expose_secret(), pubnonce.r2);
+        assert_eq!(secnonce.pubkey, key.point().sec_compressed());
+
+        // 同じ乱数でもメッセージが違えば別のノンスになる
+        assert_eq!(gen(b"a").1, pubnonce);
+        assert_ne!(gen(b"b").1, pubnonce);
+    }
+
+    #[test]
+    fn sign_vectors() {
+        // BIP327 sign_verify_vectors.json
+        let key = PrivateKey::new(
+            Scalar::from_be_bytes(&hex(
+                "7FB9E0E687ADA1EEBF7ECFE2F21E73EBDB51A7D450948DFE8D76D7F2D1007671",
+            ))
+            .unwrap(),
+        );
+        let keys = [
+            point("03935F972DA013F80AE011890FA89B67A27B7BE6CCB24D3274D18B2D4067F261A9"),
+            point("02F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9"),
+            point("02DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA661"),
+        ];
+        assert_eq!(keys[0], key.point());
+        let pubnonces = [
+            PubNonce::parse(&hex("0337C87821AFD50A8644D820A8F3E02E499C931865C2360FB43D0A0D20DAFE07EA0287BF891D2A6DEAEBADC909352AA9405D1428C15F4B75F04DAE642A95C2548480")).unwrap(),
+            PubNonce::parse(&hex("0279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F817980279BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")).unwrap(),
+            PubNonce::parse(&hex("032DE2662628C90B03F5E720284EB52FF7D71F4284F627B68A853D78C78E1FFE9303E4C5524E83FFE1493B9077CF1CA6BEB2090C93D930321071AD40B2F44E599046")).unwrap(),
+        ];
+        let aggnonce = AggNonce::parse(&hex("028465FCF0BBDBCF443AABCCE533D42B4B5A10966AC09A49655E8C42DAAB8FCD61037496A3CC86926D452CAFCFD55D25972CA1675D549310DE296BFF42F72EEEA8C9")).unwrap();
+        assert_eq!(AggNonce::new(&pubnonces), aggnonce);
+        let msg = hex("F95466D086770E689964664219266FE5ED215C92AE20BAB5C9D79ADDDDF3C0CF");
+
+        let secnonce = || {
+            let bytes = hex("508B81A611F100A6B2B6B29656590898AF488BCF2E1F55CF22E5CFB84421FE61FA27FD49B1D50085B481285E1CA205D55C82CC1B31FF5CD54A489829355901F7");
+            SecNonce {
+                k1: SecretScalar::new(U256::from_big_endian(&bytes[..32])),
+                k2: SecretScalar::new(U256::from_big_endian(&bytes[32..])),
+                pubkey: key.point().sec_compressed(),
+            }
+        };
+
+        let cases: [(&[usize], &str); 3] = [
+            (
+                &[0, 1, 2],
+                "012ABBCB52B3016AC03AD82395A1A415C48B93DEF78718E62A7A90052FE224FB",
+            ),
+            (
+                &[1, 0, 2],
+                "9FF2F7AAA856150CC8819254218D3ADEEB0535269051897724F9DB3789513A52",
+            ),
+            (
+                &[1, 2, 0],
+                "FA23C359F6FAC4E7796BB93BC9F0532A95468C539BA20FF86D7C76ED92227900",
+            ),
+        ];
+        for (indices, expected) in cases {
+            let ordered: Vec<S256Point> = indices.iter().map(|&i| keys[i]).collect();
+            let session = Session::new(&KeyAggContext::new(&ordered).unwrap(), &aggnonce, &msg);
+            let psig = session.sign(secnonce(), &key).unwrap();
+            assert_eq!(encode(&psig.to_be_bytes()), expected);
+            assert!(session.verify_partial(&psig, &pubnonces[0], &keys[0]));
+        }
+    }
+
+    #[test]
+    fn sign_and_aggregate() {
+        let mut rng = StdRng::seed_from_u64(69);
+        let keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::generate(&mut rng)).collect();
+        let mut pubkeys: Vec<S256Point> = keys.iter().map(PrivateKey::point).collect();
+        sort_keys(&mut pubkeys);
+
+        let mut key_agg = KeyAggContext::new(&pubkeys).unwrap();
+        // BIP32 風の通常の調整と taproot の x-only 調整を両方かける
+        key_agg.apply_tweak(&Scalar::from(7), false).unwrap();
+        key_agg.apply_tweak(&Scalar::from(11), true).unwrap();
+        let msg = b"MuSig2 test message";
+
+        // 1 ラウンド目: 公開ノンスを交換する
+        let (secnonces, pubnonces): (Vec<_>, Vec<_>) = keys
+            .iter()
+            .map(|key| {
+                let agg_key = key_agg.xonly_key();
+                nonce_gen(
+                    &mut rng,
+                    Some(key),
+                    &key.point(),
+                    Some(&agg_key),
+                    Some(msg),
+                    &[],
+                )
+            })
+            .unzip();
+        let aggnonce = AggNonce::new(&pubnonces);
+        assert_eq!(AggNonce::parse(&aggnonce.serialize()), Ok(aggnonce));
+
+        // 2 ラウンド目: 部分署名を集めて 1 つの署名にする
+        let session = Session::new(&key_agg, &aggnonce, msg);
+        let psigs: Vec<Scalar> = secnonces
+            .into_iter()
+            .zip(&keys)
+            .map(|(secnonce, key)| session.sign(secnonce, key).unwrap())
+            .collect();
+        for ((psig, pubnonce), key) in psigs.iter().zip(&pubnonces).zip(&keys) {
+            assert!(session.verify_partial(psig, pubnonce, &key.point()));
+        }
+        assert!(!session.verify_partial(&psigs[0], &pubnonces[1], &keys[0].point()));
+
+        let sig = session.aggregate(&psigs);
+        assert!(schnorr::verify(&key_agg.xonly_key(), msg, &sig));
+        assert!(!schnorr::verify(
+            &key_agg.xonly_key(),
+            b"other message",
+            &sig
+        ));
+        assert!(!schnorr::verify(
+            &key_agg.xonly_key(),
+            msg,
+            &session.aggregate(&psigs[1..])
+        ));
+    }
+
+    #[test]
+    fn rejects_foreign_signers() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let keys: Vec<PrivateKey> = (0..3).map(|_| PrivateKey::generate(&mut rng)).collect();
+        let key_agg = KeyAggContext::new(&[keys[0].point(), keys[1].point()]).unwrap();
+
+        let (secnonce, pubnonce) = nonce_gen(&mut rng, None, &keys[2].point(), None, None, &[]);
+        let session = Session::new(&key_agg, &AggNonce::new(&[pubnonce]), b"msg");
+        assert_eq!(
+            session.sign(secnonce, &keys[2]).unwrap_err(),
+            MusigError::UnknownSigner
+        );
+
+        let (secnonce, _) = nonce_gen(&mut rng, None, &keys[1].point(), None, None, &[]);
+        assert_eq!(
+            session.sign(secnonce, &keys[0]).unwrap_err(),
+            MusigError::SecNonceMismatch
+        );
+    }
+
+    #[test]
+    fn pubnonce_encoding() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let key = PrivateKey::generate(&mut rng);
+        let (_, pubnonce) = nonce_gen(&mut rng, Some(&key), &key.point(), None, None, &[]);
+
+        assert_eq!(PubNonce::parse(&pubnonce.serialize()), Ok(pubnonce));
+        assert!(PubNonce::parse(&[0u8; 66]).is_err());
+        assert!(AggNonce::parse(&[0u8; 66]).is_ok());
+    }
+}
@@ -0,0 +1,187 @@
+/// The Bitcoin network a key, address or peer belongs to.
+///
+/// Signet and regtest reuse the Base58 version bytes of testnet, so keys
+/// and addresses decoded from those encodings come back as `Testnet`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum Network {
+    Mainnet,
+    Testnet,
+    Signet,
+    Regtest,
+}
+
+impl Network {
+    pub const ALL: [Network; 4] = [
+        Network::Mainnet,
+        Network::Testnet,
+        Network::Signet,
+        Network::Regtest,
+    ];
+
+    /// Version byte of P2PKH addresses.
+    pub fn p2pkh_prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x00,
+            _ => 0x6f,
+        }
+    }
+
+    pub fn from_p2pkh_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            0x00 => Some(Network::Mainnet),
+            0x6f => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Version byte of P2SH addresses.
+    pub fn p2sh_prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x05,
+            _ => 0xc4,
+        }
+    }
+
+    pub fn from_p2sh_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            0x05 => Some(Network::Mainnet),
+            0xc4 => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Human-readable part of segwit addresses.
+    pub fn bech32_hrp(self) -> &'static str {
+        match self {
+            Network::Mainnet => "bc",
+            Network::Testnet | Network::Signet => "tb",
+            Network::Regtest => "bcrt",
+        }
+    }
+
+    /// The network of a segwit address; `tb` is reported as `Testnet`
+    /// since signet shares it.
+    pub fn from_bech32_hrp(hrp: &str) -> Option<Self> {
+        [Network::Mainnet, Network::Testnet, Network::Regtest]
+            .into_iter()
+            .find(|network| network.bech32_hrp() == hrp)
+    }
+
+    /// Version byte of WIF private keys.
+    pub fn wif_prefix(self) -> u8 {
+        match self {
+            Network::Mainnet => 0x80,
+            _ => 0xef,
+        }
+    }
+
+    pub fn from_wif_prefix(prefix: u8) -> Option<Self> {
+        match prefix {
+            0x80 => Some(Network::Mainnet),
+            0xef => Some(Network::Testnet),
+            _ => None,
+        }
+    }
+
+    /// Version bytes of BIP32 extended private keys (`xprv`, `tprv`).
+    pub fn xprv_version(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xad, 0xe4],
+            _ => [0x04, 0x35, 0x83, 0x94],
+        }
+    }
+
+    /// Version bytes of BIP32 extended public keys (`xpub`, `tpub`).
+    pub fn xpub_version(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0x04, 0x88, 0xb2, 0x1e],
+            _ => [0x04, 0x35, 0x87, 0xcf],
+        }
+    }
+
+    pub fn from_xprv_version(version: [u8; 4]) -> Option<Self> {
+        [Network::Mainnet, Network::Testnet]
+            .into_iter()
+            .find(|network| network.xprv_version() == version)
+    }
+
+    pub fn from_xpub_version(version: [u8; 4]) -> Option<Self> {
+        [Network::Mainnet, Network::Testnet]
+            .into_iter()
+            .find(|network| network.xpub_version() == version)
+    }
+
+    /// SLIP-44 coin type used in BIP44-style paths; every test network
+    /// shares `1`.
+    pub fn coin_type(self) -> u32 {
+        match self {
+            Network::Mainnet => 0,
+            _ => 1,
+        }
+    }
+
+    /// Magic bytes that start every p2p message on this network.
+    pub fn magic(self) -> [u8; 4] {
+        match self {
+            Network::Mainnet => [0xf9, 0xbe, 0xb4, 0xd9],
+            Network::Testnet => [0x0b, 0x11, 0x09, 0x07],
+            Network::Signet => [0x0a, 0x03, 0xcf, 0x40],
+            Network::Regtest => [0xfa, 0xbf, 0xb5, 0xda],
+        }
+    }
+
+    pub fn from_magic(magic: [u8; 4]) -> Option<Self> {
+        Network::ALL
+            .into_iter()
+            .find(|network| network.magic() == magic)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Network;
+
+    #[test]
+    fn prefixes_round_trip() {
+        for network in Network::ALL {
+            // signet と regtest は testnet と同じバイトを使う
+            let decoded = match network {
+                Network::Mainnet => Network::Mainnet,
+                _ => Network::Testnet,
+            };
+            assert_eq!(
+                Network::from_p2pkh_prefix(network.p2pkh_prefix()),
+                Some(decoded)
+            );
+            assert_eq!(
+                Network::from_p2sh_prefix(network.p2sh_prefix()),
+                Some(decoded)
+            );
+            assert_eq!(
+                Network::from_wif_prefix(network.wif_prefix()),
+                Some(decoded)
+            );
+            assert_eq!(
+                Network::from_xprv_version(network.xprv_version()),
+                Some(decoded)
+            );
+            assert_eq!(
+                Network::from_xpub_version(network.xpub_version()),
+                Some(decoded)
+            );
+            assert_eq!(Network::from_magic(network.magic()), Some(network));
+            // bech32 では regtest だけが別の hrp を持つ
+            let decoded = match network {
+                Network::Signet => Network::Testnet,
+                _ => network,
+            };
+            assert_eq!(
+                Network::from_bech32_hrp(network.bech32_hrp()),
+                Some(decoded)
+            );
+        }
+        assert_eq!(Network::from_wif_prefix(0x00), None);
+        assert_eq!(Network::from_magic([0; 4]), None);
+        assert_eq!(Network::from_bech32_hrp("ltc"), None);
+    }
+}
@@ -0,0 +1,301 @@
+//! BIP21 payment URIs: `bitcoin:<address>?amount=...&label=...`.
+//!
+//! Amounts are decimal bitcoin in the URI and satoshis here. Parameter
+//! values are percent-encoded UTF-8; unknown parameters are ignored unless
+//! prefixed with `req-`, which BIP21 says must be rejected.
+
+use std::fmt;
+use std::str::FromStr;
+
+use crate::address::Address;
+use crate::error::UriError;
+
+const SCHEME: &str = "bitcoin:";
+const SATS_PER_BTC: u64 = 100_000_000;
+const MAX_SATS: u64 = 21_000_000 * SATS_PER_BTC;
+
+/// A payment request for a wallet to open.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PaymentUri {
+    pub address: Address,
+    /// The requested amount in satoshis.
+    pub amount: Option<u64>,
+    /// A name for the recipient.
+    pub label: Option<String>,
+    /// A note describing the payment.
+    pub message: Option<String>,
+    /// A BOLT11 invoice offered as an alternative to paying on-chain.
+    pub lightning: Option<String>,
+}
+
+impl PaymentUri {
+    /// A request for any amount to `address`.
+    pub fn new(address: Address) -> Self {
+        Self {
+            address,
+            amount: None,
+            label: None,
+            message: None,
+            lightning: None,
+        }
+    }
+}
+
+impl FromStr for PaymentUri {
+    type Err = UriError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        // スキームは大文字小文字を区別しない
+        if s.len() < SCHEME.len() || !s[..SCHEME.len()].eq_ignore_ascii_case(SCHEME) {
+            return Err(UriError::InvalidScheme);
+        }
+        let rest = &s[SCHEME.len()..];
+        let (address, query) = match rest.split_once('?') {
+            Some((address, query)) => (address, Some(query)),
+            None => (rest, None),
+        };
+        let address = address.parse().map_err(UriError::InvalidAddress)?;
+        let mut uri = PaymentUri::new(address);
+
+        for param in query.into_iter().flat_map(|q| q.split('&')) {
+            let (key, value) = param.split_once('=').unwrap_or((param, ""));
+            let slot = match key {
+                "amount" => {
+                    if uri.amount.is_some() {
+                        return Err(UriError::DuplicateParameter);
+                    }
+                    uri.amount = Some(parse_amount(value)?);
+                    continue;
+                }
+                "label" => &mut uri.label,
+                "message" => &mut uri.message,
+                "lightning" => &mut uri.lightning,
+                _ if key.starts_with("req-") => return Err(UriError::UnknownRequiredParameter),
+                _ => continue,
+            };
+            if slot.is_some() {
+                return Err(UriError::DuplicateParameter);
+            }
+            *slot = Some(percent_decode(value)?);
+        }
+        Ok(uri)
+    }
+}
+
+impl fmt::Display for PaymentUri {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}{}", SCHEME, self.address)?;
+        let mut separator = '?';
+        if let Some(amount) = self.amount {
+            write!(f, "{}amount={}", separator, format_amount(amount))?;
+            separator = '&';
+        }
+        let params = [
+            ("label", &self.label),
+            ("message", &self.message),
+            ("lightning", &self.lightning),
+        ];
+        for (key, value) in params {
+            if let Some(value) = value {
+                write!(f, "{}{}={}", separator, key, percent_encode(value))?;
+                separator = '&';
+            }
+        }
+        Ok(())
+    }
+}
+
+/// `"0.0005"` を satoshi に。指数表記や 9 桁以上の小数は受け付けない。
+fn parse_amount(s: &str) -> Result<u64, UriError> {
+    let (whole, fraction) = s.split_once('.').unwrap_or((s, ""));
+    let all_digits = |part: &str| part.bytes().all(|b| b.is_ascii_digit());
+    if (whole.is_empty() && fraction.is_empty())
+        || fraction.len() > 8
+        || !all_digits(whole)
+        || !all_digits(fraction)
+    {
+        return Err(UriError::InvalidAmount);
+    }
+    let whole: u64 = if whole.is_empty() {
+        0
+    } else {
+        whole.parse().map_err(|_| UriError::InvalidAmount)?
+    };
+    let fraction: u64 = format!("{:0<8}", fraction)
+        .parse()
+        .expect("eight ascii digits");
+    whole
+        .checked_mul(SATS_PER_BTC)
+        .and_then(|sats| sats.checked_add(fraction))
+        .filter(|&sats| sats <= MAX_SATS)
+        .ok_or(UriError::InvalidAmount)
+}
+
+fn format_amount(sats: u64) -> String {
+    let whole = sats / SATS_PER_BTC;
+    let fraction = sats % SATS_PER_BTC;
+    if fraction == 0 {
+        return whole.to_string();
+    }
+    let fraction = format!("{:08}", fraction);
+    format!("{}.{}", whole, fraction.trim_end_matches('0'))
+}
+
+/// RFC 3986 の unreserved 以外をすべて `%XX` にする。
+fn percent_encode(s: &str) -> String {
+    let mut ret = String::with_capacity(s.len());
+    for &b in s.as_bytes() {
+        if b.is_ascii_alphanumeric() || b"-._~".contains(&b) {
+            ret.push(b as char);
+        } else {
+            ret.push_str(&format!("%{:02X}", b));
+        }
+    }
+    ret
+}
+
+fn percent_decode(s: &str) -> Result<String, UriError> {
+    let bytes = s.as_bytes();
+    let mut ret = Vec::with_capacity(bytes.len());
+    let mut i = 0;
+    while i < bytes.len() {
+        if bytes[i] == b'%' {
+            let byte = bytes
+                .get(i + 1..i + 3)
+                .and_then(|hex| std::str::from_utf8(hex).ok())
+                .filter(|hex| hex.bytes().all(|b| b.is_ascii_hexdigit()))
+                .and_then(|hex| u8::from_str_radix(hex, 16).ok())
+                .ok_or(UriError::InvalidEncoding)?;
+            ret.push(byte);
+            i += 3;
+        } else {
+            ret.push(bytes[i]);
+            i += 1;
+        }
+    }
+    String::from_utf8(ret).map_err(|_| UriError::InvalidEncoding)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_amount, parse_amount, PaymentUri};
+    use crate::address::Address;
+    use crate::error::{ParseError, UriError};
+
+    // BIP21 の例のアドレスはチェックサムが不正なので、本のアドレスで代用する
+    const ADDRESS: &str = "1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAs";
+
+    fn address() -> Address {
+        ADDRESS.parse().unwrap()
+    }
+
+    #[test]
+    fn bip21_examples() {
+        let uri: PaymentUri = format!("bitcoin:{}", ADDRESS).parse().unwrap();
+        assert_eq!(uri, PaymentUri::new(address()));
+
+        let uri: PaymentUri = format!("bitcoin:{}?label=Luke-Jr", ADDRESS)
+            .parse()
+            .unwrap();
+        assert_eq!(uri.label.as_deref(), Some("Luke-Jr"));
+
+        let uri: PaymentUri = format!(
+            "bitcoin:{}?amount=50&label=Luke-Jr&message=Donation%20for%20project%20xyz",
+            ADDRESS
+        )
+        .parse()
+        .unwrap();
+        assert_eq!(uri.amount, Some(50 * 100_000_000));
+        assert_eq!(uri.label.as_deref(), Some("Luke-Jr"));
+        assert_eq!(uri.message.as_deref(), Some("Donation for project xyz"));
+
+        // 未知のパラメータは無視し、req- 付きなら拒否する
+        let uri: PaymentUri = format!("bitcoin:{}?somethingyoudontunderstand=50", ADDRESS)
+            .parse()
+            .unwrap();
+        assert_eq!(uri, PaymentUri::new(address()));
+        assert_eq!(
+            format!("bitcoin:{}?req-somethingyoudontunderstand=50", ADDRESS).parse::<PaymentUri>(),
+            Err(UriError::UnknownRequiredParameter)
+        );
+    }
+
+    #[test]
+    fn round_trip() {
+        let uri = PaymentUri {
+            address: "bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4"
+                .parse()
+                .unwrap(),
+            amount: Some(50_000),
+            label: Some("Café & Co".to_string()),
+            message: Some("100%=ok?".to_string()),
+            lightning: Some("lnbc500u1p3qkglusp5".to_string()),
+        };
+        let s = uri.to_string();
+        assert_eq!(
+            s,
+            "bitcoin:bc1qw508d6qejxtdg4y5r3zarvary0c5xw7kv8f3t4?amount=0.0005\
+             &label=Caf%C3%A9%20%26%20Co&message=100%25%3Dok%3F&lightning=lnbc500u1p3qkglusp5"
+        );
+        assert_eq!(s.parse::<PaymentUri>(), Ok(uri));
+
+        // QR コード向けの大文字表記も読める
+        let upper = "BITCOIN:BC1QW508D6QEJXTDG4Y5R3ZARVARY0C5XW7KV8F3T4?amount=1";
+        let uri: PaymentUri = upper.parse().unwrap();
+        assert_eq!(uri.amount, Some(100_000_000));
+    }
+
+    #[test]
+    fn amounts() {
+        assert_eq!(parse_amount("0.0005"), Ok(50_000));
+        assert_eq!(parse_amount("20.3"), Ok(2_030_000_000));
+        assert_eq!(parse_amount(".5"), Ok(50_000_000));
+        assert_eq!(parse_amount("1."), Ok(100_000_000));
+        assert_eq!(parse_amount("0.00000001"), Ok(1));
+        assert_eq!(parse_amount("21000000"), Ok(21_000_000 * 100_000_000));
+        for bad in [
+            "",
+            ".",
+            "1e3",
+            "-1",
+            "0.000000001",
+            "21000000.00000001",
+            "1,5",
+        ] {
+            assert_eq!(parse_amount(bad), Err(UriError::InvalidAmount), "{}", bad);
+        }
+
+        assert_eq!(format_amount(50_000), "0.0005");
+        assert_eq!(format_amount(2_030_000_000), "20.3");
+        assert_eq!(format_amount(100_000_000), "1");
+        assert_eq!(format_amount(1), "0.00000001");
+    }
+
+    #[test]
+    fn errors() {
+        assert_eq!(
+            format!("litecoin:{}", ADDRESS).parse::<PaymentUri>(),
+            Err(UriError::InvalidScheme)
+        );
+        assert_eq!(
+            "bitcoin:1PMycacnJaSqwwJqjawXBErnLsZ7RkXUAt".parse::<PaymentUri>(),
+            Err(UriError::InvalidAddress(ParseError::InvalidChecksum))
+        );
+        assert_eq!(
+            format!("bitcoin:{}?label=a&label=b", ADDRESS).parse::<PaymentUri>(),
+            Err(UriError::DuplicateParameter)
+        );
+        assert_eq!(
+            format!("bitcoin:{}?amount=1&amount=1", ADDRESS).parse::<PaymentUri>(),
+            Err(UriError::DuplicateParameter)
+        );
+        for bad in ["%", "%4", "%zz", "%C3"] {
+            assert_eq!(
+                format!("bitcoin:{}?label={}", ADDRESS, bad).parse::<PaymentUri>(),
+                Err(UriError::InvalidEncoding),
+                "{}",
+                bad
+            );
+        }
+    }
+}
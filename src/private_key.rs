@@ -0,0 +1,788 @@
+use std::fmt;
+
+use primitive_types::U256;
+use rand::{CryptoRng, RngCore};
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::base58::{decode_base58check, encode_base58check};
+use crate::ct::{Choice, ConstantTimeEq};
+use crate::ecdh::SharedSecret;
+use crate::elliptic::Point;
+use crate::error::{ParseError, TweakError};
+use crate::hash::{hmac_sha256, tagged_hash};
+use crate::network::Network;
+use crate::s256_point::S256Point;
+use crate::scalar::Scalar;
+use crate::secp256k1;
+use crate::secret::SecretScalar;
+use crate::signature::{contract_tweak, Signature};
+
+/// A secp256k1 private key `e`, a non-zero scalar modulo `N`.
+///
+/// The secret is wiped from memory when the key is dropped, and `Debug`
+/// does not print it.
+#[derive(Clone)]
+pub struct PrivateKey {
+    secret: SecretScalar,
+}
+
+impl PrivateKey {
+    /// Panics if `secret` is zero, which has no public key.
+    pub fn new(secret: Scalar) -> Self {
+        assert!(!secret.is_zero(), "The private key must not be zero.");
+        Self::from_scalar(secret)
+    }
+
+    // 呼び出し側で 0 でないことを確かめてある
+    fn from_scalar(secret: Scalar) -> Self {
+        Self {
+            secret: SecretScalar::new(secret.num()),
+        }
+    }
+
+    /// Uniformly random key from a cryptographically secure RNG.
+    ///
+    /// Draws 32 bytes at a time and rejects candidates that are zero or not
+    /// below `N` rather than reducing them, so every key in `[1, N-1]` is
+    /// equally likely.
+    pub fn generate<R: CryptoRng + RngCore>(rng: &mut R) -> Self {
+        loop {
+            let mut bytes = [0u8; 32];
+            rng.fill_bytes(&mut bytes);
+            if let Ok(secret) = Scalar::from_be_bytes(&bytes) {
+                if !secret.is_zero() {
+                    return Self::from_scalar(secret);
+                }
+            }
+        }
+    }
+
+    pub fn secret(&self) -> &SecretScalar {
+        &self.secret
+    }
+
+    /// The secret as a `Scalar` for arithmetic mod `N`. Unlike the key
+    /// itself, the returned copy is not wiped.
+    pub(crate) fn to_scalar(&self) -> Scalar {
+        Scalar::new(*self.secret.expose_secret())
+    }
+
+    /// `-e`, whose public key is `-P`. BIP340 signers use it to move to the
+    /// key with even `y`.
+    pub fn negate(&self) -> Self {
+        Self::from_scalar(-self.to_scalar())
+    }
+
+    /// Wallet Import Format: Base58Check of the network's version byte, the
+    /// 32-byte secret and, for keys whose public key is used in compressed
+    /// SEC form, a trailing `0x01`.
+    pub fn wif(&self, compressed: bool, network: Network) -> String {
+        let mut payload = Vec::with_capacity(34);
+        payload.push(network.wif_prefix());
+        payload.extend_from_slice(&self.secret.to_be_bytes());
+        if compressed {
+            payload.push(0x01);
+        }
+        let wif = encode_base58check(&payload);
+        payload.zeroize();
+        wif
+    }
+
+    /// Parses a WIF string into the key, whether its public key is
+    /// compressed, and its network (`Testnet` for every test network).
+    pub fn from_wif(s: &str) -> Result<(Self, bool, Network), ParseError> {
+        let mut payload = decode_base58check(s)?;
+        let compressed = match payload.len() {
+            33 => false,
+            34 if payload[33] == 0x01 => true,
+            34 => return Err(ParseError::InvalidCompressionFlag),
+            _ => return Err(ParseError::InvalidLength),
+        };
+        let network = Network::from_wif_prefix(payload[0]).ok_or(ParseError::InvalidPrefix)?;
+
+        let secret = Scalar::from_be_bytes(&payload[1..33]);
+        payload.zeroize();
+        match secret {
+            Ok(secret) if !secret.is_zero() => Ok((Self::from_scalar(secret), compressed, network)),
+            _ => Err(ParseError::InvalidScalar),
+        }
+    }
+
+    /// `e + tweak`, as in BIP32 child derivation and the taproot output
+    /// key tweak. The public key moves to `P + tweak * G`, see
+    /// `S256Point::add_tweak`.
+    pub fn add_tweak(&self, tweak: &Scalar) -> Result<Self, TweakError> {
+        let secret = self.to_scalar() + *tweak;
+        if secret.is_zero() {
+            return Err(TweakError::InvalidResult);
+        }
+        Ok(Self::from_scalar(secret))
+    }
+
+    /// `e * tweak`; the public key moves to `tweak * P`.
+    pub fn mul_tweak(&self, tweak: &Scalar) -> Result<Self, TweakError> {
+        if tweak.is_zero() {
+            return Err(TweakError::ZeroTweak);
+        }
+        Ok(Self::from_scalar(self.to_scalar() * *tweak))
+    }
+
+    /// The public key `P = e * G`.
+    pub fn point(&self) -> S256Point {
+        secp256k1::generator_mul(&self.secret)
+    }
+
+    /// ECDH with the other party's public key: `SHA256((e * other).x)`.
+    ///
+    /// Uses the constant-time ladder since `e` is secret. Panics if `other`
+    /// is the point at infinity.
+    pub fn diffie_hellman(&self, other: &S256Point) -> SharedSecret {
+        let shared = Point::from(*other).mul_secret(&self.secret);
+        let x = shared
+            .x()
+            .expect("The public key must not be the point at infinity.");
+        SharedSecret::from_x(&x)
+    }
+
+    /// ECDSA signature of the message hash `z`:
+    /// `r = (k * G).x mod N`, `s = (z + r * e) / k mod N`, with `s`
+    /// normalized to low-S as Bitcoin's standardness rules require.
+    ///
+    /// The nonce `k` is derived from the key and `z` per RFC 6979, so
+    /// signing the same `z` twice gives the same signature and a broken
+    /// random number generator cannot leak the key through a reused `k`.
+    pub fn sign(&self, z: U256) -> Signature {
+        self.sign_recoverable(z).0
+    }
+
+    /// `sign` with 32 bytes of extra entropy mixed into the RFC 6979 nonce
+    /// derivation (RFC 6979 section 3.6), e.g. as a countermeasure against
+    /// fault attacks. Different `extra_entropy` gives a different
+    /// signature.
+    pub fn sign_with_extra_entropy(&self, z: U256, extra_entropy: &[u8; 32]) -> Signature {
+        self.sign_deterministic(z, Some(extra_entropy)).0
+    }
+
+    /// `sign` plus the recovery id (0 to 3) from which
+    /// `S256Point::recover_pubkey` rebuilds the public key: bit 0 is the
+    /// parity of `(k * G).y` and bit 1 is set when `(k * G).x >= N`.
+    pub fn sign_recoverable(&self, z: U256) -> (Signature, u8) {
+        self.sign_deterministic(z, None)
+    }
+
+    /// `sign` with `data` committed to in the nonce: the nonce point is
+    /// `R = R0 + H(R0 || data) * G` where `R0 = k * G`. Returns the
+    /// signature and `R0`, with which anyone can check the commitment via
+    /// `Signature::commits_to`.
+    ///
+    /// Hardware wallets use this for anti-exfiltration: the host picks
+    /// `data` at random, so the device cannot grind nonces to leak its key.
+    pub fn sign_to_contract(&self, z: U256, data: &[u8]) -> (Signature, S256Point) {
+        let mut nonces = Rfc6979::new(
+            &self.to_scalar(),
+            z,
+            Some(&tagged_hash("s2c/ecdsa/data", data)),
+        );
+        loop {
+            let k = nonces.next_nonce();
+            let original_nonce = secp256k1::generator_mul(&SecretScalar::new(k.num()));
+            let k = k + contract_tweak(&original_nonce, data);
+            if k.is_zero() {
+                continue;
+            }
+            if let Some((sig, _)) = self.sign_with_nonce(z, &k) {
+                return (sig, original_nonce);
+            }
+        }
+    }
+
+    fn sign_deterministic(&self, z: U256, extra_entropy: Option<&[u8; 32]>) -> (Signature, u8) {
+        let mut nonces = Rfc6979::new(&self.to_scalar(), z, extra_entropy);
+        loop {
+            // r か s が 0 になる確率は無視できるが、その場合は次の k を使う
+            if let Some(ret) = self.sign_with_nonce(z, &nonces.next_nonce()) {
+                return ret;
+            }
+        }
+    }
+
+    /// Signs with a caller-chosen nonce, for reproducing the book's
+    /// exercises and published test vectors. `None` when `r` or `s` comes
+    /// out zero.
+    ///
+    /// Never use this with real keys: two signatures sharing a `k`, or a
+    /// single `k` that leaks, reveal the private key. `sign` derives `k`
+    /// safely.
+    #[cfg(feature = "danger")]
+    pub fn sign_with_k(&self, z: U256, k: &DangerousExplicitNonce) -> Option<Signature> {
+        self.sign_with_nonce(z, &k.0).map(|(sig, _)| sig)
+    }
+
+    /// Signs with the given nonce; `None` when `r` or `s` comes out zero.
+    fn sign_with_nonce(&self, z: U256, k: &Scalar) -> Option<(Signature, u8)> {
+        let z = Scalar::reduce(z);
+        let nonce_point = secp256k1::generator_mul(&SecretScalar::new(k.num()));
+        let x = nonce_point.x()?.num();
+        let r = Scalar::reduce(x);
+        let s = (z + r * self.to_scalar()) * k.inverse();
+        if r.is_zero() || s.is_zero() {
+            return None;
+        }
+
+        let mut recovery_id = !nonce_point.has_even_y() as u8 | ((x >= secp256k1::N) as u8) << 1;
+        let sig = Signature::new(r.num(), s.num());
+        if !sig.is_low_s() {
+            // s を N - s にするのは k を -k にするのと同じで、R の y の偶奇が逆になる
+            recovery_id ^= 1;
+        }
+        Some((sig.normalize_s(), recovery_id))
+    }
+}
+
+/// An ECDSA nonce picked by hand for `PrivateKey::sign_with_k`.
+///
+/// The name is a warning: a nonce that is reused, predictable or leaked
+/// gives away the private key, so this exists only for tests and
+/// exercises and is behind the `danger` feature.
+#[cfg(feature = "danger")]
+pub struct DangerousExplicitNonce(Scalar);
+
+#[cfg(feature = "danger")]
+impl DangerousExplicitNonce {
+    /// Panics if `k` is zero.
+    pub fn new(k: Scalar) -> Self {
+        assert!(!k.is_zero(), "The nonce must not be zero.");
+        Self(k)
+    }
+}
+
+/// The HMAC-SHA256 based generator of RFC 6979 section 3.2, producing the
+/// candidate nonces for one key and message hash.
+struct Rfc6979 {
+    k: [u8; 32],
+    v: [u8; 32],
+}
+
+impl Rfc6979 {
+    fn new(secret: &Scalar, z: U256, extra_entropy: Option<&[u8; 32]>) -> Self {
+        // x = int2octets(e), h1 = bits2octets(z)
+        let mut seed = secret.to_be_bytes().to_vec();
+        seed.extend_from_slice(&Scalar::reduce(z).to_be_bytes());
+        if let Some(extra) = extra_entropy {
+            seed.extend_from_slice(extra);
+        }
+
+        let mut ret = Self {
+            k: [0x00; 32],
+            v: [0x01; 32],
+        };
+        for tag in [0x00, 0x01] {
+            let mut data = ret.v.to_vec();
+            data.push(tag);
+            data.extend_from_slice(&seed);
+            ret.k = hmac_sha256(&ret.k, &data);
+            ret.v = hmac_sha256(&ret.k, &ret.v);
+        }
+        seed.zeroize();
+        ret
+    }
+
+    /// The next `k` in `[1, N - 1]`.
+    fn next_nonce(&mut self) -> Scalar {
+        loop {
+            self.v = hmac_sha256(&self.k, &self.v);
+            if let Ok(k) = Scalar::from_be_bytes(&self.v) {
+                if !k.is_zero() {
+                    // 次に呼ばれたときに備えて状態を進めておく
+                    self.advance();
+                    return k;
+                }
+            }
+            self.advance();
+        }
+    }
+
+    fn advance(&mut self) {
+        let mut data = self.v.to_vec();
+        data.push(0x00);
+        self.k = hmac_sha256(&self.k, &data);
+        self.v = hmac_sha256(&self.k, &self.v);
+    }
+}
+
+impl Drop for Rfc6979 {
+    fn drop(&mut self) {
+        self.k.zeroize();
+        self.v.zeroize();
+    }
+}
+
+/// Recovers the private key from two signatures of different message
+/// hashes that used the same nonce `k`, which is exactly what RFC 6979
+/// prevents.
+///
+/// Equal `r` gives away the reuse. From `s1 * k = z1 + r * e` and
+/// `s2 * k = z2 + r * e`, `k = (z1 - z2) / (s1 - s2)` and
+/// `e = (s1 * k - z1) / r`. Low-S normalization may have negated either
+/// `s`, so `s1 + s2` is tried as well and the candidate whose public key
+/// verifies both signatures is returned.
+pub fn recover_key_from_nonce_reuse(
+    sig1: &Signature,
+    sig2: &Signature,
+    z1: U256,
+    z2: U256,
+) -> Option<PrivateKey> {
+    let (z1, z2) = (Scalar::reduce(z1), Scalar::reduce(z2));
+    if sig1.r != sig2.r || z1 == z2 {
+        return None;
+    }
+    let r = Scalar::try_new(sig1.r).ok()?;
+    let s1 = Scalar::try_new(sig1.s).ok()?;
+    let s2 = Scalar::try_new(sig2.s).ok()?;
+    if r.is_zero() {
+        return None;
+    }
+
+    [s1 - s2, s1 + s2]
+        .into_iter()
+        .filter(|diff| !diff.is_zero())
+        .filter_map(|diff| {
+            let k = (z1 - z2) * diff.inverse();
+            let e = (s1 * k - z1) * r.inverse();
+            (!e.is_zero()).then(|| PrivateKey::new(e))
+        })
+        .find(|key| {
+            let point = key.point();
+            point.verify(z1.num(), sig1) && point.verify(z2.num(), sig2)
+        })
+}
+
+impl ConstantTimeEq for PrivateKey {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.secret.ct_eq(&other.secret)
+    }
+}
+
+/// Equality goes through `ct_eq` so comparing keys never short-circuits.
+impl PartialEq for PrivateKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for PrivateKey {}
+
+impl Zeroize for PrivateKey {
+    fn zeroize(&mut self) {
+        self.secret.zeroize();
+    }
+}
+
+impl Drop for PrivateKey {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for PrivateKey {}
+
+impl fmt::Debug for PrivateKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "PrivateKey(<redacted>)")
+    }
+}
+
+/// Serialized as the secret scalar, i.e. 64 hex digits in human-readable
+/// formats.
+#[cfg(feature = "serde")]
+impl serde::Serialize for PrivateKey {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serde::Serialize::serialize(&self.to_scalar(), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for PrivateKey {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let secret: Scalar = serde::Deserialize::deserialize(deserializer)?;
+        if secret.is_zero() {
+            return Err(serde::de::Error::custom("the private key must not be zero"));
+        }
+        Ok(Self::from_scalar(secret))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{recover_key_from_nonce_reuse, PrivateKey};
+    use crate::base58::encode_base58check;
+    use crate::error::{ParseError, TweakError};
+    use crate::hash::hash256;
+    use crate::network::Network;
+    use crate::s256_field::S256Field;
+    use crate::s256_point::S256Point;
+    use crate::scalar::Scalar;
+    use crate::secp256k1::N;
+    use primitive_types::U256;
+    use rand::rngs::StdRng;
+    use rand::{CryptoRng, RngCore, SeedableRng};
+    use sha2::{Digest, Sha256};
+    use zeroize::Zeroize;
+
+    fn make_hash(source: &[u8]) -> U256 {
+        let mut hasher = Sha256::new();
+        hasher.update(source);
+        U256::from(&hasher.finalize()[..])
+    }
+
+    #[test]
+    fn point() {
+        let key = PrivateKey::new(Scalar::from(7));
+        let expected = S256Point::new(
+            S256Field::from_hex("5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc")
+                .unwrap(),
+            S256Field::from_hex("6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da")
+                .unwrap(),
+        );
+        assert_eq!(key.point(), expected);
+        assert_eq!(key.point(), S256Point::generator() * U256::from(7));
+    }
+
+    #[test]
+    fn sign() {
+        let key = PrivateKey::new(Scalar::reduce(make_hash(b"This is my secret")));
+        let z = make_hash(b"This is my sign");
+
+        let sig = key.sign(z);
+        assert!(key.point().verify(z, &sig));
+        assert!(!key.point().verify(z + U256::one(), &sig));
+        assert!(sig.is_low_s());
+
+        let (recoverable, recovery_id) = key.sign_recoverable(z);
+        assert_eq!(recoverable, sig);
+        assert_eq!(
+            S256Point::recover_pubkey(z, &sig, recovery_id),
+            Some(key.point())
+        );
+
+        // RFC 6979 なので同じ z には同じ署名
+        assert_eq!(key.sign(z), sig);
+        assert_ne!(key.sign(z + U256::one()), sig);
+    }
+
+    #[test]
+    fn rfc6979_vectors() {
+        // 秘密鍵 1 での既知の決定的署名 (low-S)
+        let key = PrivateKey::new(Scalar::from(1));
+        let cases = [
+            (
+                "Satoshi Nakamoto",
+                "934b1ea10a4b3c1757e2b0c017d0b6143ce3c9a7e6a4a49860d7a6ab210ee3d8",
+                "2442ce9d2b916064108014783e923ec36b49743e2ffa1c4496f01a512aafd9e5",
+            ),
+            (
+                "All those moments will be lost in time, like tears in rain. Time to die...",
+                "8600dbd41e348fe5c9465ab92d23e3db8b98b873beecd930736488696438cb6b",
+                "547fe64427496db33bf66019dacbf0039c04199abb0122918601db38a72cfc21",
+            ),
+        ];
+        for (msg, r, s) in cases {
+            let sig = key.sign(make_hash(msg.as_bytes()));
+            assert_eq!(sig.r, U256::from_str_radix(r, 16).unwrap());
+            assert_eq!(sig.s, U256::from_str_radix(s, 16).unwrap());
+        }
+    }
+
+    #[test]
+    fn extra_entropy_changes_nonce() {
+        let key = PrivateKey::new(Scalar::from(1));
+        let z = make_hash(b"Satoshi Nakamoto");
+
+        let a = key.sign_with_extra_entropy(z, &[1; 32]);
+        assert_eq!(key.sign_with_extra_entropy(z, &[1; 32]), a);
+        assert_ne!(key.sign_with_extra_entropy(z, &[2; 32]), a);
+        assert_ne!(key.sign(z), a);
+        assert!(key.point().verify(z, &a));
+    }
+
+    #[test]
+    fn sign_with_known_nonce() {
+        // 本の第 3 章の例: e = 12345, k = 1234567890
+        let key = PrivateKey::new(Scalar::from(12345));
+        let z = U256::from_big_endian(&hash256(b"Programming Bitcoin!"));
+        let (sig, _) = key.sign_with_nonce(z, &Scalar::from(1234567890)).unwrap();
+
+        assert_eq!(
+            sig.r,
+            U256::from_str_radix(
+                "2b698a0f0a4041b77e63488ad48c23e8e8838dd1fb7520408b121697b782ef22",
+                16
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            sig.s,
+            U256::from_str_radix(
+                "1dbc63bfef4416705e602a7b564161167076d8b20990a0f26f316cff2cb0bc1a",
+                16
+            )
+            .unwrap()
+        );
+        assert!(key.point().verify(z, &sig));
+    }
+
+    #[cfg(feature = "danger")]
+    #[test]
+    fn sign_with_explicit_k() {
+        use super::DangerousExplicitNonce;
+
+        let key = PrivateKey::new(Scalar::from(12345));
+        let z = U256::from_big_endian(&hash256(b"Programming Bitcoin!"));
+        let k = DangerousExplicitNonce::new(Scalar::from(1234567890));
+        let sig = key.sign_with_k(z, &k).unwrap();
+        assert_eq!(
+            sig,
+            key.sign_with_nonce(z, &Scalar::from(1234567890)).unwrap().0
+        );
+        assert!(key.point().verify(z, &sig));
+
+        // 同じ k なら r も同じになる
+        let z2 = U256::from_big_endian(&hash256(b"Programming Bitcoin?"));
+        assert_eq!(key.sign_with_k(z2, &k).unwrap().r, sig.r);
+    }
+
+    #[test]
+    fn nonce_reuse_leaks_key() {
+        let key = PrivateKey::new(Scalar::from(0xdeadbeefu64));
+        let z1 = make_hash(b"first message");
+        for k in 1..=8u64 {
+            let k = Scalar::from(k * 1_000_003);
+            for other in [&b"second"[..], b"third", b"fourth"] {
+                let z2 = make_hash(other);
+                let (sig1, _) = key.sign_with_nonce(z1, &k).unwrap();
+                let (sig2, _) = key.sign_with_nonce(z2, &k).unwrap();
+                // low-S による反転の有無にかかわらず復元できる
+                let recovered = recover_key_from_nonce_reuse(&sig1, &sig2, z1, z2).unwrap();
+                assert_eq!(recovered, key);
+            }
+        }
+
+        // RFC 6979 の署名はノンスが異なるので何もわからない
+        let z2 = make_hash(b"second");
+        assert!(recover_key_from_nonce_reuse(&key.sign(z1), &key.sign(z2), z1, z2).is_none());
+        let (sig, _) = key.sign_with_nonce(z1, &Scalar::from(5)).unwrap();
+        assert!(recover_key_from_nonce_reuse(&sig, &sig, z1, z1).is_none());
+    }
+
+    #[test]
+    fn sign_to_contract() {
+        let key = PrivateKey::new(Scalar::from(12345));
+        let z = make_hash(b"anti-exfil");
+        let data = [0x5a; 32];
+        let (sig, original_nonce) = key.sign_to_contract(z, &data);
+
+        assert!(key.point().verify(z, &sig));
+        assert!(sig.commits_to(&original_nonce, &data));
+        assert!(!sig.commits_to(&original_nonce, &[0x5b; 32]));
+        assert!(!sig.commits_to(&S256Point::generator(), &data));
+        assert!(!key.sign(z).commits_to(&original_nonce, &data));
+
+        // データが違えばノンスも変わる
+        let (other, _) = key.sign_to_contract(z, b"other data");
+        assert_ne!(other.r, sig.r);
+        assert!(key.point().verify(z, &other));
+    }
+
+    #[test]
+    fn wif() {
+        // 本の練習問題
+        let cases = [
+            (
+                U256::from(5003),
+                true,
+                Network::Testnet,
+                "cMahea7zqjxrtgAbB7LSGbcQUr1uX1ojuat9jZodMN8rFTv2sfUK",
+            ),
+            (
+                U256::from(2021).pow(U256::from(5)),
+                false,
+                Network::Testnet,
+                "91avARGdfge8E4tZfYLoxeJ5sGBdNJQH4kvjpWAxgzczjbCwxic",
+            ),
+            (
+                U256::from(0x54321deadbeefu64),
+                true,
+                Network::Mainnet,
+                "KwDiBf89QgGbjEhKnhXJuH7LrciVrZi3qYjgiuQJv1h8Ytr2S53a",
+            ),
+        ];
+        for (secret, compressed, network, expected) in cases {
+            let key = PrivateKey::new(Scalar::new(secret));
+            assert_eq!(key.wif(compressed, network), expected);
+            assert_eq!(
+                PrivateKey::from_wif(expected).unwrap(),
+                (key, compressed, network)
+            );
+        }
+    }
+
+    #[test]
+    fn wif_errors() {
+        let secret = [0x11u8; 32];
+        let wif = |prefix: u8, secret: &[u8], suffix: &[u8]| {
+            let mut payload = vec![prefix];
+            payload.extend_from_slice(secret);
+            payload.extend_from_slice(suffix);
+            encode_base58check(&payload)
+        };
+
+        assert!(PrivateKey::from_wif(&wif(0x80, &secret, &[0x01])).is_ok());
+        assert_eq!(
+            PrivateKey::from_wif(&wif(0x00, &secret, &[0x01])),
+            Err(ParseError::InvalidPrefix)
+        );
+        assert_eq!(
+            PrivateKey::from_wif(&wif(0x80, &secret, &[0x02])),
+            Err(ParseError::InvalidCompressionFlag)
+        );
+        assert_eq!(
+            PrivateKey::from_wif(&wif(0x80, &secret[..31], &[])),
+            Err(ParseError::InvalidLength)
+        );
+        assert_eq!(
+            PrivateKey::from_wif(&wif(0x80, &[0; 32], &[])),
+            Err(ParseError::InvalidScalar)
+        );
+        assert_eq!(
+            PrivateKey::from_wif(&wif(0x80, &[0xff; 32], &[])),
+            Err(ParseError::InvalidScalar)
+        );
+
+        let valid = wif(0x80, &secret, &[]);
+        // 最後の文字を変えるとチェックサムが合わなくなる
+        let mut tampered = valid[..valid.len() - 1].to_string();
+        tampered.push(if valid.ends_with('2') { '3' } else { '2' });
+        assert_eq!(
+            PrivateKey::from_wif(&tampered),
+            Err(ParseError::InvalidChecksum)
+        );
+    }
+
+    /// 用意したバイト列を順に返すテスト用の乱数
+    struct Scripted(Vec<[u8; 32]>);
+
+    impl RngCore for Scripted {
+        fn next_u32(&mut self) -> u32 {
+            unimplemented!()
+        }
+
+        fn next_u64(&mut self) -> u64 {
+            unimplemented!()
+        }
+
+        fn fill_bytes(&mut self, dest: &mut [u8]) {
+            dest.copy_from_slice(&self.0.remove(0));
+        }
+
+        fn try_fill_bytes(&mut self, dest: &mut [u8]) -> Result<(), rand::Error> {
+            self.fill_bytes(dest);
+            Ok(())
+        }
+    }
+
+    impl CryptoRng for Scripted {}
+
+    #[test]
+    fn generate() {
+        let mut rng = StdRng::seed_from_u64(7);
+        let a = PrivateKey::generate(&mut rng);
+        let b = PrivateKey::generate(&mut rng);
+        assert_ne!(a, b);
+        assert_eq!(a, PrivateKey::generate(&mut StdRng::seed_from_u64(7)));
+
+        // 0 と N 以上は捨てて引き直す
+        let mut n = [0u8; 32];
+        N.to_big_endian(&mut n);
+        let mut rng = Scripted(vec![[0; 32], n, [0xff; 32], [0x11; 32]]);
+        let key = PrivateKey::generate(&mut rng);
+        assert_eq!(key.secret().to_be_bytes(), [0x11; 32]);
+        assert!(rng.0.is_empty());
+    }
+
+    #[test]
+    fn tweak() {
+        let key = PrivateKey::generate(&mut StdRng::seed_from_u64(56));
+        let tweak = Scalar::from_be_bytes(&[0x5a; 32]).unwrap();
+
+        let added = key.add_tweak(&tweak).unwrap();
+        assert_eq!(added.to_scalar(), key.to_scalar() + tweak);
+        assert_eq!(Ok(added.point()), key.point().add_tweak(&tweak));
+
+        let multiplied = key.mul_tweak(&tweak).unwrap();
+        assert_eq!(multiplied.to_scalar(), key.to_scalar() * tweak);
+        assert_eq!(Ok(multiplied.point()), key.point().mul_tweak(&tweak));
+
+        assert_eq!(
+            key.add_tweak(&-key.to_scalar()),
+            Err(TweakError::InvalidResult)
+        );
+        assert_eq!(key.mul_tweak(&Scalar::from(0)), Err(TweakError::ZeroTweak));
+    }
+
+    #[test]
+    fn negate_and_zeroize() {
+        let mut key = PrivateKey::new(Scalar::from(5));
+        assert_eq!(key.negate().point(), -key.point());
+        assert_eq!(key.negate().negate(), key);
+
+        key.zeroize();
+        assert!(key.secret().expose_secret().is_zero());
+    }
+
+    #[test]
+    fn diffie_hellman() {
+        let mut rng = StdRng::seed_from_u64(55);
+        let alice = PrivateKey::generate(&mut rng);
+        let bob = PrivateKey::generate(&mut rng);
+        let carol = PrivateKey::generate(&mut rng);
+
+        let shared = alice.diffie_hellman(&bob.point());
+        assert_eq!(shared, bob.diffie_hellman(&alice.point()));
+        assert_ne!(shared, alice.diffie_hellman(&carol.point()));
+
+        // 共有点 a * b * G の x 座標の SHA256
+        let point = bob.point() * *alice.secret().expose_secret();
+        let expected: [u8; 32] = Sha256::digest(point.x().unwrap().to_be_bytes()).into();
+        assert_eq!(shared.as_bytes(), &expected);
+        assert_eq!(format!("{:?}", shared), "SharedSecret(<redacted>)");
+    }
+
+    #[test]
+    #[should_panic(expected = "The public key must not be the point at infinity.")]
+    fn diffie_hellman_rejects_infinity() {
+        PrivateKey::new(Scalar::from(7)).diffie_hellman(&S256Point::INFINITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "The private key must not be zero.")]
+    fn zero_is_rejected() {
+        PrivateKey::new(Scalar::from(0));
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let key = PrivateKey::new(Scalar::from(7));
+        assert_eq!(format!("{:?}", key), "PrivateKey(<redacted>)");
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let key = PrivateKey::new(Scalar::from(0xabcd));
+        let json = serde_json::to_string(&key).unwrap();
+
+        assert_eq!(json, format!("\"{:064x}\"", 0xabcd));
+        assert_eq!(serde_json::from_str::<PrivateKey>(&json).unwrap(), key);
+        assert!(serde_json::from_str::<PrivateKey>(&format!("\"{:064x}\"", 0)).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use primitive_types::U256;
+use rand::RngCore;
+
+/// Samples a uniformly distributed integer in `[0, bound)`.
+///
+/// Draws only as many bits as `bound` needs and rejects out-of-range
+/// candidates instead of reducing them with `%`, so small values are not
+/// more likely than large ones. Every draw succeeds with probability at
+/// least 1/2.
+pub fn uniform_below<R: RngCore + ?Sized>(rng: &mut R, bound: U256) -> U256 {
+    if bound.is_zero() {
+        panic!("Cannot sample below zero")
+    }
+    let bits = bound.bits();
+    loop {
+        let mut bytes = [0u8; 32];
+        rng.fill_bytes(&mut bytes);
+        let mut candidate = U256::from_big_endian(&bytes);
+        if bits < 256 {
+            candidate &= (U256::one() << bits) - U256::one();
+        }
+        if candidate < bound {
+            return candidate;
+        }
+    }
+}
+
+/// Samples a uniformly distributed integer in `[low, high)`.
+pub fn uniform_range<R: RngCore + ?Sized>(rng: &mut R, low: U256, high: U256) -> U256 {
+    if low >= high {
+        panic!("Empty sampling range")
+    }
+    low + uniform_below(rng, high - low)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{uniform_below, uniform_range};
+    use primitive_types::U256;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn stays_in_range() {
+        let mut rng = StdRng::seed_from_u64(7);
+        for _ in 0..1000 {
+            assert!(uniform_below(&mut rng, U256::from(5)) < U256::from(5));
+            let value = uniform_range(&mut rng, U256::from(10), U256::from(13));
+            assert!(value >= U256::from(10) && value < U256::from(13));
+        }
+    }
+
+    #[test]
+    fn covers_every_value() {
+        let mut rng = StdRng::seed_from_u64(42);
+        let mut seen = [0u32; 6];
+        for _ in 0..6000 {
+            seen[uniform_below(&mut rng, U256::from(6)).as_usize()] += 1;
+        }
+        // 期待値 1000 から大きく外れないこと
+        assert!(seen.iter().all(|count| (850..1150).contains(count)));
+    }
+
+    #[test]
+    fn full_width_bound() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let value = uniform_below(&mut rng, U256::MAX);
+        assert!(value < U256::MAX);
+    }
+}
@@ -0,0 +1,355 @@
+use std::fmt;
+use std::ops::{Add, Deref, Div, Mul, Neg, Sub};
+
+use primitive_types::U256;
+use rand::RngCore;
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::error::FieldError;
+use crate::field_element::{FieldElement, One};
+use crate::modular::{reduce_secp256k1, square_u256, Exponent};
+use crate::secp256k1::P;
+
+/// Element of the secp256k1 base field, with the prime baked in.
+///
+/// Mixing moduli is impossible by construction. Unlike the generic
+/// `FieldElement`, addition, subtraction, negation, multiplication and
+/// inversion never branch on the values: reductions are done with masks,
+/// so the constant-time ladder and fixed-base table built on this type
+/// really are constant time.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct S256Field(FieldElement<U256>);
+
+impl S256Field {
+    pub fn try_new(num: U256) -> Result<Self, FieldError> {
+        FieldElement::try_new(num, P).map(Self)
+    }
+
+    pub fn new(num: U256) -> Self {
+        Self(FieldElement::new(num, P))
+    }
+
+    /// Wraps a value already known to be below `P`, for `const` items.
+    pub(crate) const fn from_reduced(num: U256) -> Self {
+        Self(FieldElement { num, prime: P })
+    }
+
+    pub fn from_hex(hex: &str) -> Result<Self, FieldError> {
+        FieldElement::from_hex(hex, P).map(Self)
+    }
+
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self(FieldElement::random(rng, P))
+    }
+
+    pub fn num(&self) -> U256 {
+        self.0.num
+    }
+
+    pub fn square(&self) -> Self {
+        Self::from_reduced(reduce_secp256k1(square_u256(self.num())))
+    }
+
+    pub fn cube(&self) -> Self {
+        Self(self.0.cube())
+    }
+
+    pub fn pow<E: Exponent<U256>>(&self, exponent: E) -> Self {
+        Self(self.0.pow(exponent))
+    }
+
+    /// `self^(p-2)`. The exponent is public, so only its bits steer the
+    /// square-and-multiply chain. Panics for zero.
+    pub fn inverse(&self) -> Self {
+        if bool::from(self.num().ct_eq(&U256::zero())) {
+            panic!("Zero has no multiplicative inverse")
+        }
+        let exponent = P - U256::from(2);
+        let mut ret = Self::from_reduced(U256::one());
+        for i in (0..exponent.bits()).rev() {
+            ret = ret.square();
+            if exponent.bit(i) {
+                ret = ret * *self;
+            }
+        }
+        ret
+    }
+
+    pub fn is_square(&self) -> bool {
+        self.0.is_square()
+    }
+
+    /// Square root using `p % 4 == 3`: `self^((p+1)/4)`, checked by squaring.
+    pub fn sqrt(&self) -> Option<Self> {
+        let root = self.pow((P + U256::one()) / U256::from(4));
+        if root.square() == *self {
+            Some(root)
+        } else {
+            None
+        }
+    }
+}
+
+impl ConstantTimeEq for S256Field {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for S256Field {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(FieldElement::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl Deref for S256Field {
+    type Target = FieldElement<U256>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<S256Field> for FieldElement<U256> {
+    fn from(element: S256Field) -> Self {
+        element.0
+    }
+}
+
+impl TryFrom<FieldElement<U256>> for S256Field {
+    type Error = FieldError;
+
+    fn try_from(element: FieldElement<U256>) -> Result<Self, Self::Error> {
+        if element.prime != P {
+            return Err(FieldError::PrimeMismatch);
+        }
+        Ok(Self(element))
+    }
+}
+
+/// Every `u64` is below the secp256k1 prime, so this never fails.
+impl One for S256Field {
+    fn one(&self) -> Self {
+        Self(self.0.one())
+    }
+}
+
+impl From<u64> for S256Field {
+    fn from(num: u64) -> Self {
+        Self::new(U256::from(num))
+    }
+}
+
+impl TryFrom<U256> for S256Field {
+    type Error = FieldError;
+
+    fn try_from(num: U256) -> Result<Self, Self::Error> {
+        Self::try_new(num)
+    }
+}
+
+/// Parses exactly 32 big-endian bytes.
+impl TryFrom<&[u8]> for S256Field {
+    type Error = FieldError;
+
+    fn try_from(bytes: &[u8]) -> Result<Self, Self::Error> {
+        FieldElement::from_be_bytes(bytes, P).map(Self)
+    }
+}
+
+impl fmt::Display for S256Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl fmt::LowerHex for S256Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for S256Field {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl Add for S256Field {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        // 和が 2^256 を超えたか p 以上なら p を引いた方を選ぶ
+        let (sum, carry) = self.num().overflowing_add(other.num());
+        let (reduced, borrow) = sum.overflowing_sub(P);
+        let use_reduced = Choice::from(carry as u8) | !Choice::from(borrow as u8);
+        Self::from_reduced(U256::conditional_select(&sum, &reduced, use_reduced))
+    }
+}
+
+impl Sub for S256Field {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        // 借りが出たら p を足し戻した方を選ぶ
+        let (diff, borrow) = self.num().overflowing_sub(other.num());
+        let (wrapped, _) = diff.overflowing_add(P);
+        Self::from_reduced(U256::conditional_select(
+            &diff,
+            &wrapped,
+            Choice::from(borrow as u8),
+        ))
+    }
+}
+
+impl Mul for S256Field {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self::from_reduced(reduce_secp256k1(self.num().full_mul(other.num())))
+    }
+}
+
+impl Div for S256Field {
+    type Output = Self;
+
+    #[allow(clippy::suspicious_arithmetic_impl)]
+    fn div(self, other: Self) -> Self::Output {
+        self * other.inverse()
+    }
+}
+
+impl Neg for S256Field {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self::from_reduced(U256::zero()) - self
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for S256Field {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_hex::HexU256(self.num()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for S256Field {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let crate::serde_hex::HexU256(num) = serde::Deserialize::deserialize(deserializer)?;
+        Self::try_new(num).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::S256Field;
+    use crate::error::FieldError;
+    use crate::field_element::FieldElement;
+    use crate::secp256k1::P;
+    use primitive_types::U256;
+
+    #[test]
+    fn new() {
+        assert!(S256Field::try_new(P - U256::one()).is_ok());
+        assert_eq!(S256Field::try_new(P), Err(FieldError::NotInRange));
+    }
+
+    #[test]
+    fn masked_arithmetic_matches_generic() {
+        // 繰り上がり・借りが出る境界の値
+        let values = [
+            U256::zero(),
+            U256::one(),
+            U256::from(0x1_0000_03D1u64),
+            P - U256::one(),
+            P - U256::from(0x1_0000_03D1u64),
+        ];
+        for &a in &values {
+            for &b in &values {
+                let (fa, fb) = (S256Field::new(a), S256Field::new(b));
+                let (ga, gb) = (FieldElement::new(a, P), FieldElement::new(b, P));
+                assert_eq!(*(fa + fb), ga + gb);
+                assert_eq!(*(fa - fb), ga - gb);
+                assert_eq!(*(fa * fb), ga * gb);
+                assert_eq!(*fa.square(), ga.square());
+                assert_eq!(*(-fa), -ga);
+                if !b.is_zero() {
+                    assert_eq!(*fb.inverse(), gb.inverse());
+                    assert_eq!(*(fa / fb), ga / gb);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn sqrt() {
+        let x = S256Field::new(
+            U256::from_str_radix(
+                "79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798",
+                16,
+            )
+            .unwrap(),
+        );
+        let y = S256Field::new(
+            U256::from_str_radix(
+                "483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8",
+                16,
+            )
+            .unwrap(),
+        );
+        let root = (x * x * x + S256Field::new(U256::from(7))).sqrt().unwrap();
+
+        assert!(root == y || root == -y);
+    }
+
+    #[test]
+    fn conversions() {
+        let a = S256Field::new(U256::from(42));
+        let generic: FieldElement<U256> = a.into();
+
+        assert_eq!(a.prime, P);
+        assert_eq!(S256Field::try_from(generic), Ok(a));
+        assert_eq!(
+            S256Field::try_from(FieldElement::new(U256::from(42), U256::from(223))),
+            Err(FieldError::PrimeMismatch)
+        );
+    }
+
+    #[test]
+    fn hex() {
+        let hex = "483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8";
+        let a = S256Field::from_hex(hex).unwrap();
+
+        assert_eq!(format!("{:x}", a), hex);
+        assert_eq!(format!("{:X}", a), hex.to_uppercase());
+    }
+
+    #[test]
+    fn from_literals_and_bytes() {
+        let a = S256Field::from(7);
+        assert_eq!(a, S256Field::new(U256::from(7)));
+        assert_eq!(S256Field::try_from(P), Err(FieldError::NotInRange));
+
+        let bytes = a.to_be_bytes();
+        assert_eq!(S256Field::try_from(&bytes[..]), Ok(a));
+        assert_eq!(
+            S256Field::try_from(&[0xffu8; 32][..]),
+            Err(FieldError::NotInRange)
+        );
+        assert_eq!(
+            S256Field::try_from(&bytes[..31]),
+            Err(FieldError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn display() {
+        let a = S256Field::new(U256::from(0xab));
+        assert_eq!(
+            a.to_string(),
+            "00000000000000000000000000000000000000000000000000000000000000ab"
+        );
+    }
+}
@@ -0,0 +1,768 @@
+use std::fmt;
+use std::ops::{Add, AddAssign, Deref, Mul, Neg, Sub};
+
+use primitive_types::U256;
+use sha2::{Digest, Sha256};
+
+use crate::base58::encode_base58check;
+use crate::curve::{CurvePoint, Secp256k1};
+use crate::elliptic::Point;
+use crate::error::{ParseError, PointError, TweakError};
+use crate::hash::hash160;
+use crate::network::Network;
+use crate::s256_field::S256Field;
+use crate::scalar::Scalar;
+use crate::secp256k1::N;
+use crate::signature::Signature;
+
+/// A point on secp256k1, the book's `S256Point`.
+///
+/// Scalars are reduced modulo the group order `N` before multiplying, so
+/// `G * k` and `G * (k + N)` agree without walking the extra bits.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct S256Point(CurvePoint<Secp256k1>);
+
+impl S256Point {
+    pub const INFINITY: Self = Self(CurvePoint::Infinity);
+
+    pub fn try_new(x: S256Field, y: S256Field) -> Result<Self, PointError> {
+        CurvePoint::try_new(x, y).map(Self)
+    }
+
+    pub fn new(x: S256Field, y: S256Field) -> Self {
+        Self(CurvePoint::new(x, y))
+    }
+
+    pub fn generator() -> Self {
+        Self(CurvePoint::generator())
+    }
+
+    /// SEC encoding: `0x02`/`0x03` (by the parity of y) followed by x when
+    /// `compressed`, otherwise `0x04` followed by x and y.
+    ///
+    /// Panics for the point at infinity, which has no SEC encoding.
+    pub fn sec(&self, compressed: bool) -> Vec<u8> {
+        if compressed {
+            self.sec_compressed().to_vec()
+        } else {
+            self.sec_uncompressed().to_vec()
+        }
+    }
+
+    pub fn sec_compressed(&self) -> [u8; 33] {
+        let (x, y) = self.coordinates();
+        let mut ret = [0u8; 33];
+        ret[0] = if y.num().bit(0) { 0x03 } else { 0x02 };
+        ret[1..].copy_from_slice(&x.to_be_bytes());
+        ret
+    }
+
+    pub fn sec_uncompressed(&self) -> [u8; 65] {
+        let (x, y) = self.coordinates();
+        let mut ret = [0u8; 65];
+        ret[0] = 0x04;
+        ret[1..33].copy_from_slice(&x.to_be_bytes());
+        ret[33..].copy_from_slice(&y.to_be_bytes());
+        ret
+    }
+
+    /// Parses a compressed or uncompressed SEC encoding.
+    ///
+    /// For the compressed form y is recovered as the square root of
+    /// `x^3 + 7` with the parity given by the prefix; an x without a
+    /// square root is rejected as not on the curve.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        let coordinate =
+            |bytes: &[u8]| S256Field::try_from(bytes).map_err(|_| ParseError::InvalidCoordinate);
+
+        match bytes.first() {
+            Some(0x04) => {
+                if bytes.len() != 65 {
+                    return Err(ParseError::InvalidLength);
+                }
+                let x = coordinate(&bytes[1..33])?;
+                let y = coordinate(&bytes[33..])?;
+                Self::try_new(x, y).map_err(|_| ParseError::NotOnCurve)
+            }
+            Some(&prefix @ (0x02 | 0x03)) => {
+                if bytes.len() != 33 {
+                    return Err(ParseError::InvalidLength);
+                }
+                let x = coordinate(&bytes[1..])?;
+                let point = Self::lift_x(&x).ok_or(ParseError::NotOnCurve)?;
+                if prefix == 0x03 {
+                    Ok(-point)
+                } else {
+                    Ok(point)
+                }
+            }
+            Some(_) => Err(ParseError::InvalidPrefix),
+            None => Err(ParseError::InvalidLength),
+        }
+    }
+
+    /// The point with x coordinate `x` and an even y (BIP340 `lift_x`), or
+    /// `None` when `x^3 + 7` has no square root.
+    pub fn lift_x(x: &S256Field) -> Option<Self> {
+        let alpha = x.cube() + S256Field::from(7);
+        let beta = alpha.sqrt()?;
+        // 平方根 beta と -beta のうち偶数の方を選ぶ
+        let y = if beta.num().bit(0) { -beta } else { beta };
+        Some(Self(CurvePoint::Coordinate { x: *x, y }))
+    }
+
+    /// Deterministically maps `msg` to a point with unknown discrete log,
+    /// by try-and-increment: `x = SHA256(msg || counter)` for counter
+    /// `0, 1, 2, ...` until `lift_x(x)` succeeds.
+    ///
+    /// About half of all x values are on the curve, so a couple of tries
+    /// suffice on average. The running time depends on `msg`, so it must
+    /// not be secret; this is not the RFC 9380 construction.
+    pub fn hash_to_curve(msg: &[u8]) -> Self {
+        for counter in 0u32.. {
+            let mut hasher = Sha256::new();
+            hasher.update(msg);
+            hasher.update(counter.to_be_bytes());
+            let digest = hasher.finalize();
+
+            if let Ok(x) = S256Field::try_from(&digest[..]) {
+                if let Some(point) = Self::lift_x(&x) {
+                    return point;
+                }
+            }
+        }
+        unreachable!("no x coordinate found in 2^32 tries")
+    }
+
+    /// `hash160` of the compressed SEC encoding, the key identifier of
+    /// BIP32 and the payload of P2PKH and P2WPKH outputs.
+    pub fn identifier(&self) -> [u8; 20] {
+        hash160(&self.sec_compressed())
+    }
+
+    /// First 4 bytes of `identifier`, as used for BIP32 parent
+    /// fingerprints and PSBT key origins.
+    pub fn fingerprint(&self) -> [u8; 4] {
+        let mut ret = [0u8; 4];
+        ret.copy_from_slice(&self.identifier()[..4]);
+        ret
+    }
+
+    /// P2PKH address: Base58Check of the network's version byte and the
+    /// hash160 of the SEC encoding.
+    ///
+    /// The compressed and uncompressed encodings of the same key give
+    /// different addresses.
+    pub fn address(&self, compressed: bool, network: Network) -> String {
+        let mut payload = Vec::with_capacity(21);
+        payload.push(network.p2pkh_prefix());
+        payload.extend_from_slice(&hash160(&self.sec(compressed)));
+        encode_base58check(&payload)
+    }
+
+    /// `self + tweak * G`, the public side of `PrivateKey::add_tweak`.
+    pub fn add_tweak(&self, tweak: &Scalar) -> Result<Self, TweakError> {
+        let ret = *self + Self::generator() * *tweak;
+        if ret.is_infinity() {
+            return Err(TweakError::InvalidResult);
+        }
+        Ok(ret)
+    }
+
+    /// `tweak * self`, the public side of `PrivateKey::mul_tweak`.
+    pub fn mul_tweak(&self, tweak: &Scalar) -> Result<Self, TweakError> {
+        if tweak.is_zero() {
+            return Err(TweakError::ZeroTweak);
+        }
+        if self.is_infinity() {
+            return Err(TweakError::InvalidResult);
+        }
+        Ok(*self * *tweak)
+    }
+
+    /// Whether y is even; `false` for the point at infinity.
+    pub fn has_even_y(&self) -> bool {
+        match self.0 {
+            CurvePoint::Coordinate { y, .. } => !y.num().bit(0),
+            CurvePoint::Infinity => false,
+        }
+    }
+
+    fn coordinates(&self) -> (S256Field, S256Field) {
+        match self.0 {
+            CurvePoint::Coordinate { x, y } => (x, y),
+            CurvePoint::Infinity => panic!("The point at infinity has no SEC encoding."),
+        }
+    }
+
+    /// Checks an ECDSA signature of the message hash `z` against this
+    /// public key: with `u = z / s` and `v = r / s`, whether
+    /// `(u * G + v * P).x == r`.
+    ///
+    /// `u * G + v * P` is evaluated in one pass with Shamir's trick (see
+    /// `CurvePoint::multi_mul`) rather than as two multiplications.
+    pub fn verify(&self, z: U256, sig: &Signature) -> bool {
+        if sig.r.is_zero() || sig.r >= N || sig.s.is_zero() || sig.s >= N {
+            return false;
+        }
+
+        // u = z / s, v = r / s (mod N)
+        let s_inv = Scalar::new(sig.s).inverse();
+        let u = Scalar::reduce(z) * s_inv;
+        let v = Scalar::new(sig.r) * s_inv;
+
+        let total = CurvePoint::multi_mul(&[(u.num(), Self::generator().0), (v.num(), self.0)]);
+        match total.x() {
+            Some(x) => x.num() % N == sig.r,
+            None => false,
+        }
+    }
+
+    /// The public key that produced `sig` over `z`, rebuilt from the
+    /// recovery id of `PrivateKey::sign_recoverable`:
+    /// `Q = (s * R - z * G) / r`, where `R` is the nonce point whose x is
+    /// `r` (or `r + N` with bit 1 set) and whose y parity is bit 0.
+    pub fn recover_pubkey(z: U256, sig: &Signature, recovery_id: u8) -> Option<Self> {
+        let nonce_point = Self::nonce_point(sig, recovery_id)?;
+        let r_inv = Scalar::new(sig.r).inverse();
+        let u = -(Scalar::reduce(z) * r_inv);
+        let v = Scalar::new(sig.s) * r_inv;
+        let point = Self(CurvePoint::multi_mul(&[
+            (u.num(), Self::generator().0),
+            (v.num(), nonce_point.0),
+        ]));
+        (!point.is_infinity()).then_some(point)
+    }
+
+    /// Checks many `(z, pubkey, sig, recovery_id)` ECDSA verifications at
+    /// once; `true` only if every signature is valid.
+    ///
+    /// `r` is only the x coordinate of the nonce point, so plain ECDSA
+    /// signatures cannot be batched; the recovery id of
+    /// `PrivateKey::sign_recoverable` pins down `R` itself. Then each
+    /// `u_i * G + v_i * P_i == R_i` is weighted by a random `a_i` and all
+    /// of them are checked with one multi-scalar multiplication
+    /// `(sum a_i u_i) * G + sum (a_i v_i) P_i - sum a_i R_i == O`. The
+    /// weights are hashed from all inputs, as in `schnorr::verify_batch`.
+    pub fn verify_batch(items: &[(U256, S256Point, Signature, u8)]) -> bool {
+        let mut seed = Sha256::new();
+        for (z, pubkey, sig, recovery_id) in items {
+            seed.update(Scalar::reduce(*z).to_be_bytes());
+            seed.update(pubkey.sec_compressed());
+            seed.update(sig.serialize_compact());
+            seed.update([*recovery_id]);
+        }
+        let seed = seed.finalize();
+
+        let mut u_sum = Scalar::from(0);
+        let mut terms = Vec::with_capacity(2 * items.len() + 1);
+        for (i, (z, pubkey, sig, recovery_id)) in items.iter().enumerate() {
+            let nonce_point = match Self::nonce_point(sig, *recovery_id) {
+                Some(point) => point,
+                None => return false,
+            };
+            // 最初の重みは 1 でよい
+            let a = if i == 0 {
+                Scalar::from(1)
+            } else {
+                let mut hasher = Sha256::new();
+                hasher.update(seed);
+                hasher.update((i as u64).to_be_bytes());
+                Scalar::from_be_bytes_reduced(&hasher.finalize().into())
+            };
+
+            let s_inv = Scalar::new(sig.s).inverse();
+            u_sum = u_sum + a * Scalar::reduce(*z) * s_inv;
+            terms.push(((a * Scalar::new(sig.r) * s_inv).num(), pubkey.0));
+            terms.push(((-a).num(), nonce_point.0));
+        }
+        terms.push((u_sum.num(), Self::generator().0));
+
+        CurvePoint::multi_mul(&terms).is_infinity()
+    }
+
+    /// The nonce point `R` of `sig` described by `recovery_id`.
+    fn nonce_point(sig: &Signature, recovery_id: u8) -> Option<Self> {
+        if recovery_id > 3 || sig.r.is_zero() || sig.r >= N || sig.s.is_zero() || sig.s >= N {
+            return None;
+        }
+
+        // R.x は r か r + N (後者は R.x >= N だった場合)
+        let x = if recovery_id & 2 != 0 {
+            sig.r.checked_add(N)?
+        } else {
+            sig.r
+        };
+        let x = S256Field::try_new(x).ok()?;
+        let point = Self::lift_x(&x)?;
+        Some(if recovery_id & 1 != 0 { -point } else { point })
+    }
+}
+
+impl Deref for S256Point {
+    type Target = CurvePoint<Secp256k1>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl From<S256Point> for CurvePoint<Secp256k1> {
+    fn from(point: S256Point) -> Self {
+        point.0
+    }
+}
+
+impl From<CurvePoint<Secp256k1>> for S256Point {
+    fn from(point: CurvePoint<Secp256k1>) -> Self {
+        Self(point)
+    }
+}
+
+impl From<S256Point> for Point<S256Field> {
+    fn from(point: S256Point) -> Self {
+        point.0.to_point()
+    }
+}
+
+impl TryFrom<Point<S256Field>> for S256Point {
+    type Error = PointError;
+
+    fn try_from(point: Point<S256Field>) -> Result<Self, Self::Error> {
+        CurvePoint::try_from(point).map(Self)
+    }
+}
+
+impl fmt::Display for S256Point {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match &self.0 {
+            CurvePoint::Coordinate { x, y } => write!(f, "S256Point({}, {})", x, y),
+            CurvePoint::Infinity => write!(f, "S256Point(infinity)"),
+        }
+    }
+}
+
+impl Add for S256Point {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for S256Point {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 - other.0)
+    }
+}
+
+impl Neg for S256Point {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+impl AddAssign for S256Point {
+    fn add_assign(&mut self, other: Self) {
+        *self = *self + other;
+    }
+}
+
+impl Mul<U256> for S256Point {
+    type Output = Self;
+
+    fn mul(self, scalar: U256) -> Self::Output {
+        // 位数 N で割った余りだけ掛ければよい
+        Self(self.0 * (scalar % N))
+    }
+}
+
+impl Mul<Scalar> for S256Point {
+    type Output = Self;
+
+    fn mul(self, scalar: Scalar) -> Self::Output {
+        Self(self.0 * scalar.num())
+    }
+}
+
+impl Mul<U256> for &S256Point {
+    type Output = S256Point;
+
+    fn mul(self, scalar: U256) -> Self::Output {
+        *self * scalar
+    }
+}
+
+impl Mul<S256Point> for U256 {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> Self::Output {
+        point * self
+    }
+}
+
+impl Mul<&S256Point> for U256 {
+    type Output = S256Point;
+
+    fn mul(self, point: &S256Point) -> Self::Output {
+        *point * self
+    }
+}
+
+impl Mul<S256Point> for Scalar {
+    type Output = S256Point;
+
+    fn mul(self, point: S256Point) -> Self::Output {
+        point * self
+    }
+}
+
+impl Mul<&S256Point> for Scalar {
+    type Output = S256Point;
+
+    fn mul(self, point: &S256Point) -> Self::Output {
+        *point * self
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::S256Point;
+    use crate::error::TweakError;
+    use crate::network::Network;
+    use crate::private_key::PrivateKey;
+    use crate::s256_field::S256Field;
+    use crate::scalar::Scalar;
+    use crate::secp256k1::{self, N};
+    use crate::signature::Signature;
+    use primitive_types::U256;
+
+    fn hex(s: &str) -> U256 {
+        U256::from_str_radix(s, 16).unwrap()
+    }
+
+    fn encode(bytes: impl AsRef<[u8]>) -> String {
+        bytes
+            .as_ref()
+            .iter()
+            .map(|b| format!("{:02x}", b))
+            .collect()
+    }
+
+    #[test]
+    fn order() {
+        let g = S256Point::generator();
+
+        assert_eq!(g * N, S256Point::INFINITY);
+        assert_eq!(g * (N + U256::one()), g);
+        assert_eq!(g * U256::zero(), S256Point::INFINITY);
+        assert_eq!(S256Point::try_from(secp256k1::generator()).unwrap(), g);
+    }
+
+    #[test]
+    fn pubpoint() {
+        // 本の練習問題: 秘密鍵から公開鍵を求める
+        let cases = [
+            (
+                U256::from(7),
+                "5cbdf0646e5db4eaa398f365f2ea7a0e3d419b7e0330e39ce92bddedcac4f9bc",
+                "6aebca40ba255960a3178d6d861a54dba813d0b813fde7b5a5082628087264da",
+            ),
+            (
+                U256::from(1485),
+                "c982196a7466fbbbb0e27a940b6af926c1a74d5ad07128c82824a11b5398afda",
+                "7a91f9eae64438afb9ce6448a1c133db2d8fb9254e4546b6f001637d50901f55",
+            ),
+            (
+                U256::one() << 128,
+                "8f68b9d2f63b5f339239c1ad981f162ee88c5678723ea3351b7b444c9ec4c0da",
+                "662a9f2dba063986de1d90c2b6be215dbbea2cfe95510bfdf23cbf79501fff82",
+            ),
+        ];
+        for (secret, x, y) in cases {
+            let expected = S256Point::new(S256Field::new(hex(x)), S256Field::new(hex(y)));
+            assert_eq!(S256Point::generator() * secret, expected);
+            assert_eq!(S256Point::generator() * Scalar::reduce(secret), expected);
+            assert_eq!(secret * S256Point::generator(), expected);
+            assert_eq!(Scalar::reduce(secret) * S256Point::generator(), expected);
+        }
+    }
+
+    #[test]
+    fn verify() {
+        let point = S256Point::new(
+            S256Field::new(hex(
+                "887387e452b8eacc4acfde10d9aaf7f6d9a0f975aabb10d006e4da568744d06c",
+            )),
+            S256Field::new(hex(
+                "61de6d95231cd89026e286df3b6ae4a894a3378e393e93a0f45b666329a0ae34",
+            )),
+        );
+
+        let z = hex("ec208baa0fc1c19f708a9ca96fdeff3ac3f230bb4a7ba4aede4942ad003c0f60");
+        let sig = Signature::new(
+            hex("ac8d1c87e51d0d441be8b3dd5b05c8795b48875dffe00b7ffcfac23010d3a395"),
+            hex("68342ceff8935ededd102dd876ffd6ba72d6a427a3edb13d26eb0781cb423c4"),
+        );
+        assert!(point.verify(z, &sig));
+        assert!(!point.verify(z + U256::one(), &sig));
+
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let sig = Signature::new(
+            hex("eff69ef2b1bd93a66ed5219add4fb51e11a840f404876325a1e8ffe0529a2c"),
+            hex("c7207fee197d27c618aea621406f6bf5ef6fca38681d82b2f06fddbdce6feab6"),
+        );
+        assert!(point.verify(z, &sig));
+
+        // 範囲外の r, s は new では作れないのでフィールドを直接書く
+        assert!(!point.verify(
+            z,
+            &Signature {
+                r: U256::zero(),
+                s: sig.s
+            }
+        ));
+        assert!(!point.verify(z, &Signature { r: sig.r, s: N }));
+
+        // (r, N - s) も有効 (ECDSA の展性)
+        assert!(point.verify(z, &Signature::new(sig.r, N - sig.s)));
+        assert!(!S256Point::generator().verify(z, &sig));
+    }
+
+    #[test]
+    fn recover_pubkey() {
+        let key = PrivateKey::new(Scalar::from(12345));
+        let z = hex("7c076ff316692a3d7eb3c3bb0f8b1488cf72e1afcd929e29307032997a838a3d");
+        let (sig, recovery_id) = key.sign_recoverable(z);
+
+        assert_eq!(
+            S256Point::recover_pubkey(z, &sig, recovery_id),
+            Some(key.point())
+        );
+        // y の偶奇を取り違えると別の鍵になる
+        let other = S256Point::recover_pubkey(z, &sig, recovery_id ^ 1).unwrap();
+        assert_ne!(other, key.point());
+        assert!(other.verify(z, &sig));
+
+        assert_eq!(S256Point::recover_pubkey(z, &sig, 4), None);
+        assert_ne!(
+            S256Point::recover_pubkey(z + U256::one(), &sig, recovery_id),
+            Some(key.point())
+        );
+    }
+
+    #[test]
+    fn verify_batch() {
+        let mut items: Vec<_> = (1..=40u64)
+            .map(|i| {
+                let key = PrivateKey::new(Scalar::from(i * 104729));
+                let z = U256::from(i) * hex("9e3779b97f4a7c15f39cc0605cedc834");
+                let (sig, recovery_id) = key.sign_recoverable(z);
+                (z, key.point(), sig, recovery_id)
+            })
+            .collect();
+
+        assert!(S256Point::verify_batch(&items));
+        assert!(S256Point::verify_batch(&items[..1]));
+        assert!(S256Point::verify_batch(&[]));
+
+        let mut bad = items.clone();
+        bad[17].0 += U256::one();
+        assert!(!S256Point::verify_batch(&bad));
+
+        // 署名自体は正しくても recovery id が違えば通らない
+        let mut bad = items.clone();
+        bad[3].3 ^= 1;
+        assert!(!S256Point::verify_batch(&bad));
+
+        items[39].1 = items[0].1;
+        assert!(!S256Point::verify_batch(&items));
+    }
+
+    #[test]
+    fn display() {
+        assert_eq!(S256Point::INFINITY.to_string(), "S256Point(infinity)");
+        assert_eq!(
+            S256Point::generator().to_string(),
+            "S256Point(79be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798, \
+             483ada7726a3c4655da4fbfc0e1108a8fd17b448a68554199c47d08ffb10d4b8)"
+        );
+    }
+
+    #[test]
+    fn sec() {
+        // 本の練習問題
+        let g = S256Point::generator();
+        let uncompressed = [
+            (
+                U256::from(5000),
+                "04ffe558e388852f0120e46af2d1b370f85854a8eb0841811ece0e3e03d282d57c315dc72890a4f10a1481c031b03b351b0dc79901ca18a00cf009dbdb157a1d10",
+            ),
+            (
+                U256::from(2018).pow(U256::from(5)),
+                "04027f3da1918455e03c46f659266a1bb5204e959db7364d2f473bdf8f0a13cc9dff87647fd023c13b4a4994f17691895806e1b40b57f4fd22581a4f46851f3b06",
+            ),
+            (
+                hex("deadbeef12345"),
+                "04d90cd625ee87dd38656dd95cf79f65f60f7273b67d3096e68bd81e4f5342691f842efa762fd59961d0e99803c61edba8b3e3f7dc3a341836f97733aebf987121",
+            ),
+        ];
+        for (secret, expected) in uncompressed {
+            let point = g * secret;
+            assert_eq!(encode(point.sec_uncompressed()), expected);
+            assert_eq!(encode(point.sec(false)), expected);
+        }
+
+        let compressed = [
+            (
+                U256::from(5001),
+                "0357a4f368868a8a6d572991e484e664810ff14c05c0fa023275251151fe0e53d1",
+            ),
+            (
+                U256::from(2019).pow(U256::from(5)),
+                "02933ec2d2b111b92737ec12f1c5d20f3233a0ad21cd8b36d0bca7a0cfa5cb8701",
+            ),
+            (
+                hex("deadbeef54321"),
+                "0296be5b1292f6c856b3c5654e886fc13511462059089cdf9c479623bfcbe77690",
+            ),
+        ];
+        for (secret, expected) in compressed {
+            let point = g * secret;
+            assert_eq!(encode(point.sec_compressed()), expected);
+            assert_eq!(encode(point.sec(true)), expected);
+        }
+    }
+
+    #[test]
+    #[should_panic]
+    fn sec_of_infinity() {
+        S256Point::INFINITY.sec(true);
+    }
+
+    #[test]
+    fn parse() {
+        let g = S256Point::generator();
+        for secret in [
+            U256::one(),
+            U256::from(5001),
+            hex("deadbeef54321"),
+            N - U256::one(),
+        ] {
+            let point = g * secret;
+            assert_eq!(S256Point::parse(&point.sec_compressed()), Ok(point));
+            assert_eq!(S256Point::parse(&point.sec_uncompressed()), Ok(point));
+        }
+    }
+
+    #[test]
+    fn parse_errors() {
+        use crate::error::ParseError;
+        use crate::secp256k1::P;
+
+        let sec = S256Point::generator().sec_uncompressed();
+
+        assert_eq!(S256Point::parse(&[]), Err(ParseError::InvalidLength));
+        assert_eq!(S256Point::parse(&sec[..64]), Err(ParseError::InvalidLength));
+        assert_eq!(S256Point::parse(&sec[..33]), Err(ParseError::InvalidLength));
+        let mut unknown = sec;
+        unknown[0] = 0x05;
+        assert_eq!(S256Point::parse(&unknown), Err(ParseError::InvalidPrefix));
+        assert_eq!(S256Point::parse(&[0x00]), Err(ParseError::InvalidPrefix));
+
+        let mut off_curve = sec;
+        off_curve[64] ^= 1;
+        assert_eq!(S256Point::parse(&off_curve), Err(ParseError::NotOnCurve));
+
+        let mut too_large = [0u8; 33];
+        too_large[0] = 0x02;
+        P.to_big_endian(&mut too_large[1..]);
+        assert_eq!(
+            S256Point::parse(&too_large),
+            Err(ParseError::InvalidCoordinate)
+        );
+
+        // x^3 + 7 が平方剰余でない x には対応する点がない
+        let x = (1..)
+            .map(S256Field::from)
+            .find(|x| !(x.cube() + S256Field::from(7)).is_square())
+            .unwrap();
+        let mut no_root = [0x02u8; 33];
+        no_root[1..].copy_from_slice(&x.to_be_bytes());
+        assert_eq!(S256Point::parse(&no_root), Err(ParseError::NotOnCurve));
+    }
+
+    #[test]
+    fn hash_to_curve() {
+        let a = S256Point::hash_to_curve(b"Pedersen generator H");
+        let b = S256Point::hash_to_curve(b"Pedersen generator J");
+
+        assert!(a.to_point().is_on_curve());
+        assert!(a.has_even_y());
+        assert_eq!(a, S256Point::hash_to_curve(b"Pedersen generator H"));
+        assert_ne!(a, b);
+        assert_ne!(a, S256Point::generator());
+        assert!(!S256Point::hash_to_curve(b"").is_infinity());
+    }
+
+    #[test]
+    fn tweak() {
+        let g = S256Point::generator();
+        let three = Scalar::from(3);
+
+        assert_eq!(g.add_tweak(&three), Ok(g * U256::from(4)));
+        assert_eq!(g.mul_tweak(&three), Ok(g * U256::from(3)));
+        assert_eq!(
+            g.add_tweak(&-Scalar::from(1)),
+            Err(TweakError::InvalidResult)
+        );
+        assert_eq!(g.mul_tweak(&Scalar::from(0)), Err(TweakError::ZeroTweak));
+        assert_eq!(
+            S256Point::INFINITY.mul_tweak(&three),
+            Err(TweakError::InvalidResult)
+        );
+    }
+
+    #[test]
+    fn identifier() {
+        let g = S256Point::generator();
+        assert_eq!(
+            encode(g.identifier()),
+            "751e76e8199196d454941c45d1b3a323f1433bd6"
+        );
+        assert_eq!(g.fingerprint(), [0x75, 0x1e, 0x76, 0xe8]);
+    }
+
+    #[test]
+    fn address() {
+        // 本の練習問題
+        let cases = [
+            (
+                U256::from(5002),
+                false,
+                Network::Testnet,
+                "mmTPbXQFxboEtNRkwfh6K51jvdtHLxGeMA",
+            ),
+            (
+                U256::from(2020).pow(U256::from(5)),
+                true,
+                Network::Testnet,
+                "mopVkxp8UhXqRYbCYJsbeE1h1fiF64jcoH",
+            ),
+            (
+                U256::from(0x12345deadbeefu64),
+                true,
+                Network::Mainnet,
+                "1F1Pn2y6pDb68E5nYJJeba4TLg2U7B6KF1",
+            ),
+        ];
+        for (secret, compressed, network, expected) in cases {
+            let point = S256Point::generator() * secret;
+            assert_eq!(point.address(compressed, network), expected);
+        }
+    }
+}
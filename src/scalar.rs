@@ -0,0 +1,283 @@
+use std::fmt;
+use std::ops::{Add, Div, Mul, Neg, Sub};
+use std::sync::OnceLock;
+
+use primitive_types::{U256, U512};
+use rand::RngCore;
+
+use crate::ct::{Choice, ConditionallySelectable, ConstantTimeEq};
+use crate::error::FieldError;
+use crate::field_element::{FieldElement, One};
+use crate::hash::tagged_hash;
+use crate::modular::Exponent;
+use crate::montgomery::MontgomeryContext;
+use crate::random;
+use crate::secp256k1::N;
+
+static N_CONTEXT: OnceLock<MontgomeryContext> = OnceLock::new();
+
+/// Integer modulo the secp256k1 group order `N`.
+///
+/// Private keys, nonces and the `r`, `s`, `u`, `v` of ECDSA live in this
+/// ring, not in the base field of `S256Field`; keeping them as distinct
+/// types makes mixing up the two moduli a compile error.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct Scalar(FieldElement<U256>);
+
+impl Scalar {
+    pub fn try_new(num: U256) -> Result<Self, FieldError> {
+        FieldElement::try_new(num, N).map(Self)
+    }
+
+    pub fn new(num: U256) -> Self {
+        Self(FieldElement::new(num, N))
+    }
+
+    /// `num % N`, for values such as message hashes that may exceed `N`.
+    pub fn reduce(num: U256) -> Self {
+        Self::new(num % N)
+    }
+
+    /// Parses exactly 32 big-endian bytes, rejecting values `>= N`.
+    pub fn from_be_bytes(bytes: &[u8]) -> Result<Self, FieldError> {
+        FieldElement::from_be_bytes(bytes, N).map(Self)
+    }
+
+    /// Interprets 32 big-endian bytes as an integer and reduces it modulo
+    /// `N`, the `bits2octets` step of RFC 6979 for secp256k1.
+    pub fn from_be_bytes_reduced(bytes: &[u8; 32]) -> Self {
+        Self::reduce(U256::from_big_endian(bytes))
+    }
+
+    /// Reduces a 512-bit big-endian value modulo `N`. The bias of reducing
+    /// 64 uniform bytes is negligible, which makes this suitable for
+    /// deriving scalars from hash output.
+    pub fn from_wide_be_bytes(bytes: &[u8; 64]) -> Self {
+        let wide = U512::from_big_endian(bytes) % U512::from(N);
+        Self::new(U256::try_from(wide).expect("remainder is smaller than N"))
+    }
+
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        self.0.to_be_bytes()
+    }
+
+    /// Uniformly random non-zero scalar.
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R) -> Self {
+        Self::new(random::uniform_range(rng, U256::one(), N))
+    }
+
+    pub fn num(&self) -> U256 {
+        self.0.num
+    }
+
+    pub fn is_zero(&self) -> bool {
+        self.0.num.is_zero()
+    }
+
+    pub fn pow<E: Exponent<U256>>(&self, exponent: E) -> Self {
+        Self(self.0.pow(exponent))
+    }
+
+    /// Multiplicative inverse modulo `N`; panics for zero.
+    ///
+    /// Computed as `self^(N-2)` in Montgomery form: `N` has no special shape
+    /// to reduce by, so this avoids a 512-by-256-bit division in each of
+    /// the ~500 multiplications.
+    pub fn inverse(&self) -> Self {
+        if self.is_zero() {
+            panic!("Zero has no multiplicative inverse")
+        }
+        let ctx = N_CONTEXT.get_or_init(|| MontgomeryContext::new(N));
+        let inverse = ctx.pow(ctx.to_montgomery(&self.0), N - U256::from(2));
+        Self(ctx.from_montgomery(inverse))
+    }
+}
+
+/// A tagged hash of `data` reduced modulo `N`, for challenges and
+/// coefficients of the multi-party protocols.
+pub(crate) fn hash_to_scalar(tag: &str, data: &[u8]) -> Scalar {
+    Scalar::from_be_bytes_reduced(&tagged_hash(tag, data))
+}
+
+impl One for Scalar {
+    fn one(&self) -> Self {
+        Self(self.0.one())
+    }
+}
+
+impl ConstantTimeEq for Scalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+impl ConditionallySelectable for Scalar {
+    fn conditional_select(a: &Self, b: &Self, choice: Choice) -> Self {
+        Self(FieldElement::conditional_select(&a.0, &b.0, choice))
+    }
+}
+
+impl From<u64> for Scalar {
+    fn from(num: u64) -> Self {
+        Self::new(U256::from(num))
+    }
+}
+
+impl TryFrom<U256> for Scalar {
+    type Error = FieldError;
+
+    fn try_from(num: U256) -> Result<Self, Self::Error> {
+        Self::try_new(num)
+    }
+}
+
+impl From<Scalar> for U256 {
+    fn from(scalar: Scalar) -> Self {
+        scalar.num()
+    }
+}
+
+impl fmt::Display for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+impl fmt::LowerHex for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::LowerHex::fmt(&self.0, f)
+    }
+}
+
+impl fmt::UpperHex for Scalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::UpperHex::fmt(&self.0, f)
+    }
+}
+
+impl Add for Scalar {
+    type Output = Self;
+
+    fn add(self, other: Self) -> Self::Output {
+        Self(self.0 + other.0)
+    }
+}
+
+impl Sub for Scalar {
+    type Output = Self;
+
+    fn sub(self, other: Self) -> Self::Output {
+        Self(self.0 - other.0)
+    }
+}
+
+impl Mul for Scalar {
+    type Output = Self;
+
+    fn mul(self, other: Self) -> Self::Output {
+        Self(self.0 * other.0)
+    }
+}
+
+impl Div for Scalar {
+    type Output = Self;
+
+    fn div(self, other: Self) -> Self::Output {
+        Self(self.0 / other.0)
+    }
+}
+
+impl Neg for Scalar {
+    type Output = Self;
+
+    fn neg(self) -> Self::Output {
+        Self(-self.0)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl serde::Serialize for Scalar {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        crate::serde_hex::HexU256(self.num()).serialize(serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Scalar {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let crate::serde_hex::HexU256(num) = serde::Deserialize::deserialize(deserializer)?;
+        Self::try_new(num).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Scalar;
+    use crate::error::FieldError;
+    use crate::secp256k1::N;
+    use primitive_types::U256;
+
+    #[test]
+    fn new_and_reduce() {
+        assert!(Scalar::try_new(N - U256::one()).is_ok());
+        assert_eq!(Scalar::try_new(N), Err(FieldError::NotInRange));
+        assert_eq!(Scalar::reduce(N + U256::from(5)), Scalar::from(5));
+        assert_eq!(Scalar::reduce(U256::MAX), Scalar::new(U256::MAX - N));
+    }
+
+    #[test]
+    fn arithmetic() {
+        let a = Scalar::new(N - U256::one());
+        let b = Scalar::from(2);
+
+        // (-1) + 2 = 1, (-1) * (-1) = 1
+        assert_eq!(a + b, Scalar::from(1));
+        assert_eq!(a * a, Scalar::from(1));
+        assert_eq!(-a, Scalar::from(1));
+        assert_eq!(b - Scalar::from(3), a);
+        assert_eq!(b * b.inverse(), Scalar::from(1));
+        assert_eq!(Scalar::from(6) / b, Scalar::from(3));
+    }
+
+    #[test]
+    fn inverse_matches_plain_path() {
+        for a in [
+            Scalar::from(1),
+            Scalar::from(2),
+            Scalar::new(N - U256::one()),
+        ] {
+            assert_eq!(a.inverse().0, a.0.inverse());
+        }
+        let a = Scalar::from_be_bytes_reduced(&[0xa5; 32]);
+        assert_eq!(a.inverse().0, a.0.inverse());
+        assert_eq!(a * a.inverse(), Scalar::from(1));
+    }
+
+    #[test]
+    fn bytes() {
+        let a = Scalar::from(0x1234);
+        let bytes = a.to_be_bytes();
+
+        assert_eq!(Scalar::from_be_bytes(&bytes), Ok(a));
+        assert_eq!(
+            Scalar::from_be_bytes(&[0xff; 32]),
+            Err(FieldError::NotInRange)
+        );
+        assert_eq!(
+            Scalar::from_be_bytes_reduced(&[0xff; 32]),
+            Scalar::reduce(U256::MAX)
+        );
+
+        let mut wide = [0u8; 64];
+        wide[32..].copy_from_slice(&bytes);
+        assert_eq!(Scalar::from_wide_be_bytes(&wide), a);
+    }
+
+    #[test]
+    fn random_is_nonzero() {
+        let mut rng = rand::thread_rng();
+        for _ in 0..16 {
+            assert!(!Scalar::random(&mut rng).is_zero());
+        }
+    }
+}
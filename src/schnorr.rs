@@ -0,0 +1,343 @@
+//! BIP340 Schnorr signatures over secp256k1.
+//!
+//! Keys are x-only and nonce points always have an even y, so a signature
+//! is just `R.x || s` and verification is the single equation
+//! `s * G == R + e * P`, where `e` is a tagged hash of `R.x`, `P.x` and the
+//! message.
+
+use std::fmt;
+
+use crate::curve::CurvePoint;
+use crate::error::ParseError;
+use crate::hash::tagged_hash;
+use crate::private_key::PrivateKey;
+use crate::s256_field::S256Field;
+use crate::s256_point::S256Point;
+use crate::scalar::Scalar;
+use crate::xonly::XOnlyPublicKey;
+
+/// A BIP340 signature: the x coordinate of the nonce point `R` and `s`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SchnorrSignature {
+    pub r: S256Field,
+    pub s: Scalar,
+}
+
+impl SchnorrSignature {
+    pub fn serialize(&self) -> [u8; 64] {
+        let mut ret = [0u8; 64];
+        ret[..32].copy_from_slice(&self.r.to_be_bytes());
+        ret[32..].copy_from_slice(&self.s.to_be_bytes());
+        ret
+    }
+
+    /// Parses `R.x || s`. `R.x` need not be on the curve here; such a
+    /// signature simply fails to verify.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != 64 {
+            return Err(ParseError::InvalidLength);
+        }
+        Ok(Self {
+            r: S256Field::try_from(&bytes[..32]).map_err(|_| ParseError::InvalidCoordinate)?,
+            s: Scalar::from_be_bytes(&bytes[32..]).map_err(|_| ParseError::InvalidScalar)?,
+        })
+    }
+}
+
+impl fmt::Display for SchnorrSignature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:064x}{:064x}", self.r, self.s)
+    }
+}
+
+/// `e = hash_BIP0340/challenge(R.x || P.x || msg) mod N`.
+pub(crate) fn challenge(r: &S256Field, key: &XOnlyPublicKey, msg: &[u8]) -> Scalar {
+    let mut data = r.to_be_bytes().to_vec();
+    data.extend_from_slice(&key.serialize());
+    data.extend_from_slice(msg);
+    Scalar::from_be_bytes_reduced(&tagged_hash("BIP0340/challenge", &data))
+}
+
+/// Signs `msg` with `key` per BIP340.
+///
+/// `aux_rand` should be 32 fresh random bytes; they are hashed into the
+/// nonce as protection against side channels. The nonce stays safe even
+/// if they are all zero, since it is also derived from the key and `msg`.
+pub fn sign(key: &PrivateKey, msg: &[u8], aux_rand: &[u8; 32]) -> SchnorrSignature {
+    let point = key.point();
+    let pubkey = XOnlyPublicKey::from_point(&point).expect("never the point at infinity");
+    // 公開鍵の y が奇数なら秘密鍵を反転して偶数の y に合わせる
+    let d = if point.has_even_y() {
+        key.to_scalar()
+    } else {
+        -key.to_scalar()
+    };
+
+    let aux = tagged_hash("BIP0340/aux", aux_rand);
+    let mut data: Vec<u8> = d
+        .to_be_bytes()
+        .iter()
+        .zip(aux.iter())
+        .map(|(a, b)| a ^ b)
+        .collect();
+    data.extend_from_slice(&pubkey.serialize());
+    data.extend_from_slice(msg);
+    let k = Scalar::from_be_bytes_reduced(&tagged_hash("BIP0340/nonce", &data));
+    assert!(!k.is_zero(), "nonce hash is zero");
+
+    let nonce_point = PrivateKey::new(k).point();
+    let k = if nonce_point.has_even_y() { k } else { -k };
+    let r = nonce_point.x().expect("never the point at infinity");
+    let e = challenge(&r, &pubkey, msg);
+
+    SchnorrSignature { r, s: k + e * d }
+}
+
+/// Checks a BIP340 signature of `msg` against `key`.
+pub fn verify(key: &XOnlyPublicKey, msg: &[u8], sig: &SchnorrSignature) -> bool {
+    let e = challenge(&sig.r, key, msg);
+
+    // R = s * G - e * P
+    let r = S256Point::from(CurvePoint::multi_mul(&[
+        (sig.s.num(), S256Point::generator().into()),
+        ((-e).num(), key.lift_x().into()),
+    ]));
+    !r.is_infinity() && r.has_even_y() && r.x() == Some(sig.r)
+}
+
+/// Checks many `(msg, key, sig)` triples at once; `true` only if every
+/// signature is valid.
+///
+/// The equations `s_i * G == R_i + e_i * P_i` are combined with random
+/// weights `a_i` into one multi-scalar multiplication
+/// `(sum a_i s_i) * G - sum a_i R_i - sum (a_i e_i) P_i == O`, which is much
+/// cheaper than verifying one by one. Following BIP340, the weights come
+/// from a hash of all inputs, so a forger cannot predict them and the
+/// result is reproducible.
+pub fn verify_batch(items: &[(&[u8], XOnlyPublicKey, SchnorrSignature)]) -> bool {
+    let mut seed = Vec::new();
+    for (msg, key, sig) in items {
+        seed.extend_from_slice(&key.serialize());
+        seed.extend_from_slice(&sig.serialize());
+        seed.extend_from_slice(&tagged_hash("BIP0340/batch", msg));
+    }
+    let seed = tagged_hash("BIP0340/batch", &seed);
+
+    let mut s_sum = Scalar::from(0);
+    let mut terms = Vec::with_capacity(2 * items.len() + 1);
+    for (i, (msg, key, sig)) in items.iter().enumerate() {
+        // 最初の重みは 1 でよい
+        let a = if i == 0 {
+            Scalar::from(1)
+        } else {
+            let mut data = seed.to_vec();
+            data.extend_from_slice(&(i as u64).to_be_bytes());
+            Scalar::from_be_bytes_reduced(&tagged_hash("BIP0340/batch", &data))
+        };
+        let r = match S256Point::lift_x(&sig.r) {
+            Some(r) => r,
+            None => return false,
+        };
+        let e = challenge(&sig.r, key, msg);
+
+        s_sum = s_sum + a * sig.s;
+        terms.push(((-a).num(), r.into()));
+        terms.push(((-(a * e)).num(), key.lift_x().into()));
+    }
+    terms.push((s_sum.num(), S256Point::generator().into()));
+
+    CurvePoint::multi_mul(&terms).is_infinity()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{sign, verify, verify_batch, SchnorrSignature};
+    use crate::error::ParseError;
+    use crate::private_key::PrivateKey;
+    use crate::scalar::Scalar;
+    use crate::xonly::XOnlyPublicKey;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn encode(bytes: &[u8]) -> String {
+        bytes.iter().map(|b| format!("{:02X}", b)).collect()
+    }
+
+    #[test]
+    fn signing_vectors() {
+        // BIP340 test-vectors.csv の 0 から 3
+        let cases = [
+            (
+                "0000000000000000000000000000000000000000000000000000000000000003",
+                "F9308A019258C31049344F85F89D5229B531C845836F99B08601F113BCE036F9",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "0000000000000000000000000000000000000000000000000000000000000000",
+                "E907831F80848D1069A5371B402410364BDF1C5F8307B0084C55F1CE2DCA8215\
+                 25F66A4A85EA8B71E482A74F382D2CE5EBEEE8FDB2172F477DF4900D310536C0",
+            ),
+            (
+                "B7E151628AED2A6ABF7158809CF4F3C762E7160F38B4DA56A784D9045190CFEF",
+                "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+                "0000000000000000000000000000000000000000000000000000000000000001",
+                "243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89",
+                "6896BD60EEAE296DB48A229FF71DFE071BDE413E6D43F917DC8DCF8C78DE3341\
+                 8906D11AC976ABCCB20B091292BFF4EA897EFCB639EA871CFA95F6DE339E4B0A",
+            ),
+            (
+                "C90FDAA22168C234C4C6628B80DC1CD129024E088A67CC74020BBEA63B14E5C9",
+                "DD308AFEC5777E13121FA72B9CC1B7CC0139715309B086C960E18FD969774EB8",
+                "C87AA53824B4D7AE2EB035A2B5BBBCCC080E76CDC6D1692C4B0B62D798E6D906",
+                "7E2D58D8B3BCDF1ABADEC7829054F90DDA9805AAB56C77333024B9D0A508B75C",
+                "5831AAEED7B44BB74E5EAB94BA9D4294C49BCF2A60728D8B4C200F50DD313C1B\
+                 AB745879A5AD954A72C45A91C3A51D3C7ADEA98D82F8481E0E1E03674A6F3FB7",
+            ),
+            (
+                "0B432B2677937381AEF05BB02A66ECD012773062CF3FA2549E44F58ED2401710",
+                "25D1DFF95105F5253C4022F628A996AD3A0D95FBF21D468A1B33F8C160D8F517",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFF",
+                "7EB0509757E246F19449885651611CB965ECC1A187DD51B64FDA1EDC9637D5EC\
+                 97582B9CB13DB3933705B32BA982AF5AF25FD78881EBB32771FC5922EFC66EA3",
+            ),
+        ];
+        for (secret, pubkey, aux, msg, expected) in cases {
+            let key = PrivateKey::new(Scalar::from_be_bytes(&hex(secret)).unwrap());
+            let pubkey = XOnlyPublicKey::parse(&hex(pubkey)).unwrap();
+            assert_eq!(XOnlyPublicKey::from_point(&key.point()), Some(pubkey));
+
+            let aux: [u8; 32] = hex(aux).try_into().unwrap();
+            let msg = hex(msg);
+            let sig = sign(&key, &msg, &aux);
+            assert_eq!(encode(&sig.serialize()), expected);
+            assert!(verify(&pubkey, &msg, &sig));
+        }
+    }
+
+    #[test]
+    fn verification_vectors() {
+        let key = XOnlyPublicKey::parse(&hex(
+            "DFF1D77F2A671C5F36183726DB2341BE58FEAE1DA2DECED843240F7B502BA659",
+        ))
+        .unwrap();
+        let msg = hex("243F6A8885A308D313198A2E03707344A4093822299F31D0082EFA98EC4E6C89");
+        let sig = |s: &str| SchnorrSignature::parse(&hex(s)).unwrap();
+
+        // ベクタ 4: R.x の上位が 0 の正しい署名
+        let key4 = XOnlyPublicKey::parse(&hex(
+            "D69C3509BB99E412E68B0FE8544E72837DFA30746D8BE2AA65975F29D22DC7B9",
+        ))
+        .unwrap();
+        assert!(verify(
+            &key4,
+            &hex("4DF3C3F68FCC83B27E9D42C90431A72499F17875C81A599B566C9889B9696703"),
+            &sig(
+                "00000000000000000000003B78CE563F89A0ED9414F5AA28AD0D96D6795F9C63\
+                  76AFB1548AF603B3EB45C9F8207DEE1060CB71C04E80F593060B07D28308D7F4"
+            )
+        ));
+
+        // ベクタ 6: R の y が奇数
+        assert!(!verify(
+            &key,
+            &msg,
+            &sig(
+                "FFF97BD5755EEEA420453A14355235D382F6472F8568A18B2F057A1460297556\
+                  3CC27944640AC607CD107AE10923D9EF7A73C643E166BE5EBEAFA34B1AC553E2"
+            )
+        ));
+        // ベクタ 7: メッセージが反転している
+        assert!(!verify(
+            &key,
+            &msg,
+            &sig(
+                "1FA62E331EDBC21C394792D2AB1100A7B432B013DF3F6FF4F99FCB33E0E1515F\
+                  28890B3EDB6E7189B630448B515CE4F8622A954CFE545735AAEA5134FCCDB2BD"
+            )
+        ));
+        // ベクタ 8: s が反転している
+        assert!(!verify(
+            &key,
+            &msg,
+            &sig(
+                "6CFF5C3BA86C69EA4B7376F31A9BCB4F74C1976089B2D9963DA2E5543E177769\
+                  961764B3AA9B2FFCB6EF947B6887A226E8D7C93E00C5ED0C1834FF0D0C2E6DA6"
+            )
+        ));
+
+        // ベクタ 5: 曲線上にない公開鍵
+        assert!(XOnlyPublicKey::parse(&hex(
+            "EEFDEA4CDB677750A420FEE807EACF21EB9898AE79B9768766E4FAA04A2D4A34"
+        ))
+        .is_err());
+    }
+
+    #[test]
+    fn parse_rejects_out_of_range() {
+        let mut bytes = [0xffu8; 64];
+        assert_eq!(
+            SchnorrSignature::parse(&bytes),
+            Err(ParseError::InvalidCoordinate)
+        );
+        bytes[..32].copy_from_slice(&[0; 32]);
+        assert_eq!(
+            SchnorrSignature::parse(&bytes),
+            Err(ParseError::InvalidScalar)
+        );
+        assert_eq!(
+            SchnorrSignature::parse(&bytes[..63]),
+            Err(ParseError::InvalidLength)
+        );
+    }
+
+    #[test]
+    fn batch_verification() {
+        let keys: Vec<PrivateKey> = (1..=12u64)
+            .map(|i| PrivateKey::new(Scalar::from(i * 7919)))
+            .collect();
+        let msgs: Vec<Vec<u8>> = (0..12u8).map(|i| vec![i; i as usize]).collect();
+        let mut items: Vec<(&[u8], XOnlyPublicKey, SchnorrSignature)> = keys
+            .iter()
+            .zip(&msgs)
+            .map(|(key, msg)| {
+                let pubkey = XOnlyPublicKey::from_point(&key.point()).unwrap();
+                (&msg[..], pubkey, sign(key, msg, &[0; 32]))
+            })
+            .collect();
+
+        assert!(verify_batch(&items));
+        assert!(verify_batch(&items[..1]));
+        assert!(verify_batch(&[]));
+
+        // 1 つでも不正な署名が混ざれば失敗する
+        let mut bad = items.clone();
+        bad[5].2.s = bad[5].2.s + Scalar::from(1);
+        assert!(!verify_batch(&bad));
+
+        // 2 つの署名の誤差が打ち消し合うようにずらしても検出できる
+        let (s3, s4) = (items[3].2.s, items[4].2.s);
+        let mut swapped = items.clone();
+        swapped[3].2.s = s3 + Scalar::from(1);
+        swapped[4].2.s = s4 - Scalar::from(1);
+        assert!(!verify_batch(&swapped));
+
+        items[7].0 = b"other message";
+        assert!(!verify_batch(&items));
+    }
+
+    #[test]
+    fn variable_length_messages() {
+        let key = PrivateKey::new(Scalar::from(42));
+        let pubkey = XOnlyPublicKey::from_point(&key.point()).unwrap();
+        for msg in [&b""[..], b"a", &[0x5a; 100]] {
+            let sig = sign(&key, msg, &[7; 32]);
+            assert!(verify(&pubkey, msg, &sig));
+            assert_eq!(SchnorrSignature::parse(&sig.serialize()), Ok(sig));
+        }
+        assert!(!verify(&pubkey, b"b", &sign(&key, b"a", &[7; 32])));
+    }
+}
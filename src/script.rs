@@ -0,0 +1,197 @@
+//! Bitcoin scripts as raw bytes.
+
+use std::fmt;
+use std::io::{self, Read, Write};
+
+use crate::encoding::{read_varint, write_varint};
+use crate::hex;
+
+// 標準のテンプレートに現れるオペコード
+pub(crate) const OP_0: u8 = 0x00;
+pub(crate) const OP_1: u8 = 0x51;
+pub(crate) const OP_RETURN: u8 = 0x6a;
+pub(crate) const OP_DUP: u8 = 0x76;
+pub(crate) const OP_EQUAL: u8 = 0x87;
+pub(crate) const OP_EQUALVERIFY: u8 = 0x88;
+pub(crate) const OP_HASH160: u8 = 0xa9;
+pub(crate) const OP_CHECKSIG: u8 = 0xac;
+
+/// The standard `script_pubkey` templates.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScriptType {
+    /// `<pubkey> OP_CHECKSIG`, with a 33- or 65-byte SEC key.
+    P2pk,
+    /// `OP_DUP OP_HASH160 <20 bytes> OP_EQUALVERIFY OP_CHECKSIG`.
+    P2pkh,
+    /// `OP_HASH160 <20 bytes> OP_EQUAL`.
+    P2sh,
+    /// `OP_0 <20 bytes>`.
+    P2wpkh,
+    /// `OP_0 <32 bytes>`.
+    P2wsh,
+    /// `OP_1 <32 bytes>`.
+    P2tr,
+    /// `OP_RETURN ...`, a provably unspendable data carrier.
+    NullData,
+    /// Anything else.
+    NonStandard,
+}
+
+/// A serialized script, e.g. a `script_pubkey` or redeem script, without
+/// the length prefix it carries inside a transaction.
+#[derive(Clone, Default, PartialEq, Eq, Hash)]
+pub struct Script(Vec<u8>);
+
+impl Script {
+    pub fn new(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+
+    pub fn as_bytes(&self) -> &[u8] {
+        &self.0
+    }
+
+    /// Reads a varint length-prefixed script, as it appears in a
+    /// transaction.
+    pub fn parse<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        let len = read_varint(reader)?;
+        // 長さは信用せず、実際に読めた分だけ確保する
+        let mut bytes = Vec::new();
+        reader.take(len).read_to_end(&mut bytes)?;
+        if (bytes.len() as u64) < len {
+            return Err(io::ErrorKind::UnexpectedEof.into());
+        }
+        Ok(Self(bytes))
+    }
+
+    /// Writes the script with its varint length prefix, as `parse` reads it.
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        write_varint(writer, self.0.len() as u64)?;
+        writer.write_all(&self.0)
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.0
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Which standard template this script follows, judged by its shape
+    /// alone: keys and hashes inside are not checked.
+    pub fn script_type(&self) -> ScriptType {
+        let bytes = &self.0[..];
+        match (bytes.len(), bytes) {
+            (35, [33, 0x02 | 0x03, .., OP_CHECKSIG]) | (67, [65, 0x04, .., OP_CHECKSIG]) => {
+                ScriptType::P2pk
+            }
+            (25, [OP_DUP, OP_HASH160, 20, .., OP_EQUALVERIFY, OP_CHECKSIG]) => ScriptType::P2pkh,
+            (23, [OP_HASH160, 20, .., OP_EQUAL]) => ScriptType::P2sh,
+            (22, [OP_0, 20, ..]) => ScriptType::P2wpkh,
+            (34, [OP_0, 32, ..]) => ScriptType::P2wsh,
+            (34, [OP_1, 32, ..]) => ScriptType::P2tr,
+            (_, [OP_RETURN, ..]) => ScriptType::NullData,
+            _ => ScriptType::NonStandard,
+        }
+    }
+}
+
+impl From<Vec<u8>> for Script {
+    fn from(bytes: Vec<u8>) -> Self {
+        Self(bytes)
+    }
+}
+
+impl fmt::Debug for Script {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Script({})", hex::encode(&self.0))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{Script, ScriptType};
+    use crate::hex;
+
+    #[test]
+    fn bytes_and_debug() {
+        let script = Script::from(vec![0x6a, 0x01, 0xff]);
+        assert_eq!(script.len(), 3);
+        assert!(!script.is_empty());
+        assert!(Script::default().is_empty());
+        assert_eq!(format!("{:?}", script), "Script(6a01ff)");
+        assert_eq!(script.into_bytes(), [0x6a, 0x01, 0xff]);
+    }
+
+    #[test]
+    fn parse() {
+        let mut reader = &[0x03, 0x51, 0x52, 0x53, 0xff][..];
+        let script = Script::parse(&mut reader).unwrap();
+        assert_eq!(script.as_bytes(), [0x51, 0x52, 0x53]);
+        assert_eq!(reader, [0xff]);
+
+        let mut serialized = Vec::new();
+        script.write_to(&mut serialized).unwrap();
+        assert_eq!(serialized, [0x03, 0x51, 0x52, 0x53]);
+
+        let err = Script::parse(&mut &[0x03, 0x51, 0x52][..]).unwrap_err();
+        assert_eq!(err.kind(), std::io::ErrorKind::UnexpectedEof);
+    }
+
+    #[test]
+    fn script_types() {
+        let cases = [
+            // 本の第 6 章のジェネシスブロックの P2PK 出力
+            (
+                "4104678afdb0fe5548271967f1a67130b7105cd6a828e03909a67962e0ea1f61deb6\
+                 49f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf11d5fac",
+                ScriptType::P2pk,
+            ),
+            (
+                "2103b287eaf122eea69030a0e9feed096bed8045c8b98bec453e1ffac7fbdbd4bb71ac",
+                ScriptType::P2pk,
+            ),
+            (
+                "76a914f54a5851e9372b87810a8e60cdd2e7cfd80b6e3188ac",
+                ScriptType::P2pkh,
+            ),
+            (
+                "a91474d691da1574e6b3c192ecfb52cc8984ee7b6c5687",
+                ScriptType::P2sh,
+            ),
+            (
+                "0014751e76e8199196d454941c45d1b3a323f1433bd6",
+                ScriptType::P2wpkh,
+            ),
+            (
+                "00201863143c14c5166804bd19203356da136c985678cd4d27a1b8c6329604903262",
+                ScriptType::P2wsh,
+            ),
+            (
+                "512079be667ef9dcbbac55a06295ce870b07029bfcdb2dce28d959f2815b16f81798",
+                ScriptType::P2tr,
+            ),
+            ("6a0b68656c6c6f20776f726c64", ScriptType::NullData),
+            ("6a", ScriptType::NullData),
+            // 長さが合わない P2PKH や未知の segwit バージョン
+            (
+                "76a914f54a5851e9372b87810a8e60cdd2e7cfd80b6e31ac",
+                ScriptType::NonStandard,
+            ),
+            (
+                "5210751e76e8199196d454941c45d1b3a323",
+                ScriptType::NonStandard,
+            ),
+            ("", ScriptType::NonStandard),
+        ];
+        for (script, expected) in cases {
+            let script = Script::new(hex::decode(script).unwrap());
+            assert_eq!(script.script_type(), expected, "{:?}", script);
+        }
+    }
+}
@@ -0,0 +1,125 @@
+use std::sync::OnceLock;
+
+use primitive_types::U256;
+
+use crate::elliptic::Point;
+use crate::jacobian::FixedBaseTable;
+use crate::s256_field::S256Field;
+use crate::s256_point::S256Point;
+use crate::secret::SecretScalar;
+
+/// Field prime `p = 2^256 - 2^32 - 977`.
+pub const P: U256 = U256([
+    0xFFFF_FFFE_FFFF_FC2F,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+    0xFFFF_FFFF_FFFF_FFFF,
+]);
+
+/// Order of the generator `G`.
+pub const N: U256 = U256([
+    0xBFD2_5E8C_D036_4141,
+    0xBAAE_DCE6_AF48_A03B,
+    0xFFFF_FFFF_FFFF_FFFE,
+    0xFFFF_FFFF_FFFF_FFFF,
+]);
+
+/// x coordinate of the generator `G`.
+pub const G_X: U256 = U256([
+    0x59F2_815B_16F8_1798,
+    0x029B_FCDB_2DCE_28D9,
+    0x55A0_6295_CE87_0B07,
+    0x79BE_667E_F9DC_BBAC,
+]);
+
+/// y coordinate of the generator `G`.
+pub const G_Y: U256 = U256([
+    0x9C47_D08F_FB10_D4B8,
+    0xFD17_B448_A685_5419,
+    0x5DA4_FBFC_0E11_08A8,
+    0x483A_DA77_26A3_C465,
+]);
+
+/// The generator `G` of secp256k1 (`y^2 = x^3 + 7`).
+pub fn generator() -> Point<S256Field> {
+    Point::new(
+        S256Field::new(G_X),
+        S256Field::new(G_Y),
+        S256Field::from(0),
+        S256Field::from(7),
+    )
+}
+
+static GENERATOR_TABLE: OnceLock<FixedBaseTable<S256Field>> = OnceLock::new();
+
+/// `scalar * G` through a table of multiples of `G` built on first use.
+///
+/// Constant time in the scalar and several times faster than the generic
+/// ladder, so this is the path for deriving public keys and nonce points.
+pub fn mul_generator(scalar: &SecretScalar) -> Point<S256Field> {
+    GENERATOR_TABLE
+        .get_or_init(|| FixedBaseTable::new(&generator()))
+        .mul(scalar.expose_secret())
+}
+
+/// `mul_generator` for secret keys, shares and nonces, as an `S256Point`.
+pub(crate) fn generator_mul(scalar: &SecretScalar) -> S256Point {
+    S256Point::try_from(mul_generator(scalar)).expect("multiples of G are on the curve")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{generator, mul_generator, G_X, G_Y, N, P};
+    use crate::elliptic::Point;
+    use crate::secret::SecretScalar;
+    use primitive_types::U256;
+
+    #[test]
+    fn prime() {
+        let expected = U256::from_str_radix(
+            "FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEFFFFFC2F",
+            16,
+        )
+        .unwrap();
+        assert_eq!(P, expected);
+    }
+
+    #[test]
+    fn generator_constants() {
+        let hex = |s: &str| U256::from_str_radix(s, 16).unwrap();
+
+        assert_eq!(
+            N,
+            hex("FFFFFFFFFFFFFFFFFFFFFFFFFFFFFFFEBAAEDCE6AF48A03BBFD25E8CD0364141")
+        );
+        assert_eq!(
+            G_X,
+            hex("79BE667EF9DCBBAC55A06295CE870B07029BFCDB2DCE28D959F2815B16F81798")
+        );
+        assert_eq!(
+            G_Y,
+            hex("483ADA7726A3C4655DA4FBFC0E1108A8FD17B448A68554199C47D08FFB10D4B8")
+        );
+        assert_eq!(generator() * N, Point::Infinity);
+    }
+
+    #[test]
+    fn mul_generator_matches_ladder() {
+        let g = generator();
+        for k in [
+            U256::one(),
+            U256::from(2),
+            U256::from(0xdeadbeefu64),
+            N - U256::one(),
+            U256::from_str_radix(
+                "3D8A1C5E9F70B2461E0D7C93A5B8F2106C4E9D7A2B1F3E5C8D0A6B4927F1E3C5",
+                16,
+            )
+            .unwrap(),
+        ] {
+            let secret = SecretScalar::new(k);
+            assert_eq!(mul_generator(&secret), g.mul_secret(&secret));
+        }
+        assert_eq!(mul_generator(&SecretScalar::new(N)), Point::Infinity);
+    }
+}
@@ -0,0 +1,114 @@
+use std::fmt;
+
+use primitive_types::U256;
+use rand::RngCore;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+use crate::ct::{Choice, ConstantTimeEq};
+use crate::random;
+
+/// A secret 256-bit integer (private key, nonce) that is wiped from memory
+/// when dropped.
+///
+/// It is deliberately not `Copy` and its `Debug` output is redacted, so the
+/// value only leaves the wrapper through `expose_secret`.
+#[derive(Clone)]
+pub struct SecretScalar(U256);
+
+impl SecretScalar {
+    pub fn new(value: U256) -> Self {
+        Self(value)
+    }
+
+    /// Uniformly random secret in `[1, bound)`.
+    pub fn random<R: RngCore + ?Sized>(rng: &mut R, bound: U256) -> Self {
+        Self(random::uniform_range(rng, U256::one(), bound))
+    }
+
+    pub fn expose_secret(&self) -> &U256 {
+        &self.0
+    }
+
+    /// 32-byte big-endian encoding; wiping the copy is up to the caller.
+    pub fn to_be_bytes(&self) -> [u8; 32] {
+        let mut bytes = [0u8; 32];
+        self.0.to_big_endian(&mut bytes);
+        bytes
+    }
+}
+
+impl ConstantTimeEq for SecretScalar {
+    fn ct_eq(&self, other: &Self) -> Choice {
+        self.0.ct_eq(&other.0)
+    }
+}
+
+/// Equality goes through `ct_eq` so comparing secrets never short-circuits.
+impl PartialEq for SecretScalar {
+    fn eq(&self, other: &Self) -> bool {
+        self.ct_eq(other).into()
+    }
+}
+
+impl Eq for SecretScalar {}
+
+impl Zeroize for SecretScalar {
+    fn zeroize(&mut self) {
+        self.0 .0.zeroize();
+    }
+}
+
+impl Drop for SecretScalar {
+    fn drop(&mut self) {
+        self.zeroize();
+    }
+}
+
+impl ZeroizeOnDrop for SecretScalar {}
+
+impl fmt::Debug for SecretScalar {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "SecretScalar(<redacted>)")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SecretScalar;
+    use primitive_types::U256;
+    use zeroize::Zeroize;
+
+    #[test]
+    fn zeroize() {
+        let mut secret = SecretScalar::new(U256::from(0xdeadbeefu64));
+        secret.zeroize();
+
+        assert_eq!(*secret.expose_secret(), U256::zero());
+    }
+
+    #[test]
+    fn eq() {
+        let a = SecretScalar::new(U256::from(7));
+        let b = SecretScalar::new(U256::from(7));
+        let c = SecretScalar::new(U256::from(8));
+
+        assert_eq!(a, b);
+        assert_ne!(a, c);
+    }
+
+    #[test]
+    fn debug_is_redacted() {
+        let secret = SecretScalar::new(U256::from(0xdeadbeefu64));
+
+        assert_eq!(format!("{:?}", secret), "SecretScalar(<redacted>)");
+    }
+
+    #[test]
+    fn random() {
+        let mut rng = rand::thread_rng();
+        let secret = SecretScalar::random(&mut rng, U256::from(10));
+
+        assert!(*secret.expose_secret() >= U256::one());
+        assert!(*secret.expose_secret() < U256::from(10));
+    }
+}
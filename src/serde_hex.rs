@@ -0,0 +1,69 @@
+use std::fmt;
+
+use primitive_types::U256;
+use serde::de::{self, Deserializer, SeqAccess, Visitor};
+use serde::ser::Serializer;
+use serde::{Deserialize, Serialize};
+
+/// A `U256` serialized as a 64-character hex string in human-readable
+/// formats and as 32 big-endian bytes otherwise.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub(crate) struct HexU256(pub U256);
+
+impl Serialize for HexU256 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&format!("{:064x}", self.0))
+        } else {
+            let mut bytes = [0u8; 32];
+            self.0.to_big_endian(&mut bytes);
+            serializer.serialize_bytes(&bytes)
+        }
+    }
+}
+
+struct HexU256Visitor;
+
+impl<'de> Visitor<'de> for HexU256Visitor {
+    type Value = HexU256;
+
+    fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "a 64-character hex string or 32 bytes")
+    }
+
+    fn visit_str<E: de::Error>(self, v: &str) -> Result<Self::Value, E> {
+        if v.len() != 64 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        U256::from_str_radix(v, 16)
+            .map(HexU256)
+            .map_err(|_| E::invalid_value(de::Unexpected::Str(v), &self))
+    }
+
+    fn visit_bytes<E: de::Error>(self, v: &[u8]) -> Result<Self::Value, E> {
+        if v.len() != 32 {
+            return Err(E::invalid_length(v.len(), &self));
+        }
+        Ok(HexU256(U256::from_big_endian(v)))
+    }
+
+    fn visit_seq<A: SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut bytes = [0u8; 32];
+        for (i, byte) in bytes.iter_mut().enumerate() {
+            *byte = seq
+                .next_element()?
+                .ok_or_else(|| de::Error::invalid_length(i, &self))?;
+        }
+        Ok(HexU256(U256::from_big_endian(&bytes)))
+    }
+}
+
+impl<'de> Deserialize<'de> for HexU256 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        if deserializer.is_human_readable() {
+            deserializer.deserialize_str(HexU256Visitor)
+        } else {
+            deserializer.deserialize_bytes(HexU256Visitor)
+        }
+    }
+}
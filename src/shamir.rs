@@ -0,0 +1,300 @@
+//! Shamir secret sharing over GF(256), following SLIP-39.
+//!
+//! Every byte of the secret is shared with its own polynomial over
+//! GF(2^8), so shares are as long as the secret. As in SLIP-39 the secret
+//! sits at `x = 255` and a digest of it at `x = 254`, which lets
+//! `recover_secret` detect a wrong or mismatched share instead of silently
+//! returning garbage.
+//!
+//! This covers only SLIP-39's inner sharing layer; groups, encryption and
+//! the mnemonic encoding of shares are out of scope.
+
+use rand::{CryptoRng, RngCore};
+
+use crate::ct::ConstantTimeEq;
+use crate::error::ShamirError;
+use crate::hash::hmac_sha256;
+
+const SECRET_INDEX: u8 = 255;
+const DIGEST_INDEX: u8 = 254;
+const DIGEST_LEN: usize = 4;
+const MAX_SHARES: u8 = 16;
+
+/// GF(2^8) with the AES polynomial `x^8 + x^4 + x^3 + x + 1`: powers and
+/// discrete logs of the generator 3.
+const TABLES: ([u8; 255], [u8; 256]) = {
+    let mut exp = [0u8; 255];
+    let mut log = [0u8; 256];
+    let mut x: u8 = 1;
+    let mut i = 0;
+    while i < 255 {
+        exp[i] = x;
+        log[x as usize] = i as u8;
+        // x * 3 = x * 2 + x
+        let double = (x << 1) ^ if x & 0x80 != 0 { 0x1b } else { 0 };
+        x ^= double;
+        i += 1;
+    }
+    (exp, log)
+};
+
+fn mul(a: u8, b: u8) -> u8 {
+    if a == 0 || b == 0 {
+        return 0;
+    }
+    let (exp, log) = &TABLES;
+    exp[(log[a as usize] as usize + log[b as usize] as usize) % 255]
+}
+
+fn div(a: u8, b: u8) -> u8 {
+    if a == 0 {
+        return 0;
+    }
+    let (exp, log) = &TABLES;
+    exp[(log[a as usize] as usize + 255 - log[b as usize] as usize) % 255]
+}
+
+/// Evaluates at `x` the polynomial through `points`, byte by byte.
+/// Addition and subtraction in GF(2^8) are both XOR.
+fn interpolate(points: &[(u8, &[u8])], x: u8) -> Vec<u8> {
+    if let Some((_, y)) = points.iter().find(|(xi, _)| *xi == x) {
+        return y.to_vec();
+    }
+    let mut ret = vec![0u8; points[0].1.len()];
+    for (i, (xi, yi)) in points.iter().enumerate() {
+        let basis = points
+            .iter()
+            .enumerate()
+            .filter(|(j, _)| *j != i)
+            .fold(1u8, |acc, (_, (xj, _))| mul(acc, div(x ^ xj, xi ^ xj)));
+        for (r, y) in ret.iter_mut().zip(yi.iter()) {
+            *r ^= mul(basis, *y);
+        }
+    }
+    ret
+}
+
+fn digest(random: &[u8], secret: &[u8]) -> [u8; DIGEST_LEN] {
+    let mut ret = [0u8; DIGEST_LEN];
+    ret.copy_from_slice(&hmac_sha256(random, secret)[..DIGEST_LEN]);
+    ret
+}
+
+/// One of the `n` shares of a secret.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Share {
+    /// How many shares are needed to recover the secret.
+    pub threshold: u8,
+    /// The x coordinate, from 0 to `n - 1`.
+    pub index: u8,
+    /// The polynomials evaluated at `index`, as long as the secret.
+    pub value: Vec<u8>,
+}
+
+/// Splits `secret` into `n` shares, any `t` of which recover it.
+///
+/// `secret` is a private key or BIP39 entropy/seed: an even number of
+/// bytes, at least 16. Up to 16 shares can be made.
+pub fn split_secret<R: CryptoRng + RngCore>(
+    rng: &mut R,
+    secret: &[u8],
+    t: u8,
+    n: u8,
+) -> Result<Vec<Share>, ShamirError> {
+    if t == 0 || t > n || n > MAX_SHARES {
+        return Err(ShamirError::InvalidThreshold);
+    }
+    if secret.len() < 16 || !secret.len().is_multiple_of(2) {
+        return Err(ShamirError::InvalidSecretLength);
+    }
+    if t == 1 {
+        // しきい値 1 なら全員が秘密そのものを持つ
+        return Ok((0..n)
+            .map(|index| Share {
+                threshold: t,
+                index,
+                value: secret.to_vec(),
+            })
+            .collect());
+    }
+
+    // t - 2 個の乱数の点と、ダイジェスト・秘密の 2 点で t - 1 次多項式を決める
+    let mut shares: Vec<Share> = (0..t - 2)
+        .map(|index| {
+            let mut value = vec![0u8; secret.len()];
+            rng.fill_bytes(&mut value);
+            Share {
+                threshold: t,
+                index,
+                value,
+            }
+        })
+        .collect();
+
+    let mut random = vec![0u8; secret.len() - DIGEST_LEN];
+    rng.fill_bytes(&mut random);
+    let mut digest_share = digest(&random, secret).to_vec();
+    digest_share.extend_from_slice(&random);
+
+    let mut points: Vec<(u8, &[u8])> = shares.iter().map(|s| (s.index, &s.value[..])).collect();
+    points.push((DIGEST_INDEX, &digest_share));
+    points.push((SECRET_INDEX, secret));
+
+    let rest: Vec<Share> = (t - 2..n)
+        .map(|index| Share {
+            threshold: t,
+            index,
+            value: interpolate(&points, index),
+        })
+        .collect();
+    shares.extend(rest);
+    Ok(shares)
+}
+
+/// Recovers the secret from at least `threshold` shares of the same split.
+pub fn recover_secret(shares: &[Share]) -> Result<Vec<u8>, ShamirError> {
+    let first = shares.first().ok_or(ShamirError::NotEnoughShares)?;
+    let consistent = shares.iter().enumerate().all(|(i, share)| {
+        share.threshold == first.threshold
+            && share.value.len() == first.value.len()
+            && shares[..i].iter().all(|other| other.index != share.index)
+    });
+    if !consistent {
+        return Err(ShamirError::InconsistentShares);
+    }
+    let t = first.threshold as usize;
+    if t == 0 || shares.len() < t {
+        return Err(ShamirError::NotEnoughShares);
+    }
+    if first.value.len() < 16 || !first.value.len().is_multiple_of(2) {
+        return Err(ShamirError::InvalidSecretLength);
+    }
+    if t == 1 {
+        return Ok(first.value.clone());
+    }
+
+    let points: Vec<(u8, &[u8])> = shares[..t]
+        .iter()
+        .map(|s| (s.index, &s.value[..]))
+        .collect();
+    let secret = interpolate(&points, SECRET_INDEX);
+    let digest_share = interpolate(&points, DIGEST_INDEX);
+
+    let (expected, random) = digest_share.split_at(DIGEST_LEN);
+    if !bool::from(digest(random, &secret)[..].ct_eq(expected)) {
+        return Err(ShamirError::InvalidDigest);
+    }
+    Ok(secret)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{div, interpolate, mul, recover_secret, split_secret};
+    use crate::error::ShamirError;
+    use rand::rngs::StdRng;
+    use rand::SeedableRng;
+
+    #[test]
+    fn field_arithmetic() {
+        // AES の既知の積: 0x57 * 0x83 = 0xc1
+        assert_eq!(mul(0x57, 0x83), 0xc1);
+        assert_eq!(mul(0x57, 0x13), 0xfe);
+        for a in 1..=255u8 {
+            assert_eq!(mul(a, div(1, a)), 1);
+            assert_eq!(div(mul(a, 0x35), 0x35), a);
+        }
+        assert_eq!(mul(0, 7), 0);
+    }
+
+    #[test]
+    fn interpolate_line() {
+        // y = 5 + 3x (GF(2^8) の加算は XOR)
+        let points: [(u8, &[u8]); 2] = [(1, &[5 ^ 3]), (2, &[5 ^ mul(3, 2)])];
+        assert_eq!(interpolate(&points, 0), vec![5]);
+        assert_eq!(interpolate(&points, 1), vec![5 ^ 3]);
+    }
+
+    #[test]
+    fn split_and_recover() {
+        let mut rng = StdRng::seed_from_u64(71);
+        let secret: Vec<u8> = (0..32).collect();
+        let shares = split_secret(&mut rng, &secret, 3, 5).unwrap();
+        assert_eq!(shares.len(), 5);
+        assert!(shares.iter().all(|s| s.value != secret));
+
+        // どの 3 枚からでも復元できる
+        for a in 0..5 {
+            for b in a + 1..5 {
+                for c in b + 1..5 {
+                    let subset = [shares[c].clone(), shares[a].clone(), shares[b].clone()];
+                    assert_eq!(recover_secret(&subset).unwrap(), secret);
+                }
+            }
+        }
+        assert_eq!(recover_secret(&shares).unwrap(), secret);
+        assert_eq!(
+            recover_secret(&shares[..2]).unwrap_err(),
+            ShamirError::NotEnoughShares
+        );
+    }
+
+    #[test]
+    fn threshold_one_and_two() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let secret = [0x42u8; 16];
+
+        let shares = split_secret(&mut rng, &secret, 1, 3).unwrap();
+        assert_eq!(recover_secret(&shares[2..]).unwrap(), secret);
+
+        let shares = split_secret(&mut rng, &secret, 2, 2).unwrap();
+        assert_eq!(recover_secret(&shares).unwrap(), secret);
+    }
+
+    #[test]
+    fn detects_bad_shares() {
+        let mut rng = StdRng::seed_from_u64(2);
+        let secret = [7u8; 16];
+        let mut shares = split_secret(&mut rng, &secret, 2, 3).unwrap();
+
+        let mut dup = shares[..2].to_vec();
+        dup[1].index = dup[0].index;
+        assert_eq!(
+            recover_secret(&dup).unwrap_err(),
+            ShamirError::InconsistentShares
+        );
+
+        shares[0].value[3] ^= 1;
+        assert_eq!(
+            recover_secret(&shares[..2]).unwrap_err(),
+            ShamirError::InvalidDigest
+        );
+
+        // 別の分割のシェアと混ぜる
+        let other = split_secret(&mut rng, &secret, 2, 3).unwrap();
+        assert_eq!(
+            recover_secret(&[shares[1].clone(), other[2].clone()]).unwrap_err(),
+            ShamirError::InvalidDigest
+        );
+    }
+
+    #[test]
+    fn rejects_bad_parameters() {
+        let mut rng = StdRng::seed_from_u64(3);
+        assert_eq!(
+            split_secret(&mut rng, &[0; 16], 3, 2).unwrap_err(),
+            ShamirError::InvalidThreshold
+        );
+        assert_eq!(
+            split_secret(&mut rng, &[0; 16], 2, 17).unwrap_err(),
+            ShamirError::InvalidThreshold
+        );
+        assert_eq!(
+            split_secret(&mut rng, &[0; 15], 2, 3).unwrap_err(),
+            ShamirError::InvalidSecretLength
+        );
+        assert_eq!(
+            recover_secret(&[]).unwrap_err(),
+            ShamirError::NotEnoughShares
+        );
+    }
+}
@@ -0,0 +1,361 @@
+use std::fmt;
+
+use primitive_types::U256;
+
+use crate::error::SigError;
+use crate::hash::tagged_hash;
+use crate::s256_point::S256Point;
+use crate::scalar::Scalar;
+use crate::secp256k1::N;
+
+/// The sign-to-contract tweak `H(R0 || data)` for the original nonce point
+/// `R0`.
+pub(crate) fn contract_tweak(original_nonce: &S256Point, data: &[u8]) -> Scalar {
+    let mut msg = original_nonce.sec_compressed().to_vec();
+    msg.extend_from_slice(&tagged_hash("s2c/ecdsa/data", data));
+    Scalar::from_be_bytes_reduced(&tagged_hash("s2c/ecdsa/point", &msg))
+}
+
+/// An ECDSA signature `(r, s)` over secp256k1.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct Signature {
+    pub r: U256,
+    pub s: U256,
+}
+
+impl Signature {
+    /// Checks that `r` and `s` are both in `[1, N - 1]`.
+    pub fn try_new(r: U256, s: U256) -> Result<Self, SigError> {
+        let in_range = |n: U256| !n.is_zero() && n < N;
+        if !in_range(r) || !in_range(s) {
+            return Err(SigError::OutOfRange);
+        }
+        Ok(Self { r, s })
+    }
+
+    /// Panics unless `r` and `s` are both in `[1, N - 1]`.
+    pub fn new(r: U256, s: U256) -> Self {
+        Self::try_new(r, s).expect("r and s must be in [1, N - 1]")
+    }
+
+    /// Whether `s <= N / 2`. Both `(r, s)` and `(r, N - s)` are valid, so
+    /// Bitcoin's standardness rules only relay the low one to stop
+    /// third parties from changing txids by flipping `s`.
+    pub fn is_low_s(&self) -> bool {
+        self.s <= N >> 1
+    }
+
+    /// The low-S form of this signature, which is equally valid.
+    pub fn normalize_s(&self) -> Self {
+        if self.is_low_s() {
+            *self
+        } else {
+            Self::new(self.r, N - self.s)
+        }
+    }
+
+    /// Whether this signature's nonce point is
+    /// `R0 + H(R0 || data) * G` for `original_nonce = R0`, i.e. whether it
+    /// commits to `data` (see `PrivateKey::sign_to_contract`).
+    ///
+    /// In anti-exfiltration protocols the host supplies `data`, so a
+    /// malicious signer cannot choose the nonce to leak its key. This only
+    /// checks the commitment; check the signature itself with
+    /// `S256Point::verify`.
+    pub fn commits_to(&self, original_nonce: &S256Point, data: &[u8]) -> bool {
+        if original_nonce.is_infinity() {
+            return false;
+        }
+        let tweak = contract_tweak(original_nonce, data);
+        match (*original_nonce + tweak * S256Point::generator()).x() {
+            Some(x) => x.num() % N == self.r,
+            None => false,
+        }
+    }
+
+    /// The fixed-size 64-byte encoding `r || s`, each 32 bytes big-endian,
+    /// as used by Electrum, hardware wallets and BIP340.
+    pub fn serialize_compact(&self) -> [u8; 64] {
+        let mut ret = [0u8; 64];
+        self.r.to_big_endian(&mut ret[..32]);
+        self.s.to_big_endian(&mut ret[32..]);
+        ret
+    }
+
+    /// Parses the 64-byte `r || s` encoding.
+    pub fn parse_compact(bytes: &[u8]) -> Result<Self, SigError> {
+        if bytes.len() != 64 {
+            return Err(SigError::InvalidLength);
+        }
+        Self::try_new(
+            U256::from_big_endian(&bytes[..32]),
+            U256::from_big_endian(&bytes[32..]),
+        )
+    }
+
+    /// DER encoding: `0x30 len 0x02 len r 0x02 len s`, with each integer
+    /// in its shortest big-endian form and a `0x00` in front when the top
+    /// bit is set, so it does not read as negative.
+    pub fn der(&self) -> Vec<u8> {
+        let r = der_integer(self.r);
+        let s = der_integer(self.s);
+        let mut ret = vec![0x30, (r.len() + s.len()) as u8];
+        ret.extend(r);
+        ret.extend(s);
+        ret
+    }
+
+    /// Parses a DER signature, enforcing the strict encoding of BIP66:
+    /// exact lengths, no negative integers and no superfluous leading zero
+    /// bytes. `bytes` must not include the sighash type byte.
+    pub fn parse_der(bytes: &[u8]) -> Result<Self, SigError> {
+        // 0x30 len 0x02 lenR R 0x02 lenS S で、R と S は 1 バイト以上
+        if bytes.len() < 8 || bytes.len() > 72 || bytes[1] as usize != bytes.len() - 2 {
+            return Err(SigError::InvalidLength);
+        }
+        if bytes[0] != 0x30 {
+            return Err(SigError::InvalidMarker);
+        }
+        let (r, rest) = read_der_integer(&bytes[2..])?;
+        let (s, rest) = read_der_integer(rest)?;
+        if !rest.is_empty() {
+            return Err(SigError::InvalidLength);
+        }
+        Self::try_new(r, s)
+    }
+}
+
+fn der_integer(n: U256) -> Vec<u8> {
+    let mut bytes = [0u8; 32];
+    n.to_big_endian(&mut bytes);
+    let start = bytes.iter().position(|&b| b != 0).unwrap_or(31);
+
+    let mut body = bytes[start..].to_vec();
+    if body[0] & 0x80 != 0 {
+        body.insert(0, 0);
+    }
+    let mut ret = vec![0x02, body.len() as u8];
+    ret.extend(body);
+    ret
+}
+
+fn read_der_integer(bytes: &[u8]) -> Result<(U256, &[u8]), SigError> {
+    match bytes.first() {
+        Some(0x02) => {}
+        Some(_) => return Err(SigError::InvalidMarker),
+        None => return Err(SigError::InvalidLength),
+    }
+    let len = *bytes.get(1).ok_or(SigError::InvalidLength)? as usize;
+    let body = bytes.get(2..2 + len).ok_or(SigError::InvalidLength)?;
+    match body {
+        [] => return Err(SigError::InvalidLength),
+        [b, ..] if b & 0x80 != 0 => return Err(SigError::NegativeInteger),
+        // 次のバイトの最上位ビットが立っていないなら先頭の 0 は不要
+        [0, b, ..] if b & 0x80 == 0 => return Err(SigError::ExcessPadding),
+        _ => {}
+    }
+    let body = body.strip_prefix(&[0]).unwrap_or(body);
+    if body.len() > 32 {
+        return Err(SigError::OutOfRange);
+    }
+    Ok((U256::from_big_endian(body), &bytes[2 + len..]))
+}
+
+impl fmt::Display for Signature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "Signature({:064x}, {:064x})", self.r, self.s)
+    }
+}
+
+/// Serialized as the pair `(r, s)`, each as 64 hex digits.
+#[cfg(feature = "serde")]
+impl serde::Serialize for Signature {
+    fn serialize<S: serde::Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use crate::serde_hex::HexU256;
+
+        serde::Serialize::serialize(&(HexU256(self.r), HexU256(self.s)), serializer)
+    }
+}
+
+#[cfg(feature = "serde")]
+impl<'de> serde::Deserialize<'de> for Signature {
+    fn deserialize<D: serde::Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        use crate::serde_hex::HexU256;
+
+        let (HexU256(r), HexU256(s)) = serde::Deserialize::deserialize(deserializer)?;
+        Self::try_new(r, s).map_err(serde::de::Error::custom)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Signature;
+    use crate::error::SigError;
+    use crate::secp256k1::N;
+    use primitive_types::U256;
+
+    #[test]
+    fn new_checks_range() {
+        let one = U256::one();
+        assert!(Signature::try_new(one, N - one).is_ok());
+        assert_eq!(
+            Signature::try_new(U256::zero(), one),
+            Err(SigError::OutOfRange)
+        );
+        assert_eq!(Signature::try_new(one, N), Err(SigError::OutOfRange));
+        assert_eq!(
+            Signature::try_new(U256::MAX, one),
+            Err(SigError::OutOfRange)
+        );
+    }
+
+    #[test]
+    #[should_panic]
+    fn new_rejects_zero() {
+        Signature::new(U256::one(), U256::zero());
+    }
+
+    #[test]
+    fn normalize_s() {
+        let half = N >> 1;
+        let low = Signature::new(U256::one(), half);
+        assert!(low.is_low_s());
+        assert_eq!(low.normalize_s(), low);
+
+        let high = Signature::new(U256::one(), half + 1);
+        assert!(!high.is_low_s());
+        assert_eq!(
+            high.normalize_s(),
+            Signature::new(U256::one(), N - half - 1)
+        );
+        assert!(high.normalize_s().is_low_s());
+    }
+
+    #[test]
+    fn display_as_hex() {
+        let sig = Signature::new(U256::from(0xab), U256::from(0xcd));
+        assert_eq!(
+            sig.to_string(),
+            format!("Signature({:064x}, {:064x})", 0xab, 0xcd)
+        );
+    }
+
+    #[test]
+    fn compact_round_trip() {
+        let sig = Signature::new(U256::from(0xab), N - U256::one());
+        let bytes = sig.serialize_compact();
+        assert_eq!(bytes[31], 0xab);
+        assert_eq!(Signature::parse_compact(&bytes), Ok(sig));
+
+        assert_eq!(
+            Signature::parse_compact(&bytes[..63]),
+            Err(SigError::InvalidLength)
+        );
+        assert_eq!(
+            Signature::parse_compact(&[0u8; 64]),
+            Err(SigError::OutOfRange)
+        );
+    }
+
+    #[test]
+    fn der_book_vector() {
+        let sig = Signature::new(
+            U256::from_str_radix(
+                "37206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6",
+                16,
+            )
+            .unwrap(),
+            U256::from_str_radix(
+                "8ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec",
+                16,
+            )
+            .unwrap(),
+        );
+        let der: String = sig.der().iter().map(|b| format!("{:02x}", b)).collect();
+        assert_eq!(
+            der,
+            "3045022037206a0610995c58074999cb9767b87af4c4978db68c06e8e6e81d282047a7c6\
+             0221008ca63759c1157ebeaec0d03cecca119fc9a75bf8e6d0fa65c841c8e2738cdaec"
+        );
+    }
+
+    #[test]
+    fn der_round_trip() {
+        for (r, s) in [
+            (U256::one(), U256::from(0x80)),
+            (N - U256::one(), U256::from(0x7f)),
+            (U256::from(0xdeadbeefu64), N >> 1),
+        ] {
+            let sig = Signature::new(r, s);
+            assert_eq!(Signature::parse_der(&sig.der()), Ok(sig));
+        }
+        assert_eq!(
+            Signature::new(U256::one(), U256::from(0x80)).der(),
+            [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80]
+        );
+    }
+
+    #[test]
+    fn parse_der_is_strict() {
+        let valid = [0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80];
+        assert!(Signature::parse_der(&valid).is_ok());
+
+        let cases: [(&[u8], SigError); 9] = [
+            (&[0x30, 0x00], SigError::InvalidLength),
+            // 全体の長さが合わない
+            (
+                &[0x30, 0x08, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+                SigError::InvalidLength,
+            ),
+            (
+                &[0x31, 0x07, 0x02, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+                SigError::InvalidMarker,
+            ),
+            (
+                &[0x30, 0x07, 0x03, 0x01, 0x01, 0x02, 0x02, 0x00, 0x80],
+                SigError::InvalidMarker,
+            ),
+            // s の後ろに余分なバイト
+            (
+                &[0x30, 0x07, 0x02, 0x01, 0x01, 0x02, 0x01, 0x01, 0x00],
+                SigError::InvalidLength,
+            ),
+            // 負の整数
+            (
+                &[0x30, 0x06, 0x02, 0x01, 0x01, 0x02, 0x01, 0x80],
+                SigError::NegativeInteger,
+            ),
+            // 不要な先頭の 0
+            (
+                &[0x30, 0x07, 0x02, 0x02, 0x00, 0x01, 0x02, 0x01, 0x01],
+                SigError::ExcessPadding,
+            ),
+            // 長さ 0 の整数
+            (
+                &[0x30, 0x06, 0x02, 0x00, 0x02, 0x02, 0x00, 0x80],
+                SigError::InvalidLength,
+            ),
+            // r = 0
+            (
+                &[0x30, 0x06, 0x02, 0x01, 0x00, 0x02, 0x01, 0x01],
+                SigError::OutOfRange,
+            ),
+        ];
+        for (bytes, err) in cases {
+            assert_eq!(Signature::parse_der(bytes), Err(err), "{:02x?}", bytes);
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn serde_round_trip() {
+        let sig = Signature::new(U256::from(0xab), U256::from(0xcd));
+        let json = serde_json::to_string(&sig).unwrap();
+
+        assert_eq!(json, format!("[\"{:064x}\",\"{:064x}\"]", 0xab, 0xcd));
+        assert_eq!(serde_json::from_str::<Signature>(&json).unwrap(), sig);
+
+        let zero = format!("[\"{:064x}\",\"{:064x}\"]", 0, 0xcd);
+        assert!(serde_json::from_str::<Signature>(&zero).is_err());
+    }
+}
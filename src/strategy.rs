@@ -0,0 +1,104 @@
+//! `proptest` strategies for the crate's core types, so field axioms and
+//! group laws can be property-tested without hand-written generators.
+
+use primitive_types::U256;
+use proptest::prelude::*;
+
+use crate::elliptic::Point;
+use crate::field_element::FieldElement;
+use crate::fp::Fp;
+use crate::s256_field::S256Field;
+use crate::secp256k1::P;
+
+fn u256_below(bound: U256) -> impl Strategy<Value = U256> {
+    any::<[u64; 4]>().prop_map(move |limbs| U256(limbs) % bound)
+}
+
+/// Any element of the field with the given prime.
+pub fn field_element(prime: U256) -> impl Strategy<Value = FieldElement<U256>> {
+    u256_below(prime).prop_map(move |num| FieldElement::new(num, prime))
+}
+
+/// Any point on `y^2 = x^3 + a*x + b` over the field of `a` and `b`,
+/// including the point at infinity.
+pub fn point(
+    a: FieldElement<U256>,
+    b: FieldElement<U256>,
+) -> impl Strategy<Value = Point<FieldElement<U256>>> {
+    let affine = (field_element(a.prime), any::<bool>()).prop_filter_map(
+        "x does not lift to a point on the curve",
+        move |(x, odd)| {
+            let y = (x.cube() + a * x + b).sqrt()?;
+            let y = if odd { -y } else { y };
+            Some(Point::new(x, y, a, b))
+        },
+    );
+    prop_oneof![1 => Just(Point::Infinity), 15 => affine]
+}
+
+impl Arbitrary for S256Field {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        u256_below(P).prop_map(S256Field::new).boxed()
+    }
+}
+
+impl<const Q: u64> Arbitrary for Fp<Q> {
+    type Parameters = ();
+    type Strategy = BoxedStrategy<Self>;
+
+    fn arbitrary_with(_: Self::Parameters) -> Self::Strategy {
+        (0..Q).prop_map(Fp::new).boxed()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{field_element, point};
+    use crate::field_element::FieldElement;
+    use crate::fp::Fp;
+    use crate::s256_field::S256Field;
+    use primitive_types::U256;
+    use proptest::prelude::*;
+
+    fn f223(n: u64) -> FieldElement<U256> {
+        FieldElement::new(U256::from(n), U256::from(223))
+    }
+
+    proptest! {
+        #[test]
+        fn field_axioms(a in any::<S256Field>(), b in any::<S256Field>(), c in any::<S256Field>()) {
+            prop_assert_eq!(a + b, b + a);
+            prop_assert_eq!(a * (b + c), a * b + a * c);
+            prop_assert_eq!((a - b) + b, a);
+            if a.num() != U256::zero() {
+                prop_assert_eq!(a * a.inverse(), S256Field::from(1));
+            }
+        }
+
+        #[test]
+        fn small_field_axioms(a in any::<Fp<223>>(), b in any::<Fp<223>>()) {
+            prop_assert_eq!(a * b, b * a);
+            prop_assert_eq!(a + -a, Fp::new(0));
+        }
+
+        #[test]
+        fn sqrt_of_square(a in field_element(U256::from(97))) {
+            let root = a.square().sqrt().unwrap();
+            prop_assert!(root == a || root == -a);
+        }
+
+        #[test]
+        fn group_laws(
+            p in point(f223(0), f223(7)),
+            q in point(f223(0), f223(7)),
+            r in point(f223(0), f223(7)),
+        ) {
+            prop_assert_eq!(&p + &q, &q + &p);
+            prop_assert_eq!((&p + &q) + &r, &p + (&q + &r));
+            prop_assert_eq!(&p + crate::elliptic::Point::Infinity, p);
+        }
+    }
+}
@@ -0,0 +1,227 @@
+use crate::encoding::encode_varint;
+use crate::error::TweakError;
+use crate::hash::tagged_hash;
+use crate::private_key::PrivateKey;
+use crate::scalar::Scalar;
+use crate::xonly::XOnlyPublicKey;
+
+/// Leaf version of tapscript (BIP342).
+pub const TAPSCRIPT_LEAF_VERSION: u8 = 0xc0;
+
+/// A taproot script tree: a binary tree whose leaves are scripts.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum TapTree {
+    Leaf { version: u8, script: Vec<u8> },
+    Branch(Box<TapTree>, Box<TapTree>),
+}
+
+impl TapTree {
+    /// A tapscript leaf.
+    pub fn leaf(script: Vec<u8>) -> Self {
+        TapTree::Leaf {
+            version: TAPSCRIPT_LEAF_VERSION,
+            script,
+        }
+    }
+
+    pub fn branch(left: TapTree, right: TapTree) -> Self {
+        TapTree::Branch(Box::new(left), Box::new(right))
+    }
+
+    /// The hash committed to by the output key.
+    pub fn merkle_root(&self) -> [u8; 32] {
+        match self {
+            TapTree::Leaf { version, script } => tap_leaf_hash(*version, script),
+            TapTree::Branch(left, right) => {
+                tap_branch_hash(&left.merkle_root(), &right.merkle_root())
+            }
+        }
+    }
+}
+
+/// `TapLeaf` tagged hash of `version || encode_varint(len) || script`.
+pub fn tap_leaf_hash(version: u8, script: &[u8]) -> [u8; 32] {
+    let mut data = vec![version];
+    data.extend_from_slice(&encode_varint(script.len() as u64));
+    data.extend_from_slice(script);
+    tagged_hash("TapLeaf", &data)
+}
+
+/// `TapBranch` tagged hash of the two children in lexicographic order, so
+/// the order of the children does not matter.
+pub fn tap_branch_hash(a: &[u8; 32], b: &[u8; 32]) -> [u8; 32] {
+    let (first, second) = if a <= b { (a, b) } else { (b, a) };
+    let mut data = [0u8; 64];
+    data[..32].copy_from_slice(first);
+    data[32..].copy_from_slice(second);
+    tagged_hash("TapBranch", &data)
+}
+
+/// `t = TapTweak(P || merkle_root)`, with the root left out for key-path
+/// only outputs.
+fn tap_tweak(
+    internal_key: &XOnlyPublicKey,
+    merkle_root: Option<&[u8; 32]>,
+) -> Result<Scalar, TweakError> {
+    let mut data = internal_key.serialize().to_vec();
+    if let Some(root) = merkle_root {
+        data.extend_from_slice(root);
+    }
+    // t >= N となる確率は無視できるが、その場合の出力鍵は作れない
+    Scalar::from_be_bytes(&tagged_hash("TapTweak", &data)).map_err(|_| TweakError::InvalidResult)
+}
+
+/// What is needed to create and spend a P2TR output (BIP341): the output
+/// key `Q = P + t * G` for internal key `P` and the parity of `Q`'s y,
+/// which script-path spends reveal in the control block.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TaprootSpendInfo {
+    pub internal_key: XOnlyPublicKey,
+    pub merkle_root: Option<[u8; 32]>,
+    pub output_key: XOnlyPublicKey,
+    /// `true` when `Q` has an odd y.
+    pub output_key_parity: bool,
+}
+
+impl TaprootSpendInfo {
+    /// Tweaks `internal_key` with the merkle root of `tree`, or with nothing
+    /// for an output that can only be spent by key.
+    pub fn new(internal_key: XOnlyPublicKey, tree: Option<&TapTree>) -> Result<Self, TweakError> {
+        let merkle_root = tree.map(TapTree::merkle_root);
+        let tweak = tap_tweak(&internal_key, merkle_root.as_ref())?;
+        let output = internal_key.lift_x().add_tweak(&tweak)?;
+
+        Ok(Self {
+            internal_key,
+            merkle_root,
+            output_key: XOnlyPublicKey::from_point(&output)
+                .expect("add_tweak never returns infinity"),
+            output_key_parity: !output.has_even_y(),
+        })
+    }
+
+    /// The secret key for a key-path spend: `d + t` where `d` is `key`
+    /// negated if needed so that `d * G` has an even y.
+    pub fn tweak_private_key(&self, key: &PrivateKey) -> Result<PrivateKey, TweakError> {
+        let point = key.point();
+        if XOnlyPublicKey::from_point(&point) != Some(self.internal_key) {
+            return Err(TweakError::InvalidResult);
+        }
+        let even = if point.has_even_y() {
+            key.clone()
+        } else {
+            key.negate()
+        };
+        even.add_tweak(&tap_tweak(&self.internal_key, self.merkle_root.as_ref())?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{tap_branch_hash, tap_leaf_hash, TapTree, TaprootSpendInfo};
+    use crate::address::Address;
+    use crate::bip39::Mnemonic;
+    use crate::hd::ExtendedPrivKey;
+    use crate::network::Network;
+    use crate::private_key::PrivateKey;
+    use crate::scalar::Scalar;
+    use crate::xonly::XOnlyPublicKey;
+
+    fn hex(s: &str) -> Vec<u8> {
+        (0..s.len())
+            .step_by(2)
+            .map(|i| u8::from_str_radix(&s[i..i + 2], 16).unwrap())
+            .collect()
+    }
+
+    fn xonly(s: &str) -> XOnlyPublicKey {
+        XOnlyPublicKey::parse(&hex(s)).unwrap()
+    }
+
+    #[test]
+    fn key_path_only() {
+        // BIP341 wallet-test-vectors の scriptPubKey 0
+        let info = TaprootSpendInfo::new(
+            xonly("d6889cb081036e0faefa3a35157ad71086b123b2b144b649798b494c300a961d"),
+            None,
+        )
+        .unwrap();
+        assert_eq!(
+            info.output_key,
+            xonly("53a1f6e454df1aa2776a2814a721372d6258050de330b3c6d10ee8f4e0dda343")
+        );
+    }
+
+    #[test]
+    fn bip86_output_key() {
+        // BIP86 テストベクタ: m/86'/0'/0'/0/0
+        let mnemonic: Mnemonic = "abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon abandon about"
+            .parse()
+            .unwrap();
+        let master = ExtendedPrivKey::new_master(&mnemonic.to_seed(""), Network::Mainnet).unwrap();
+        let key = master
+            .derive_path(&"m/86'/0'/0'/0/0".parse().unwrap())
+            .unwrap()
+            .private_key;
+
+        let internal_key = XOnlyPublicKey::from_point(&key.point()).unwrap();
+        assert_eq!(
+            internal_key,
+            xonly("cc8a4bc64d897bddc5fbc2f670f7a8ba0b386779106cf1223c6fc5d7cd6fc115")
+        );
+
+        let info = TaprootSpendInfo::new(internal_key, None).unwrap();
+        assert_eq!(
+            info.output_key,
+            xonly("a60869f0dbcf1dc659c9cecbaf8050135ea9e8cdc487053f1dc6880949dc684c")
+        );
+        assert_eq!(
+            Address::p2tr(info.output_key, Network::Mainnet).to_string(),
+            "bc1p5cyxnuxmeuwuvkwfem96lqzszd02n6xdcjrs20cac6yqjjwudpxqkedrcr"
+        );
+    }
+
+    #[test]
+    fn tweaked_private_key_matches_output_key() {
+        let tree = TapTree::branch(
+            TapTree::leaf(vec![0x51]),
+            TapTree::branch(TapTree::leaf(vec![0x52]), TapTree::leaf(vec![0x53])),
+        );
+        for secret in [3u64, 5, 7, 11] {
+            let key = PrivateKey::new(Scalar::from(secret));
+            let internal_key = XOnlyPublicKey::from_point(&key.point()).unwrap();
+            for tree in [None, Some(&tree)] {
+                let info = TaprootSpendInfo::new(internal_key, tree).unwrap();
+                let tweaked = info.tweak_private_key(&key).unwrap().point();
+
+                assert_eq!(XOnlyPublicKey::from_point(&tweaked), Some(info.output_key));
+                assert_eq!(!tweaked.has_even_y(), info.output_key_parity);
+            }
+        }
+
+        let other = PrivateKey::new(Scalar::from(2));
+        let info = TaprootSpendInfo::new(
+            XOnlyPublicKey::from_point(&PrivateKey::new(Scalar::from(3)).point()).unwrap(),
+            None,
+        )
+        .unwrap();
+        assert!(info.tweak_private_key(&other).is_err());
+    }
+
+    #[test]
+    fn merkle_root() {
+        let a = TapTree::leaf(vec![0x51]);
+        let b = TapTree::leaf(vec![0x52]);
+
+        assert_eq!(a.merkle_root(), tap_leaf_hash(0xc0, &[0x51]));
+        // 枝の左右は入れ替えても同じ
+        assert_eq!(
+            TapTree::branch(a.clone(), b.clone()).merkle_root(),
+            TapTree::branch(b.clone(), a.clone()).merkle_root()
+        );
+        assert_eq!(
+            TapTree::branch(a.clone(), b.clone()).merkle_root(),
+            tap_branch_hash(&a.merkle_root(), &b.merkle_root())
+        );
+    }
+}
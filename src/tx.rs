@@ -0,0 +1,245 @@
+//! Transactions in the legacy (pre-segwit) serialization.
+
+use std::io::{self, Read, Write};
+
+use crate::encoding::{
+    read_u32_le, read_u64_le, read_varint, write_u32_le, write_u64_le, write_varint,
+};
+use crate::hash::HashWriter;
+use crate::hash_types::Txid;
+use crate::script::Script;
+
+/// A transaction: inputs spending earlier outputs, and new outputs.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Tx {
+    pub version: u32,
+    pub tx_ins: Vec<TxIn>,
+    pub tx_outs: Vec<TxOut>,
+    /// The block height (below 500,000,000) or Unix time before which the
+    /// transaction cannot be mined.
+    pub locktime: u32,
+}
+
+/// A reference to an output of an earlier transaction, with the script
+/// that unlocks it.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxIn {
+    pub prev_tx: Txid,
+    pub prev_index: u32,
+    pub script_sig: Script,
+    pub sequence: u32,
+}
+
+/// An amount in satoshis locked to a `script_pubkey`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TxOut {
+    pub amount: u64,
+    pub script_pubkey: Script,
+}
+
+impl Tx {
+    /// Reads a transaction in the legacy serialization.
+    ///
+    /// The segwit serialization starts with a zero marker where the input
+    /// count would be; it is rejected with `ErrorKind::InvalidData` rather
+    /// than misread as a transaction without inputs.
+    pub fn parse<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        let version = read_u32_le(reader)?;
+        let num_ins = read_varint(reader)?;
+        if num_ins == 0 {
+            return Err(io::Error::new(
+                io::ErrorKind::InvalidData,
+                "segwit serialization is not supported",
+            ));
+        }
+        // 個数は信用せず、あらかじめ確保しない
+        let tx_ins = (0..num_ins)
+            .map(|_| TxIn::parse(reader))
+            .collect::<io::Result<_>>()?;
+        let num_outs = read_varint(reader)?;
+        let tx_outs = (0..num_outs)
+            .map(|_| TxOut::parse(reader))
+            .collect::<io::Result<_>>()?;
+        let locktime = read_u32_le(reader)?;
+        Ok(Self {
+            version,
+            tx_ins,
+            tx_outs,
+            locktime,
+        })
+    }
+
+    /// Writes the legacy serialization that `parse` reads.
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        write_u32_le(writer, self.version)?;
+        write_varint(writer, self.tx_ins.len() as u64)?;
+        for tx_in in &self.tx_ins {
+            tx_in.write_to(writer)?;
+        }
+        write_varint(writer, self.tx_outs.len() as u64)?;
+        for tx_out in &self.tx_outs {
+            tx_out.write_to(writer)?;
+        }
+        write_u32_le(writer, self.locktime)
+    }
+
+    pub fn serialize(&self) -> Vec<u8> {
+        let mut ret = Vec::new();
+        self.write_to(&mut ret)
+            .expect("writing to a Vec cannot fail");
+        ret
+    }
+
+    /// `hash256` of the serialization; displayed byte-reversed.
+    pub fn id(&self) -> Txid {
+        let mut writer = HashWriter::new();
+        self.write_to(&mut writer)
+            .expect("writing to a HashWriter cannot fail");
+        Txid::from_bytes(writer.hash256())
+    }
+}
+
+impl TxIn {
+    pub fn parse<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        let mut prev_tx = [0u8; 32];
+        reader.read_exact(&mut prev_tx)?;
+        Ok(Self {
+            prev_tx: Txid::from_bytes(prev_tx),
+            prev_index: read_u32_le(reader)?,
+            script_sig: Script::parse(reader)?,
+            sequence: read_u32_le(reader)?,
+        })
+    }
+
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        writer.write_all(&self.prev_tx.to_bytes())?;
+        write_u32_le(writer, self.prev_index)?;
+        self.script_sig.write_to(writer)?;
+        write_u32_le(writer, self.sequence)
+    }
+}
+
+impl TxOut {
+    pub fn parse<R: Read + ?Sized>(reader: &mut R) -> io::Result<Self> {
+        Ok(Self {
+            amount: read_u64_le(reader)?,
+            script_pubkey: Script::parse(reader)?,
+        })
+    }
+
+    pub fn write_to<W: Write + ?Sized>(&self, writer: &mut W) -> io::Result<()> {
+        write_u64_le(writer, self.amount)?;
+        self.script_pubkey.write_to(writer)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Tx;
+    use crate::hash_types::Txid;
+    use crate::hex;
+    use std::io::ErrorKind;
+
+    // 本の第 5 章のメインネットのトランザクション
+    // (452c629d67e41baec3ac6f04fe744b4b9617f8f859c63b3002f8684e7a4fee03)
+    const BOOK_TX: &str = "0100000001813f79011acb80925dfe69b3def355fe914bd1d96a3f5f71\
+        bf8303c6a989c7d1000000006b483045022100ed81ff192e75a3fd2304004dcadb746fa5e24c5031ccfcf2\
+        1320b0277457c98f02207a986d955c6e0cb35d446a89d3f56100f4d7f67801c31967743a9c8e10615bed01\
+        210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278afeffffff02a135ef01\
+        000000001976a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac99c39800000000001976a9141c\
+        4bc762dd5423e332166702cb75f40df79fea1288ac19430600";
+
+    // ジェネシスブロックの coinbase。メインネットとテストネット (testnet3) で共通
+    const GENESIS_COINBASE: &str = "01000000010000000000000000000000000000000000000000\
+        000000000000000000000000ffffffff4d04ffff001d0104455468652054696d65732030332f4a616e2f32\
+        303039204368616e63656c6c6f72206f6e206272696e6b206f66207365636f6e64206261696c6f75742066\
+        6f722062616e6b73ffffffff0100f2052a01000000434104678afdb0fe5548271967f1a67130b7105cd6a8\
+        28e03909a67962e0ea1f61deb649f6bc3f4cef38c4f35504e51ec112de5c384df7ba0b8d578a4c702b6bf1\
+        1d5fac00000000";
+
+    fn parse(raw: &str) -> Tx {
+        let raw = hex::decode(raw).unwrap();
+        let mut reader = &raw[..];
+        let tx = Tx::parse(&mut reader).unwrap();
+        assert!(reader.is_empty());
+        tx
+    }
+
+    #[test]
+    fn parse_book_tx() {
+        let tx = parse(BOOK_TX);
+        assert_eq!(tx.version, 1);
+        assert_eq!(tx.locktime, 410393);
+
+        assert_eq!(tx.tx_ins.len(), 1);
+        let tx_in = &tx.tx_ins[0];
+        let prev_tx: Txid = "d1c789a9c60383bf715f3f6ad9d14b91fe55f3deb369fe5d9280cb1a01793f81"
+            .parse()
+            .unwrap();
+        assert_eq!(tx_in.prev_tx, prev_tx);
+        assert_eq!(tx_in.prev_index, 0);
+        assert_eq!(tx_in.script_sig.len(), 0x6b);
+        assert_eq!(
+            hex::encode(&tx_in.script_sig.as_bytes()[0x6b - 34..]),
+            "210349fc4e631e3624a545de3f89f5d8684c7b8138bd94bdd531d2e213bf016b278a"
+        );
+        assert_eq!(tx_in.sequence, 0xfffffffe);
+
+        assert_eq!(tx.tx_outs.len(), 2);
+        assert_eq!(tx.tx_outs[0].amount, 32454049);
+        assert_eq!(
+            hex::encode(tx.tx_outs[0].script_pubkey.as_bytes()),
+            "76a914bc3b654dca7e56b04dca18f2566cdaf02e8d9ada88ac"
+        );
+        assert_eq!(tx.tx_outs[1].amount, 10011545);
+        assert_eq!(
+            hex::encode(tx.tx_outs[1].script_pubkey.as_bytes()),
+            "76a9141c4bc762dd5423e332166702cb75f40df79fea1288ac"
+        );
+    }
+
+    #[test]
+    fn parse_genesis_coinbase() {
+        let tx = parse(GENESIS_COINBASE);
+        assert_eq!(tx.version, 1);
+        assert_eq!(tx.locktime, 0);
+        assert_eq!(tx.tx_ins[0].prev_tx, Txid::from_bytes([0; 32]));
+        assert_eq!(tx.tx_ins[0].prev_index, 0xffffffff);
+        // coinbase の scriptSig には The Times の見出しが入っている
+        let headline = b"The Times 03/Jan/2009 Chancellor on brink of second bailout for banks";
+        assert!(tx.tx_ins[0].script_sig.as_bytes().ends_with(headline));
+        assert_eq!(tx.tx_outs.len(), 1);
+        assert_eq!(tx.tx_outs[0].amount, 50 * 100_000_000);
+        assert_eq!(tx.tx_outs[0].script_pubkey.len(), 67);
+    }
+
+    #[test]
+    fn serialize_round_trip() {
+        for raw in [BOOK_TX, GENESIS_COINBASE] {
+            assert_eq!(hex::encode(parse(raw).serialize()), raw);
+        }
+    }
+
+    #[test]
+    fn id() {
+        assert_eq!(
+            parse(BOOK_TX).id().to_string(),
+            "452c629d67e41baec3ac6f04fe744b4b9617f8f859c63b3002f8684e7a4fee03"
+        );
+        let genesis: Txid = "4a5e1e4baab89f3a32518a88c31bc87f618f76673e2cc77ab2127b7afdeda33b"
+            .parse()
+            .unwrap();
+        assert_eq!(parse(GENESIS_COINBASE).id(), genesis);
+    }
+
+    #[test]
+    fn parse_errors() {
+        let raw = hex::decode(BOOK_TX).unwrap();
+        let err = Tx::parse(&mut &raw[..raw.len() - 1]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::UnexpectedEof);
+
+        // segwit のマーカーとフラグ
+        let err = Tx::parse(&mut &[1, 0, 0, 0, 0, 1][..]).unwrap_err();
+        assert_eq!(err.kind(), ErrorKind::InvalidData);
+    }
+}
@@ -0,0 +1,110 @@
+//! Parallel vanity address search.
+//!
+//! Every worker thread draws fresh keys from its own CSPRNG and derives the
+//! address through `PrivateKey::point`, i.e. the precomputed generator table,
+//! so this doubles as a throughput benchmark of generator multiplication.
+
+use rand::thread_rng;
+use rayon::iter::{repeat, ParallelIterator};
+
+use crate::base58::ALPHABET;
+use crate::error::ParseError;
+use crate::network::Network;
+use crate::private_key::PrivateKey;
+
+/// Searches in parallel for a key whose P2PKH address satisfies `matcher`.
+/// Returns the first key found by any thread; loops forever if `matcher`
+/// never accepts.
+pub fn search<F>(compressed: bool, network: Network, matcher: F) -> PrivateKey
+where
+    F: Fn(&str) -> bool + Sync,
+{
+    repeat(())
+        .map_init(thread_rng, |rng, ()| PrivateKey::generate(rng))
+        .find_any(|key| matcher(&key.point().address(compressed, network)))
+        .expect("the key stream is infinite")
+}
+
+/// Longest P2PKH address: 25 bytes of Base58Check.
+const MAX_ADDRESS_LEN: usize = 34;
+
+/// Searches for an address starting with `prefix`.
+///
+/// Prefixes that can never match are rejected up front instead of
+/// searching forever: characters outside the Base58 alphabet
+/// (`InvalidCharacter`), prefixes longer than an address (`InvalidLength`)
+/// and a first character the network's version byte never produces, i.e.
+/// anything but `1` on mainnet and `m` or `n` on the test networks
+/// (`InvalidPrefix`).
+pub fn search_prefix(
+    prefix: &str,
+    compressed: bool,
+    network: Network,
+) -> Result<PrivateKey, ParseError> {
+    if !prefix.bytes().all(|c| ALPHABET.contains(&c)) {
+        return Err(ParseError::InvalidCharacter);
+    }
+    if prefix.len() > MAX_ADDRESS_LEN {
+        return Err(ParseError::InvalidLength);
+    }
+    // 先頭の文字はバージョンバイトだけで決まる
+    let leading: &[char] = match network {
+        Network::Mainnet => &['1'],
+        _ => &['m', 'n'],
+    };
+    if prefix.chars().next().is_some_and(|c| !leading.contains(&c)) {
+        return Err(ParseError::InvalidPrefix);
+    }
+    Ok(search(compressed, network, |address| {
+        address.starts_with(prefix)
+    }))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{search, search_prefix};
+    use crate::error::ParseError;
+    use crate::network::Network;
+
+    #[test]
+    fn finds_prefix() {
+        // メインネットの P2PKH は必ず '1' で始まるので 2 文字目だけを探す
+        let key = search_prefix("1A", true, Network::Mainnet).unwrap();
+        assert!(key
+            .point()
+            .address(true, Network::Mainnet)
+            .starts_with("1A"));
+    }
+
+    #[test]
+    fn finds_with_matcher() {
+        let key = search(false, Network::Testnet, |address| address.ends_with('z'));
+        assert!(key.point().address(false, Network::Testnet).ends_with('z'));
+    }
+
+    #[test]
+    fn rejects_invalid_prefix() {
+        assert_eq!(
+            search_prefix("10", true, Network::Mainnet).unwrap_err(),
+            ParseError::InvalidCharacter
+        );
+        assert_eq!(
+            search_prefix("2", true, Network::Mainnet).unwrap_err(),
+            ParseError::InvalidPrefix
+        );
+        assert_eq!(
+            search_prefix("1A", true, Network::Testnet).unwrap_err(),
+            ParseError::InvalidPrefix
+        );
+        assert_eq!(
+            search_prefix(&"1".repeat(35), true, Network::Mainnet).unwrap_err(),
+            ParseError::InvalidLength
+        );
+    }
+
+    #[test]
+    fn accepts_test_network_prefix() {
+        let key = search_prefix("n", false, Network::Signet).unwrap();
+        assert!(key.point().address(false, Network::Signet).starts_with('n'));
+    }
+}
@@ -0,0 +1,98 @@
+use std::fmt;
+
+use crate::error::ParseError;
+use crate::s256_field::S256Field;
+use crate::s256_point::S256Point;
+
+/// A public key identified by its x coordinate only, as used by BIP340
+/// Schnorr signatures and taproot.
+///
+/// Of the two points sharing an x coordinate, the key always stands for
+/// the one with an even y. Construction guarantees such a point exists.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct XOnlyPublicKey(S256Field);
+
+impl XOnlyPublicKey {
+    /// Drops the y coordinate of `point`; `None` for the point at infinity.
+    ///
+    /// `point` and `-point` give the same key.
+    pub fn from_point(point: &S256Point) -> Option<Self> {
+        point.x().map(Self)
+    }
+
+    /// Parses 32 big-endian bytes of x.
+    pub fn parse(bytes: &[u8]) -> Result<Self, ParseError> {
+        if bytes.len() != 32 {
+            return Err(ParseError::InvalidLength);
+        }
+        let x = S256Field::try_from(bytes).map_err(|_| ParseError::InvalidCoordinate)?;
+        S256Point::lift_x(&x)
+            .map(|_| Self(x))
+            .ok_or(ParseError::NotOnCurve)
+    }
+
+    pub fn serialize(&self) -> [u8; 32] {
+        self.0.to_be_bytes()
+    }
+
+    pub fn x(&self) -> S256Field {
+        self.0
+    }
+
+    /// The full point with an even y.
+    pub fn lift_x(&self) -> S256Point {
+        S256Point::lift_x(&self.0).expect("x was checked to be on the curve")
+    }
+}
+
+impl fmt::Display for XOnlyPublicKey {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:x}", self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XOnlyPublicKey;
+    use crate::error::ParseError;
+    use crate::s256_point::S256Point;
+    use crate::secp256k1::P;
+    use primitive_types::U256;
+
+    #[test]
+    fn from_point_and_lift_x() {
+        // BIP340 テストベクタ 0: 秘密鍵 3
+        let point = S256Point::generator() * U256::from(3);
+        let key = XOnlyPublicKey::from_point(&point).unwrap();
+
+        assert_eq!(
+            key.to_string(),
+            "f9308a019258c31049344f85f89d5229b531c845836f99b08601f113bce036f9"
+        );
+        assert_eq!(XOnlyPublicKey::from_point(&-point), Some(key));
+
+        let lifted = key.lift_x();
+        assert!(lifted.has_even_y());
+        assert!(lifted == point || lifted == -point);
+        assert_eq!(XOnlyPublicKey::from_point(&S256Point::INFINITY), None);
+    }
+
+    #[test]
+    fn serialize_and_parse() {
+        let key = XOnlyPublicKey::from_point(&S256Point::generator()).unwrap();
+        let bytes = key.serialize();
+
+        assert_eq!(XOnlyPublicKey::parse(&bytes), Ok(key));
+        assert_eq!(
+            XOnlyPublicKey::parse(&bytes[..31]),
+            Err(ParseError::InvalidLength)
+        );
+
+        let mut too_large = [0u8; 32];
+        P.to_big_endian(&mut too_large);
+        assert_eq!(
+            XOnlyPublicKey::parse(&too_large),
+            Err(ParseError::InvalidCoordinate)
+        );
+    }
+}